@@ -5,11 +5,14 @@ extern crate core;
 use std::ffi::c_void;
 
 use std::mem::{transmute, ManuallyDrop};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
 
 use core_graphics::context::CGContext;
-use pax_runtime::api::math::Point2;
 use piet_coregraphics::CoreGraphicsContext;
 
+use crate::core_graphics_layers::LayeredCoreGraphicsContext;
+
 use flexbuffers;
 use flexbuffers::DeserializationError;
 use serde::Serialize;
@@ -21,7 +24,7 @@ use pax_runtime::{ExpressionTable, PaxEngine, Renderer};
 //Note that any types exposed by pax_message must ALSO be added to `PaxCartridge.h`
 //in order to be visible to Swift
 pub use pax_message::*;
-use pax_runtime::api::{Click, ModifierKey, MouseButton, MouseEventArgs, RenderContext};
+use pax_runtime::api::RenderContext;
 
 /// Container data structure for PaxEngine, aggregated to support passing across C bridge
 #[repr(C)] //Exposed to Swift via PaxCartridge.h
@@ -73,7 +76,7 @@ pub extern "C" fn pax_interrupt(
     engine_container: *mut PaxEngineContainer,
     buffer: *const InterruptBuffer,
 ) {
-    let engine = unsafe { Box::from_raw((*engine_container)._engine) };
+    let mut engine = unsafe { Box::from_raw((*engine_container)._engine) };
     // let slice = unsafe { buffer.as_ref().unwrap() };
 
     let length: u64 = unsafe {
@@ -92,35 +95,6 @@ pub extern "C" fn pax_interrupt(
         flexbuffers::from_slice(slice);
     let interrupt = interrupt_wrapped.unwrap();
     match interrupt {
-        NativeInterrupt::Click(args) => {
-            let prospective_hit = engine
-                .runtime_context
-                .get_topmost_element_beneath_ray(Point2::new(args.x, args.y));
-            match prospective_hit {
-                Some(topmost_node) => {
-                    let modifiers = args
-                        .modifiers
-                        .iter()
-                        .map(|x| ModifierKey::from(x))
-                        .collect();
-                    let args_click = Click {
-                        mouse: MouseEventArgs {
-                            x: args.x,
-                            y: args.y,
-                            button: MouseButton::from(args.button),
-                            modifiers,
-                        },
-                    };
-                    topmost_node.dispatch_click(
-                        args_click,
-                        engine.runtime_context.globals(),
-                        &engine.runtime_context,
-                    );
-                }
-                _ => {}
-            };
-        }
-        NativeInterrupt::Scroll(_args) => {}
         NativeInterrupt::Image(args) => match args {
             ImageLoadInterruptArgs::Reference(_ref_args) => {
                 // TODO this needs to be redone since image_map now lives in the
@@ -133,12 +107,20 @@ pub extern "C" fn pax_interrupt(
             }
             ImageLoadInterruptArgs::Data(_) => {}
         },
-        _ => {}
+        interrupt => {
+            engine.handle_interrupt(interrupt);
+        }
     }
 
     unsafe { (*engine_container)._engine = Box::into_raw(engine) };
 }
 
+/// Time on a monotonic clock, measured from the first call
+fn clock_now() -> Duration {
+    static ORIGIN: OnceLock<Instant> = OnceLock::new();
+    ORIGIN.get_or_init(Instant::now).elapsed()
+}
+
 /// Perform full tick of engine, including property computation, lifecycle event handling, and rendering side-effects.
 /// Returns a message queue of native rendering actions encoded as a Flexbuffer via FFI to Swift.
 /// The returned message queue requires explicit deallocation: `pax_deallocate_message_queue`
@@ -154,11 +136,13 @@ pub extern "C" fn pax_tick(
 
     let will_cast_cgContext = cgContext as *mut CGContext;
     let ctx = unsafe { &mut *will_cast_cgContext };
-    let mut render_context = Renderer::new();
+    let mut render_context = Renderer::new().with_layer_compositing().with_native_blur();
 
     (*engine).set_viewport_size((width as f64, height as f64));
-    render_context.add_context("0", CoreGraphicsContext::new_y_up(ctx, height as f64, None));
+    let piet = CoreGraphicsContext::new_y_up(ctx, height as f64, None);
+    render_context.add_context("0", LayeredCoreGraphicsContext::new(piet, cgContext));
 
+    (*engine).set_clock(clock_now());
     let messages = (*engine).tick();
     engine.render(&mut render_context as &mut dyn RenderContext);
 
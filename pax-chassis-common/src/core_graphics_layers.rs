@@ -0,0 +1,252 @@
+//! Group opacity and blur for CoreGraphics render contexts, beyond what `CoreGraphicsContext`
+//! offers: translucent groups are composited in transparency layers, and shapes blurred as
+//! shadows
+
+use std::borrow::Cow;
+use std::ffi::c_void;
+
+use core_graphics::base::CGFloat;
+use core_graphics::geometry::{CGAffineTransform, CGSize};
+use pax_runtime::{LayerCompositing, NativeBlur};
+use piet::kurbo::{Affine, BezPath, Point, Rect, Shape};
+use piet::{Color, Error, FixedGradient, ImageFormat, InterpolationMode, IntoBrush, StrokeStyle};
+use piet_coregraphics::CoreGraphicsContext;
+
+#[link(name = "CoreGraphics", kind = "framework")]
+extern "C" {
+    fn CGContextSetAlpha(c: *mut c_void, alpha: CGFloat);
+    fn CGContextBeginTransparencyLayer(c: *mut c_void, auxiliary_info: *const c_void);
+    fn CGContextEndTransparencyLayer(c: *mut c_void);
+    fn CGContextGetCTM(c: *mut c_void) -> CGAffineTransform;
+    fn CGContextSetShadowWithColor(
+        c: *mut c_void,
+        offset: CGSize,
+        blur: CGFloat,
+        color: *const c_void,
+    );
+    fn CGColorCreateSRGB(
+        red: CGFloat,
+        green: CGFloat,
+        blue: CGFloat,
+        alpha: CGFloat,
+    ) -> *const c_void;
+    fn CGColorRelease(color: *const c_void);
+}
+
+/// A `CoreGraphicsContext` that draws each pushed layer into a CoreGraphics transparency
+/// layer, composited onto the content beneath at the context's alpha, and blurs shapes as the
+/// shadows of copies drawn out of sight
+pub struct LayeredCoreGraphicsContext<'a> {
+    piet: CoreGraphicsContext<'a>,
+    /// The `CGContextRef` `piet` draws into
+    cg_context: *mut c_void,
+}
+
+impl<'a> LayeredCoreGraphicsContext<'a> {
+    /// `cg_context` is the `CGContextRef` `piet` was created for
+    pub fn new(piet: CoreGraphicsContext<'a>, cg_context: *mut c_void) -> Self {
+        Self { piet, cg_context }
+    }
+
+    /// Draws the shadow, in `color` and blurred by `blur_radius`, of what `draw` draws within
+    /// `bounds` given a transform that moves it beside the context's bounds: CoreGraphics blurs
+    /// the shadows of any shape, but not shapes themselves
+    fn draw_shadow(
+        &mut self,
+        bounds: Rect,
+        color: Color,
+        blur_radius: f64,
+        draw: impl FnOnce(&mut CoreGraphicsContext<'a>, Affine),
+    ) {
+        let ctm = unsafe { CGContextGetCTM(self.cg_context) };
+        let transform = Affine::new([ctm.a, ctm.b, ctm.c, ctm.d, ctm.tx, ctm.ty]);
+        if transform.determinant() == 0.0 {
+            return;
+        }
+        // Shadows are offset and blurred in device space, whatever the transform
+        let scale = transform.determinant().abs().sqrt();
+        let offset = transform.transform_rect_bbox(bounds).x1.max(0.0).ceil() + 1.0;
+        let beside = transform.inverse() * Affine::translate((-offset, 0.0)) * transform;
+        let (r, g, b, a) = color.as_rgba();
+        if piet::RenderContext::save(&mut self.piet).is_err() {
+            return;
+        }
+        unsafe {
+            let cg_color = CGColorCreateSRGB(r, g, b, a);
+            // As on the web, a shadow's blur is twice its standard deviation
+            CGContextSetShadowWithColor(
+                self.cg_context,
+                CGSize::new(offset, 0.0),
+                2.0 * blur_radius * scale,
+                cg_color,
+            );
+            CGColorRelease(cg_color);
+        }
+        draw(&mut self.piet, beside);
+        let _ = piet::RenderContext::restore(&mut self.piet);
+    }
+}
+
+impl LayerCompositing for LayeredCoreGraphicsContext<'_> {
+    fn push_layer(&mut self, opacity: f64) {
+        // The alpha the layer is composited at is that of the state it's begun in, restored
+        // along with the state saved before the layer was pushed
+        unsafe {
+            CGContextSetAlpha(self.cg_context, opacity as CGFloat);
+            CGContextBeginTransparencyLayer(self.cg_context, std::ptr::null());
+        }
+    }
+
+    fn pop_layer(&mut self) {
+        unsafe { CGContextEndTransparencyLayer(self.cg_context) };
+    }
+}
+
+impl NativeBlur for LayeredCoreGraphicsContext<'_> {
+    fn fill_blurred(&mut self, path: BezPath, color: Color, blur_radius: f64) {
+        let bounds = path.bounding_box();
+        self.draw_shadow(bounds, color, blur_radius, |cg, beside| {
+            piet::RenderContext::fill(cg, beside * path, &Color::BLACK)
+        });
+    }
+
+    fn stroke_blurred(&mut self, path: BezPath, color: Color, width: f64, blur_radius: f64) {
+        let bounds = path.bounding_box().inflate(width / 2.0, width / 2.0);
+        self.draw_shadow(bounds, color, blur_radius, |cg, beside| {
+            piet::RenderContext::stroke(cg, beside * path, &Color::BLACK, width)
+        });
+    }
+}
+
+/// A `CoreGraphicsContext` brush
+#[derive(Clone)]
+pub struct LayerBrush(<CoreGraphicsContext<'static> as piet::RenderContext>::Brush);
+
+impl<'a> IntoBrush<LayeredCoreGraphicsContext<'a>> for LayerBrush {
+    fn make_brush<'b>(
+        &'b self,
+        _piet: &mut LayeredCoreGraphicsContext<'a>,
+        _bbox: impl FnOnce() -> Rect,
+    ) -> Cow<'b, LayerBrush> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl<'a> piet::RenderContext for LayeredCoreGraphicsContext<'a> {
+    type Brush = LayerBrush;
+    type Text = <CoreGraphicsContext<'a> as piet::RenderContext>::Text;
+    type TextLayout = <CoreGraphicsContext<'a> as piet::RenderContext>::TextLayout;
+    type Image = <CoreGraphicsContext<'a> as piet::RenderContext>::Image;
+
+    fn status(&mut self) -> Result<(), Error> {
+        self.piet.status()
+    }
+
+    fn solid_brush(&mut self, color: Color) -> Self::Brush {
+        LayerBrush(self.piet.solid_brush(color))
+    }
+
+    fn gradient(&mut self, gradient: impl Into<FixedGradient>) -> Result<Self::Brush, Error> {
+        self.piet.gradient(gradient).map(LayerBrush)
+    }
+
+    fn clear(&mut self, region: impl Into<Option<Rect>>, color: Color) {
+        self.piet.clear(region, color)
+    }
+
+    fn stroke(&mut self, shape: impl Shape, brush: &impl IntoBrush<Self>, width: f64) {
+        let brush = brush.make_brush(self, || shape.bounding_box());
+        self.piet.stroke(shape, &brush.0, width)
+    }
+
+    fn stroke_styled(
+        &mut self,
+        shape: impl Shape,
+        brush: &impl IntoBrush<Self>,
+        width: f64,
+        style: &StrokeStyle,
+    ) {
+        let brush = brush.make_brush(self, || shape.bounding_box());
+        self.piet.stroke_styled(shape, &brush.0, width, style)
+    }
+
+    fn fill(&mut self, shape: impl Shape, brush: &impl IntoBrush<Self>) {
+        let brush = brush.make_brush(self, || shape.bounding_box());
+        self.piet.fill(shape, &brush.0)
+    }
+
+    fn fill_even_odd(&mut self, shape: impl Shape, brush: &impl IntoBrush<Self>) {
+        let brush = brush.make_brush(self, || shape.bounding_box());
+        self.piet.fill_even_odd(shape, &brush.0)
+    }
+
+    fn clip(&mut self, shape: impl Shape) {
+        self.piet.clip(shape)
+    }
+
+    fn text(&mut self) -> &mut Self::Text {
+        self.piet.text()
+    }
+
+    fn draw_text(&mut self, layout: &Self::TextLayout, pos: impl Into<Point>) {
+        self.piet.draw_text(layout, pos)
+    }
+
+    fn save(&mut self) -> Result<(), Error> {
+        self.piet.save()
+    }
+
+    fn restore(&mut self) -> Result<(), Error> {
+        self.piet.restore()
+    }
+
+    fn finish(&mut self) -> Result<(), Error> {
+        self.piet.finish()
+    }
+
+    fn transform(&mut self, transform: Affine) {
+        self.piet.transform(transform)
+    }
+
+    fn make_image(
+        &mut self,
+        width: usize,
+        height: usize,
+        buf: &[u8],
+        format: ImageFormat,
+    ) -> Result<Self::Image, Error> {
+        self.piet.make_image(width, height, buf, format)
+    }
+
+    fn draw_image(
+        &mut self,
+        image: &Self::Image,
+        dst_rect: impl Into<Rect>,
+        interp: InterpolationMode,
+    ) {
+        self.piet.draw_image(image, dst_rect, interp)
+    }
+
+    fn draw_image_area(
+        &mut self,
+        image: &Self::Image,
+        src_rect: impl Into<Rect>,
+        dst_rect: impl Into<Rect>,
+        interp: InterpolationMode,
+    ) {
+        self.piet.draw_image_area(image, src_rect, dst_rect, interp)
+    }
+
+    fn capture_image_area(&mut self, src_rect: impl Into<Rect>) -> Result<Self::Image, Error> {
+        self.piet.capture_image_area(src_rect)
+    }
+
+    fn blurred_rect(&mut self, rect: Rect, blur_radius: f64, brush: &impl IntoBrush<Self>) {
+        let brush = brush.make_brush(self, || rect);
+        self.piet.blurred_rect(rect, blur_radius, &brush.0)
+    }
+
+    fn current_transform(&self) -> Affine {
+        self.piet.current_transform()
+    }
+}
@@ -1 +1,2 @@
 pub mod core_graphics_c_bridge;
+mod core_graphics_layers;
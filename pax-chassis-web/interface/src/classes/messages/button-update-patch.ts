@@ -7,6 +7,10 @@ export class ButtonUpdatePatch {
     public size_x?: number;
     public size_y?: number;
     public transform?: number[];
+    public opacity?: number;
+    public visible?: boolean;
+    public pointer_events?: boolean;
+    public clip_path?: string;
     public content?: string;
     public style?: TextStyle;
 
@@ -22,6 +26,10 @@ export class ButtonUpdatePatch {
         this.size_x = jsonMessage["size_x"];
         this.size_y = jsonMessage["size_y"];
         this.transform = jsonMessage["transform"];
+        this.opacity = jsonMessage["opacity"];
+        this.visible = jsonMessage["visible"];
+        this.pointer_events = jsonMessage["pointer_events"];
+        this.clip_path = jsonMessage["clip_path"];
         const styleMessage = jsonMessage["style"];
 
         if (styleMessage) {
@@ -35,6 +43,10 @@ export class ButtonUpdatePatch {
         this.size_x = 0;
         this.size_y = 0;
         this.transform = [];
+        this.opacity = undefined;
+        this.visible = undefined;
+        this.pointer_events = undefined;
+        this.clip_path = undefined;
         this.objectManager.returnToPool(TEXT_STYLE, this.style);
         this.style = undefined;
     }
@@ -5,6 +5,10 @@ export class CheckboxUpdatePatch {
     public size_x?: number;
     public size_y?: number;
     public transform?: number[];
+    public opacity?: number;
+    public visible?: boolean;
+    public pointer_events?: boolean;
+    public clip_path?: string;
     public checked?: boolean;
     objectManager: ObjectManager;
 
@@ -17,6 +21,10 @@ export class CheckboxUpdatePatch {
         this.size_x = jsonMessage["size_x"];
         this.size_y = jsonMessage["size_y"];
         this.transform = jsonMessage["transform"];
+        this.opacity = jsonMessage["opacity"];
+        this.visible = jsonMessage["visible"];
+        this.pointer_events = jsonMessage["pointer_events"];
+        this.clip_path = jsonMessage["clip_path"];
         this.checked = jsonMessage["checked"];
     }
 
@@ -25,6 +33,10 @@ export class CheckboxUpdatePatch {
         this.size_x = 0;
         this.size_y = 0;
         this.transform = [];
+        this.opacity = undefined;
+        this.visible = undefined;
+        this.pointer_events = undefined;
+        this.clip_path = undefined;
         this.checked = undefined;
     }
 }
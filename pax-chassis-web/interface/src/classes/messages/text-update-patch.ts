@@ -8,6 +8,10 @@ export class TextUpdatePatch {
     public size_x?: number;
     public size_y?: number;
     public transform?: number[];
+    public opacity?: number;
+    public visible?: boolean;
+    public pointer_events?: boolean;
+    public clip_path?: string;
     public style?: TextStyle;
     public style_link?: TextStyle;
     public depth?: number;
@@ -24,6 +28,10 @@ export class TextUpdatePatch {
         this.size_x = jsonMessage["size_x"];
         this.size_y = jsonMessage["size_y"];
         this.transform = jsonMessage["transform"];
+        this.opacity = jsonMessage["opacity"];
+        this.visible = jsonMessage["visible"];
+        this.pointer_events = jsonMessage["pointer_events"];
+        this.clip_path = jsonMessage["clip_path"];
         this.depth = jsonMessage["depth"];
         this.editable = jsonMessage["editable"];
 
@@ -46,6 +54,10 @@ export class TextUpdatePatch {
         this.size_x = 0;
         this.size_y = 0;
         this.transform = [];
+        this.opacity = undefined;
+        this.visible = undefined;
+        this.pointer_events = undefined;
+        this.clip_path = undefined;
         this.objectManager.returnToPool(TEXT_STYLE, this.style);
         this.style = undefined;
         this.objectManager.returnToPool(TEXT_STYLE, this.style_link);
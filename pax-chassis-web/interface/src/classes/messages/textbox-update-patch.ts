@@ -12,6 +12,10 @@ export class TextboxUpdatePatch {
     public border_radius: number;
     public focus_on_mount?: boolean;
     public transform?: number[];
+    public opacity?: number;
+    public visible?: boolean;
+    public pointer_events?: boolean;
+    public clip_path?: string;
     public text?: string;
     objectManager: ObjectManager;
     public style?: TextStyle;
@@ -25,6 +29,10 @@ export class TextboxUpdatePatch {
         this.size_x = jsonMessage["size_x"];
         this.size_y = jsonMessage["size_y"];
         this.transform = jsonMessage["transform"];
+        this.opacity = jsonMessage["opacity"];
+        this.visible = jsonMessage["visible"];
+        this.pointer_events = jsonMessage["pointer_events"];
+        this.clip_path = jsonMessage["clip_path"];
         this.text = jsonMessage["text"];
         this.stroke_color = jsonMessage["stroke_color"];
         this.stroke_width = jsonMessage["stroke_width"];
@@ -44,6 +52,10 @@ export class TextboxUpdatePatch {
         this.size_x = 0;
         this.size_y = 0;
         this.transform = [];
+        this.opacity = undefined;
+        this.visible = undefined;
+        this.pointer_events = undefined;
+        this.clip_path = undefined;
         this.text = "";
     }
 }
@@ -13,7 +13,7 @@ import {ImageLoadPatch} from "./messages/image-load-patch";
 import {OcclusionContext} from "./occlusion-context";
 import {ObjectManager} from "../pools/object-manager";
 import {INPUT, BUTTON, DIV, OBJECT, OCCLUSION_CONTEXT, SCROLLER} from "../pools/supported-objects";
-import {arrayToKey, packAffineCoeffsIntoMatrix3DString, readImageToByteBuffer} from "../utils/helpers";
+import {arrayToKey, getCssCursor, packAffineCoeffsIntoMatrix3DString, readImageToByteBuffer} from "../utils/helpers";
 import {ColorGroup, TextStyle, getAlignItems, getJustifyContent, getTextAlign} from "./text";
 import type {PaxChassisWeb} from "../types/pax-chassis-web";
 import { CheckboxUpdatePatch } from "./messages/checkbox-update-patch";
@@ -29,6 +29,7 @@ export class NativeElementPool {
     registeredFontFaces: Set<string>;
     messageList:string[] = [];
     private isMobile = false;
+    private mount?: HTMLElement;
 
     constructor(objectManager: ObjectManager) {
         this.objectManager = objectManager;
@@ -41,6 +42,7 @@ export class NativeElementPool {
     build(chassis: PaxChassisWeb, isMobile: boolean, mount: Element){
         this.isMobile = isMobile;
         this.chassis = chassis;
+        this.mount = mount as HTMLElement;
         this.baseOcclusionContext.build(mount, undefined, chassis, this.canvases);
     }
 
@@ -96,6 +98,12 @@ export class NativeElementPool {
         });
     }
 
+    cursorUpdate(cursor: string) {
+        if (this.mount) {
+            this.mount.style.cursor = getCssCursor(cursor);
+        }
+    }
+
     occlusionUpdate(patch: OcclusionUpdatePatch) {
         // @ts-ignore
         let node = this.textNodes[patch.idChain];
@@ -173,6 +181,18 @@ export class NativeElementPool {
         if (patch.transform != null) {
             leaf.style.transform = packAffineCoeffsIntoMatrix3DString(patch.transform);
         }
+        if (patch.opacity != null) {
+            leaf.style.opacity = patch.opacity.toString();
+        }
+        if (patch.visible != null) {
+            leaf.style.visibility = patch.visible ? "" : "hidden";
+        }
+        if (patch.pointer_events != null) {
+            leaf.style.pointerEvents = patch.pointer_events ? "" : "none";
+        }
+        if (patch.clip_path != null) {
+            leaf.style.clipPath = patch.clip_path ? `path("${patch.clip_path}")` : "";
+        }
     }
 
     checkboxDelete(id_chain: number[]) {
@@ -294,6 +314,18 @@ export class NativeElementPool {
         if (patch.transform != null) {
             leaf.style.transform = packAffineCoeffsIntoMatrix3DString(patch.transform);
         }
+        if (patch.opacity != null) {
+            leaf.style.opacity = patch.opacity.toString();
+        }
+        if (patch.visible != null) {
+            leaf.style.visibility = patch.visible ? "" : "hidden";
+        }
+        if (patch.pointer_events != null) {
+            leaf.style.pointerEvents = patch.pointer_events ? "" : "none";
+        }
+        if (patch.clip_path != null) {
+            leaf.style.clipPath = patch.clip_path ? `path("${patch.clip_path}")` : "";
+        }
 
         if (patch.focus_on_mount) {
             setTimeout(() => { textbox.focus(); }, 10);
@@ -384,6 +416,22 @@ export class NativeElementPool {
         if (patch.transform != null) {
             leaf.style.transform = packAffineCoeffsIntoMatrix3DString(patch.transform);
         }
+        if (patch.opacity != null) {
+            leaf.style.opacity = patch.opacity.toString();
+        }
+        if (patch.visible != null) {
+            leaf.style.visibility = patch.visible ? "" : "hidden";
+        }
+        if (patch.pointer_events != null) {
+            leaf.style.pointerEvents = patch.pointer_events ? "" : "none";
+        }
+        if (patch.clip_path != null) {
+            leaf.style.clipPath = patch.clip_path ? `path("${patch.clip_path}")` : "";
+        }
+
+        if (patch.content != null || patch.style != null || patch.size_x != null) {
+            this.reportMeasure(patch.id_chain!, button);
+        }
     }
 
     buttonDelete(id_chain: number[]) {
@@ -461,6 +509,18 @@ export class NativeElementPool {
         if (patch.transform != null) {
             leaf.style.transform = packAffineCoeffsIntoMatrix3DString(patch.transform);
         }
+        if (patch.opacity != null) {
+            leaf.style.opacity = patch.opacity.toString();
+        }
+        if (patch.visible != null) {
+            leaf.style.visibility = patch.visible ? "" : "hidden";
+        }
+        if (patch.pointer_events != null) {
+            leaf.style.pointerEvents = patch.pointer_events ? "" : "none";
+        }
+        if (patch.clip_path != null) {
+            leaf.style.clipPath = patch.clip_path ? `path("${patch.clip_path}")` : "";
+        }
 
         if (patch.editable != null) {
             textChild.setAttribute("contenteditable", patch.editable.toString());
@@ -519,6 +579,32 @@ export class NativeElementPool {
                 });
             }
         }
+
+        if (patch.content != null || patch.style != null || patch.size_x != null) {
+            this.reportMeasure(patch.id_chain!, textChild);
+        }
+    }
+
+    // Reports the natural size of a native element's content to the engine, for `Size::Auto`
+    // widths and heights: its width without wrapping, and its height at its current width.
+    // Offsets are used over bounding rects, which include the leaf's transform.
+    private reportMeasure(idChain: number[], element: HTMLElement) {
+        const width = element.style.width;
+        const height = element.style.height;
+        element.style.width = "max-content";
+        const naturalWidth = element.offsetWidth;
+        element.style.width = width;
+        element.style.height = "auto";
+        const naturalHeight = element.offsetHeight;
+        element.style.height = height;
+        let message = {
+            "Measure": {
+                "id_chain": idChain,
+                "width": naturalWidth,
+                "height": naturalHeight,
+            }
+        };
+        this.chassis!.interrupt(JSON.stringify(message), undefined);
     }
 
     textDelete(id_chain: number[]) {
@@ -139,6 +139,11 @@ export function setupEventListeners(chassis: PaxChassisWeb) {
     }, true);
     // @ts-ignore
     window.addEventListener('mouseout', (evt) => {
+        // Hover within the viewport is tracked by the engine from mouse moves; only report the
+        // pointer leaving the page altogether
+        if (evt.relatedTarget !== null) {
+            return;
+        }
         let event = {
             "MouseOut": {
                 "x": evt.clientX,
@@ -219,7 +224,10 @@ export function setupEventListeners(chassis: PaxChassisWeb) {
                 "is_repeat": evt.repeat
             }
         };
-        chassis.interrupt(JSON.stringify(event), []);
+        let res = chassis.interrupt(JSON.stringify(event), []);
+        if (res.prevent_default) {
+            evt.preventDefault();
+        }
     }, true);
     // @ts-ignore
     window.addEventListener('keyup', (evt) => {
@@ -87,7 +87,7 @@ function renderLoop (chassis: PaxChassisWeb, mount: Element, get_latest_memory:
     nativePool.sendScrollerValues();
     nativePool.clearCanvases();
 
-    const memorySliceSpec = chassis.tick();
+    const memorySliceSpec = chassis.tick(performance.now());
     const latestMemory : WebAssembly.Memory = get_latest_memory();
     const memoryBuffer = new Uint8Array(latestMemory.buffer);
 
@@ -210,6 +210,9 @@ export function processMessages(messages: any[], chassis: PaxChassisWeb, objectM
         }else if (unwrapped_msg["ScrollerDelete"]) {
             let msg = unwrapped_msg["ScrollerDelete"];
             nativePool.scrollerDelete(msg)
+        }else if (unwrapped_msg["CursorUpdate"]) {
+            let msg = unwrapped_msg["CursorUpdate"];
+            nativePool.cursorUpdate(msg);
         }
     })
 }
@@ -114,3 +114,8 @@ export function generateLocationId(scrollerId: number[] | undefined, zIndex: num
 export function arrayToKey(arr: number[]): string {
     return arr.join(',');
 }
+
+// Cursor variants are named after their CSS keywords, e.g. `NwseResize` for `nwse-resize`
+export function getCssCursor(cursor: string): string {
+    return cursor.replace(/([a-z])([A-Z])/g, "$1-$2").toLowerCase();
+}
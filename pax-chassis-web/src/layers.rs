@@ -0,0 +1,310 @@
+//! Group opacity and blur for canvas render contexts, beyond what `WebRenderContext` offers:
+//! translucent groups are composited offscreen, and shapes blurred as shadows
+
+use std::borrow::Cow;
+
+use pax_runtime::{LayerCompositing, NativeBlur};
+use piet::kurbo::{Affine, BezPath, Point, Rect, Shape};
+use piet::{Color, Error, FixedGradient, ImageFormat, InterpolationMode, IntoBrush, StrokeStyle};
+use piet_web::WebRenderContext;
+use wasm_bindgen::JsCast;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, Window};
+
+type Web = WebRenderContext<'static>;
+
+/// A canvas and the render contexts drawing into it
+struct Surface {
+    canvas: HtmlCanvasElement,
+    context: CanvasRenderingContext2d,
+    piet: Web,
+}
+
+impl Surface {
+    fn new(canvas: HtmlCanvasElement, context: CanvasRenderingContext2d, window: &Window) -> Self {
+        let piet = WebRenderContext::new(context.clone(), window.clone());
+        Surface {
+            canvas,
+            context,
+            piet,
+        }
+    }
+
+    fn offscreen(window: &Window) -> Self {
+        let canvas = window
+            .document()
+            .unwrap()
+            .create_element("canvas")
+            .unwrap()
+            .dyn_into::<HtmlCanvasElement>()
+            .unwrap();
+        let context = canvas
+            .get_context("2d")
+            .unwrap()
+            .unwrap()
+            .dyn_into::<CanvasRenderingContext2d>()
+            .unwrap();
+        Surface::new(canvas, context, window)
+    }
+}
+
+/// A `WebRenderContext` drawing into an on-screen canvas, that draws each pushed layer into an
+/// offscreen canvas of the same size, composited onto the canvas beneath with its `globalAlpha`,
+/// and blurs shapes as the shadows of copies drawn beside the canvas
+pub struct LayeredWebRenderContext {
+    window: Window,
+    base: Surface,
+    /// The offscreen layers drawn into instead of `base`, innermost last, each with the opacity
+    /// it's to be composited at
+    layers: Vec<(Surface, f64)>,
+    /// Offscreen canvases of popped layers, reused by later layers
+    spare_layers: Vec<Surface>,
+}
+
+impl LayeredWebRenderContext {
+    pub fn new(
+        canvas: HtmlCanvasElement,
+        context: CanvasRenderingContext2d,
+        window: Window,
+    ) -> Self {
+        Self {
+            base: Surface::new(canvas, context, &window),
+            window,
+            layers: Vec::new(),
+            spare_layers: Vec::new(),
+        }
+    }
+
+    fn top(&self) -> &Surface {
+        self.layers.last().map_or(&self.base, |(layer, _)| layer)
+    }
+
+    fn top_mut(&mut self) -> &mut Web {
+        match self.layers.last_mut() {
+            Some((layer, _)) => &mut layer.piet,
+            None => &mut self.base.piet,
+        }
+    }
+
+    /// Draws the shadow, in `color` and blurred by `blur_radius`, of what `draw` draws within
+    /// `bounds` given a transform that moves it beside the canvas: canvases blur the shadows of
+    /// any shape, but not shapes themselves
+    fn draw_shadow(
+        &mut self,
+        bounds: Rect,
+        color: Color,
+        blur_radius: f64,
+        draw: impl FnOnce(&mut Web, Affine),
+    ) {
+        let Ok(m) = self.top().context.get_transform() else {
+            return;
+        };
+        let transform = Affine::new([m.a(), m.b(), m.c(), m.d(), m.e(), m.f()]);
+        if transform.determinant() == 0.0 {
+            return;
+        }
+        // Shadows are offset and blurred in canvas pixels, whatever the transform
+        let scale = transform.determinant().abs().sqrt();
+        let offset = transform.transform_rect_bbox(bounds).x1.max(0.0).ceil() + 1.0;
+        let beside = transform.inverse() * Affine::translate((-offset, 0.0)) * transform;
+        let (r, g, b, a) = color.as_rgba8();
+        let context = &self.top().context;
+        context.save();
+        context.set_shadow_color(&format!("rgba({}, {}, {}, {})", r, g, b, a as f64 / 255.0));
+        // A shadow's blur is twice its standard deviation
+        context.set_shadow_blur(2.0 * blur_radius * scale);
+        context.set_shadow_offset_x(offset);
+        draw(self.top_mut(), beside);
+        self.top().context.restore();
+    }
+}
+
+impl LayerCompositing for LayeredWebRenderContext {
+    fn push_layer(&mut self, opacity: f64) {
+        let (width, height) = (self.base.canvas.width(), self.base.canvas.height());
+        let transform = self.top().context.get_transform();
+        let layer = self
+            .spare_layers
+            .pop()
+            .unwrap_or_else(|| Surface::offscreen(&self.window));
+        if layer.canvas.width() != width || layer.canvas.height() != height {
+            layer.canvas.set_width(width);
+            layer.canvas.set_height(height);
+        }
+        let _ = layer.context.set_transform(1.0, 0.0, 0.0, 1.0, 0.0, 0.0);
+        layer
+            .context
+            .clear_rect(0.0, 0.0, width as f64, height as f64);
+        // Draws into the layer are transformed as they would be beneath it
+        if let Ok(m) = transform {
+            let _ = layer
+                .context
+                .set_transform(m.a(), m.b(), m.c(), m.d(), m.e(), m.f());
+        }
+        self.layers.push((layer, opacity));
+    }
+
+    fn pop_layer(&mut self) {
+        let Some((layer, opacity)) = self.layers.pop() else {
+            return;
+        };
+        // Both canvases have the same size, so the layer is drawn untransformed; the clip in
+        // effect beneath it still applies
+        let beneath = &self.top().context;
+        beneath.save();
+        let _ = beneath.set_transform(1.0, 0.0, 0.0, 1.0, 0.0, 0.0);
+        beneath.set_global_alpha(opacity);
+        let _ = beneath.draw_image_with_html_canvas_element(&layer.canvas, 0.0, 0.0);
+        beneath.restore();
+        self.spare_layers.push(layer);
+    }
+}
+
+impl NativeBlur for LayeredWebRenderContext {
+    fn fill_blurred(&mut self, path: BezPath, color: Color, blur_radius: f64) {
+        let bounds = path.bounding_box();
+        self.draw_shadow(bounds, color, blur_radius, |web, beside| {
+            piet::RenderContext::fill(web, beside * path, &Color::BLACK)
+        });
+    }
+
+    fn stroke_blurred(&mut self, path: BezPath, color: Color, width: f64, blur_radius: f64) {
+        let bounds = path.bounding_box().inflate(width / 2.0, width / 2.0);
+        self.draw_shadow(bounds, color, blur_radius, |web, beside| {
+            piet::RenderContext::stroke(web, beside * path, &Color::BLACK, width)
+        });
+    }
+}
+
+/// A `WebRenderContext` brush, usable in whichever layer is drawn into
+#[derive(Clone)]
+pub struct LayerBrush(<Web as piet::RenderContext>::Brush);
+
+impl IntoBrush<LayeredWebRenderContext> for LayerBrush {
+    fn make_brush<'a>(
+        &'a self,
+        _piet: &mut LayeredWebRenderContext,
+        _bbox: impl FnOnce() -> Rect,
+    ) -> Cow<'a, LayerBrush> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl piet::RenderContext for LayeredWebRenderContext {
+    type Brush = LayerBrush;
+    type Text = <Web as piet::RenderContext>::Text;
+    type TextLayout = <Web as piet::RenderContext>::TextLayout;
+    type Image = <Web as piet::RenderContext>::Image;
+
+    fn status(&mut self) -> Result<(), Error> {
+        self.top_mut().status()
+    }
+
+    fn solid_brush(&mut self, color: Color) -> Self::Brush {
+        LayerBrush(self.top_mut().solid_brush(color))
+    }
+
+    fn gradient(&mut self, gradient: impl Into<FixedGradient>) -> Result<Self::Brush, Error> {
+        self.top_mut().gradient(gradient).map(LayerBrush)
+    }
+
+    fn clear(&mut self, region: impl Into<Option<Rect>>, color: Color) {
+        self.top_mut().clear(region, color)
+    }
+
+    fn stroke(&mut self, shape: impl Shape, brush: &impl IntoBrush<Self>, width: f64) {
+        let brush = brush.make_brush(self, || shape.bounding_box());
+        self.top_mut().stroke(shape, &brush.0, width)
+    }
+
+    fn stroke_styled(
+        &mut self,
+        shape: impl Shape,
+        brush: &impl IntoBrush<Self>,
+        width: f64,
+        style: &StrokeStyle,
+    ) {
+        let brush = brush.make_brush(self, || shape.bounding_box());
+        self.top_mut().stroke_styled(shape, &brush.0, width, style)
+    }
+
+    fn fill(&mut self, shape: impl Shape, brush: &impl IntoBrush<Self>) {
+        let brush = brush.make_brush(self, || shape.bounding_box());
+        self.top_mut().fill(shape, &brush.0)
+    }
+
+    fn fill_even_odd(&mut self, shape: impl Shape, brush: &impl IntoBrush<Self>) {
+        let brush = brush.make_brush(self, || shape.bounding_box());
+        self.top_mut().fill_even_odd(shape, &brush.0)
+    }
+
+    fn clip(&mut self, shape: impl Shape) {
+        self.top_mut().clip(shape)
+    }
+
+    fn text(&mut self) -> &mut Self::Text {
+        self.top_mut().text()
+    }
+
+    fn draw_text(&mut self, layout: &Self::TextLayout, pos: impl Into<Point>) {
+        self.top_mut().draw_text(layout, pos)
+    }
+
+    fn save(&mut self) -> Result<(), Error> {
+        self.top_mut().save()
+    }
+
+    fn restore(&mut self) -> Result<(), Error> {
+        self.top_mut().restore()
+    }
+
+    fn finish(&mut self) -> Result<(), Error> {
+        self.top_mut().finish()
+    }
+
+    fn transform(&mut self, transform: Affine) {
+        self.top_mut().transform(transform)
+    }
+
+    fn make_image(
+        &mut self,
+        width: usize,
+        height: usize,
+        buf: &[u8],
+        format: ImageFormat,
+    ) -> Result<Self::Image, Error> {
+        self.top_mut().make_image(width, height, buf, format)
+    }
+
+    fn draw_image(
+        &mut self,
+        image: &Self::Image,
+        dst_rect: impl Into<Rect>,
+        interp: InterpolationMode,
+    ) {
+        self.top_mut().draw_image(image, dst_rect, interp)
+    }
+
+    fn draw_image_area(
+        &mut self,
+        image: &Self::Image,
+        src_rect: impl Into<Rect>,
+        dst_rect: impl Into<Rect>,
+        interp: InterpolationMode,
+    ) {
+        self.top_mut()
+            .draw_image_area(image, src_rect, dst_rect, interp)
+    }
+
+    fn capture_image_area(&mut self, src_rect: impl Into<Rect>) -> Result<Self::Image, Error> {
+        self.top_mut().capture_image_area(src_rect)
+    }
+
+    fn blurred_rect(&mut self, rect: Rect, blur_radius: f64, brush: &impl IntoBrush<Self>) {
+        let brush = brush.make_brush(self, || rect);
+        self.top_mut().blurred_rect(rect, blur_radius, &brush.0)
+    }
+
+    fn current_transform(&self) -> Affine {
+        self.top().piet.current_transform()
+    }
+}
@@ -3,31 +3,22 @@
 use js_sys::Uint8Array;
 use log::Level;
 use pax_message::ImageLoadInterruptArgs;
-use pax_runtime::api::math::Point2;
-use pax_runtime::api::ButtonClick;
-use pax_runtime::api::CheckboxChange;
 use pax_runtime::api::RenderContext;
-use pax_runtime::api::TextInput;
-use pax_runtime::api::TextboxChange;
-use pax_runtime::api::TextboxInput;
 use pax_runtime::ExpressionTable;
 use std::cell::RefCell;
 
 use std::rc::Rc;
+use std::time::Duration;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use web_sys::{window, HtmlCanvasElement};
 
-use piet_web::WebRenderContext;
-
 use pax_runtime::{PaxEngine, Renderer};
 
+mod layers;
+use layers::LayeredWebRenderContext;
+
 use pax_message::NativeInterrupt;
-use pax_runtime::api::{
-    Clap, Click, ContextMenu, DoubleClick, KeyDown, KeyPress, KeyUp, KeyboardEventArgs,
-    ModifierKey, MouseButton, MouseDown, MouseEventArgs, MouseMove, MouseOut, MouseOver, MouseUp,
-    Touch, TouchEnd, TouchMove, TouchStart, Wheel,
-};
 use serde_json;
 
 #[cfg(feature = "designtime")]
@@ -48,7 +39,7 @@ pub fn wasm_memory() -> JsValue {
 
 #[wasm_bindgen]
 pub struct PaxChassisWeb {
-    drawing_contexts: Renderer<WebRenderContext<'static>>,
+    drawing_contexts: Renderer<LayeredWebRenderContext>,
     engine: Rc<RefCell<PaxEngine>>,
     #[cfg(feature = "designtime")]
     definition_to_instance_traverser: pax_cartridge::DefinitionToInstanceTraverser,
@@ -100,7 +91,7 @@ impl PaxChassisWeb {
             let engine_container: Rc<RefCell<PaxEngine>> = Rc::new(RefCell::new(engine));
             Self {
                 engine: engine_container,
-                drawing_contexts: Renderer::new(),
+                drawing_contexts: Renderer::new().with_layer_compositing().with_native_blur(),
                 definition_to_instance_traverser,
                 designtime_manager,
                 last_manifest_version_rendered: 0,
@@ -118,7 +109,7 @@ impl PaxChassisWeb {
 
             Self {
                 engine: engine_container,
-                drawing_contexts: Renderer::new(),
+                drawing_contexts: Renderer::new().with_layer_compositing().with_native_blur(),
             }
         }
     }
@@ -146,7 +137,7 @@ impl PaxChassisWeb {
         canvas.set_height(height as u32);
         let _ = context.scale(dpr, dpr);
 
-        let render_context = WebRenderContext::new(context, window.clone());
+        let render_context = LayeredWebRenderContext::new(canvas, context, window.clone());
         self.drawing_contexts.add_context(&id, render_context);
     }
 
@@ -164,8 +155,6 @@ impl PaxChassisWeb {
     ) -> InterruptResult {
         let x: NativeInterrupt = serde_json::from_str(&native_interrupt).unwrap();
 
-        let engine = self.engine.borrow_mut();
-        let globals = engine.runtime_context.globals();
         let prevent_default = match x {
             NativeInterrupt::Image(args) => match args {
                 ImageLoadInterruptArgs::Reference(_ref_args) => false,
@@ -180,392 +169,7 @@ impl PaxChassisWeb {
                     false
                 }
             },
-            NativeInterrupt::FormButtonClick(args) => {
-                let node = engine
-                    .get_expanded_node(args.id_chain[0])
-                    .expect("button node exists in engine");
-                node.dispatch_button_click(ButtonClick {}, globals, &engine.runtime_context)
-            }
-            NativeInterrupt::FormTextboxInput(args) => {
-                let node = engine
-                    .get_expanded_node(args.id_chain[0])
-                    .expect("textbox node exists in engine");
-                node.dispatch_textbox_input(
-                    TextboxInput { text: args.text },
-                    globals,
-                    &engine.runtime_context,
-                )
-            }
-            NativeInterrupt::TextInput(args) => {
-                let node = engine
-                    .get_expanded_node(args.id_chain[0])
-                    .expect("text node exists in engine");
-                node.dispatch_text_input(
-                    TextInput { text: args.text },
-                    globals,
-                    &engine.runtime_context,
-                )
-            }
-            NativeInterrupt::FormTextboxChange(args) => {
-                let node = engine
-                    .get_expanded_node(args.id_chain[0])
-                    .expect("textbox node exists in engine");
-                node.dispatch_textbox_change(
-                    TextboxChange { text: args.text },
-                    globals,
-                    &engine.runtime_context,
-                )
-            }
-            NativeInterrupt::FormCheckboxToggle(args) => {
-                let node = engine
-                    .get_expanded_node(args.id_chain[0])
-                    .expect("checkbox node exists in engine");
-                node.dispatch_checkbox_change(
-                    CheckboxChange {
-                        checked: args.state,
-                    },
-                    globals,
-                    &engine.runtime_context,
-                )
-            }
-
-            NativeInterrupt::AddedLayer(_args) => false,
-            NativeInterrupt::Click(args) => {
-                let prospective_hit = engine
-                    .runtime_context
-                    .get_topmost_element_beneath_ray(Point2::new(args.x, args.y));
-                if let Some(topmost_node) = prospective_hit {
-                    let args_click = Click {
-                        mouse: MouseEventArgs {
-                            x: args.x,
-                            y: args.y,
-                            button: MouseButton::from(args.button),
-                            modifiers: args
-                                .modifiers
-                                .iter()
-                                .map(|x| ModifierKey::from(x))
-                                .collect(),
-                        },
-                    };
-                    topmost_node.dispatch_click(args_click, globals, &engine.runtime_context)
-                } else {
-                    false
-                }
-            }
-            NativeInterrupt::Scroll(_args) => false,
-            NativeInterrupt::Clap(args) => {
-                let prospective_hit = engine
-                    .runtime_context
-                    .get_topmost_element_beneath_ray(Point2::new(args.x, args.y));
-                if let Some(topmost_node) = prospective_hit {
-                    let args_clap = Clap {
-                        x: args.x,
-                        y: args.y,
-                    };
-                    topmost_node.dispatch_clap(args_clap, globals, &engine.runtime_context)
-                } else {
-                    false
-                }
-            }
-            NativeInterrupt::TouchStart(args) => {
-                let first_touch = args.touches.get(0).unwrap();
-                let prospective_hit = engine
-                    .runtime_context
-                    .get_topmost_element_beneath_ray(Point2::new(first_touch.x, first_touch.y));
-                if let Some(topmost_node) = prospective_hit {
-                    let touches = args.touches.iter().map(|x| Touch::from(x)).collect();
-                    let args_touch_start = TouchStart { touches };
-                    topmost_node.dispatch_touch_start(
-                        args_touch_start,
-                        globals,
-                        &engine.runtime_context,
-                    )
-                } else {
-                    false
-                }
-            }
-            NativeInterrupt::TouchMove(args) => {
-                let first_touch = args.touches.get(0).unwrap();
-                let prospective_hit = engine
-                    .runtime_context
-                    .get_topmost_element_beneath_ray(Point2::new(first_touch.x, first_touch.y));
-                if let Some(topmost_node) = prospective_hit {
-                    let touches = args.touches.iter().map(|x| Touch::from(x)).collect();
-                    let args_touch_move = TouchMove { touches };
-                    topmost_node.dispatch_touch_move(
-                        args_touch_move,
-                        globals,
-                        &engine.runtime_context,
-                    )
-                } else {
-                    false
-                }
-            }
-            NativeInterrupt::TouchEnd(args) => {
-                let first_touch = args.touches.get(0).unwrap();
-                let prospective_hit = engine
-                    .runtime_context
-                    .get_topmost_element_beneath_ray(Point2::new(first_touch.x, first_touch.y));
-                if let Some(topmost_node) = prospective_hit {
-                    let touches = args.touches.iter().map(|x| Touch::from(x)).collect();
-                    let args_touch_end = TouchEnd { touches };
-                    topmost_node.dispatch_touch_end(
-                        args_touch_end,
-                        globals,
-                        &engine.runtime_context,
-                    )
-                } else {
-                    false
-                }
-            }
-            NativeInterrupt::KeyDown(args) => {
-                let modifiers = args
-                    .modifiers
-                    .iter()
-                    .map(|x| ModifierKey::from(x))
-                    .collect();
-                let args_key_down = KeyDown {
-                    keyboard: KeyboardEventArgs {
-                        key: args.key,
-                        modifiers,
-                        is_repeat: args.is_repeat,
-                    },
-                };
-                engine.global_dispatch_key_down(args_key_down);
-                false
-            }
-            NativeInterrupt::KeyUp(args) => {
-                let modifiers = args
-                    .modifiers
-                    .iter()
-                    .map(|x| ModifierKey::from(x))
-                    .collect();
-                let args_key_up = KeyUp {
-                    keyboard: KeyboardEventArgs {
-                        key: args.key,
-                        modifiers,
-                        is_repeat: args.is_repeat,
-                    },
-                };
-                engine.global_dispatch_key_up(args_key_up);
-                false
-            }
-            NativeInterrupt::KeyPress(args) => {
-                let modifiers = args
-                    .modifiers
-                    .iter()
-                    .map(|x| ModifierKey::from(x))
-                    .collect();
-                let args_key_press = KeyPress {
-                    keyboard: KeyboardEventArgs {
-                        key: args.key,
-                        modifiers,
-                        is_repeat: args.is_repeat,
-                    },
-                };
-                engine.global_dispatch_key_press(args_key_press);
-                false
-            }
-            NativeInterrupt::DoubleClick(args) => {
-                let prospective_hit = engine
-                    .runtime_context
-                    .get_topmost_element_beneath_ray(Point2::new(args.x, args.y));
-                if let Some(topmost_node) = prospective_hit {
-                    let args_double_click = DoubleClick {
-                        mouse: MouseEventArgs {
-                            x: args.x,
-                            y: args.y,
-                            button: MouseButton::from(args.button),
-                            modifiers: args
-                                .modifiers
-                                .iter()
-                                .map(|x| ModifierKey::from(x))
-                                .collect(),
-                        },
-                    };
-                    topmost_node.dispatch_double_click(
-                        args_double_click,
-                        globals,
-                        &engine.runtime_context,
-                    )
-                } else {
-                    false
-                }
-            }
-            NativeInterrupt::MouseMove(args) => {
-                let prospective_hit = engine
-                    .runtime_context
-                    .get_topmost_element_beneath_ray(Point2::new(args.x, args.y));
-                if let Some(topmost_node) = prospective_hit {
-                    let args_mouse_move = MouseMove {
-                        mouse: MouseEventArgs {
-                            x: args.x,
-                            y: args.y,
-                            button: MouseButton::from(args.button),
-                            modifiers: args
-                                .modifiers
-                                .iter()
-                                .map(|x| ModifierKey::from(x))
-                                .collect(),
-                        },
-                    };
-                    topmost_node.dispatch_mouse_move(
-                        args_mouse_move,
-                        globals,
-                        &engine.runtime_context,
-                    )
-                } else {
-                    false
-                }
-            }
-            NativeInterrupt::Wheel(args) => {
-                let prospective_hit = engine
-                    .runtime_context
-                    .get_topmost_element_beneath_ray(Point2::new(args.x, args.y));
-                if let Some(topmost_node) = prospective_hit {
-                    let modifiers = args
-                        .modifiers
-                        .iter()
-                        .map(|x| ModifierKey::from(x))
-                        .collect();
-                    let args_wheel = Wheel {
-                        x: args.x,
-                        y: args.y,
-                        delta_x: args.delta_x,
-                        delta_y: args.delta_y,
-                        modifiers,
-                    };
-                    topmost_node.dispatch_wheel(args_wheel, globals, &engine.runtime_context)
-                } else {
-                    false
-                }
-            }
-            NativeInterrupt::MouseDown(args) => {
-                let prospective_hit = engine
-                    .runtime_context
-                    .get_topmost_element_beneath_ray(Point2::new(args.x, args.y));
-                if let Some(topmost_node) = prospective_hit {
-                    let args_mouse_down = MouseDown {
-                        mouse: MouseEventArgs {
-                            x: args.x,
-                            y: args.y,
-                            button: MouseButton::from(args.button),
-                            modifiers: args
-                                .modifiers
-                                .iter()
-                                .map(|x| ModifierKey::from(x))
-                                .collect(),
-                        },
-                    };
-                    topmost_node.dispatch_mouse_down(
-                        args_mouse_down,
-                        globals,
-                        &engine.runtime_context,
-                    )
-                } else {
-                    false
-                }
-            }
-            NativeInterrupt::MouseUp(args) => {
-                let prospective_hit = engine
-                    .runtime_context
-                    .get_topmost_element_beneath_ray(Point2::new(args.x, args.y));
-                if let Some(topmost_node) = prospective_hit {
-                    let args_mouse_up = MouseUp {
-                        mouse: MouseEventArgs {
-                            x: args.x,
-                            y: args.y,
-                            button: MouseButton::from(args.button),
-                            modifiers: args
-                                .modifiers
-                                .iter()
-                                .map(|x| ModifierKey::from(x))
-                                .collect(),
-                        },
-                    };
-                    topmost_node.dispatch_mouse_up(args_mouse_up, globals, &engine.runtime_context)
-                } else {
-                    false
-                }
-            }
-            NativeInterrupt::MouseOver(args) => {
-                let prospective_hit = engine
-                    .runtime_context
-                    .get_topmost_element_beneath_ray(Point2::new(args.x, args.y));
-                if let Some(topmost_node) = prospective_hit {
-                    let args_mouse_over = MouseOver {
-                        mouse: MouseEventArgs {
-                            x: args.x,
-                            y: args.y,
-                            button: MouseButton::from(args.button),
-                            modifiers: args
-                                .modifiers
-                                .iter()
-                                .map(|x| ModifierKey::from(x))
-                                .collect(),
-                        },
-                    };
-                    topmost_node.dispatch_mouse_over(
-                        args_mouse_over,
-                        globals,
-                        &engine.runtime_context,
-                    )
-                } else {
-                    false
-                }
-            }
-            NativeInterrupt::MouseOut(args) => {
-                let prospective_hit = engine
-                    .runtime_context
-                    .get_topmost_element_beneath_ray(Point2::new(args.x, args.y));
-                if let Some(topmost_node) = prospective_hit {
-                    let args_mouse_out = MouseOut {
-                        mouse: MouseEventArgs {
-                            x: args.x,
-                            y: args.y,
-                            button: MouseButton::from(args.button),
-                            modifiers: args
-                                .modifiers
-                                .iter()
-                                .map(|x| ModifierKey::from(x))
-                                .collect(),
-                        },
-                    };
-                    topmost_node.dispatch_mouse_out(
-                        args_mouse_out,
-                        globals,
-                        &engine.runtime_context,
-                    )
-                } else {
-                    false
-                }
-            }
-            NativeInterrupt::ContextMenu(args) => {
-                let prospective_hit = engine
-                    .runtime_context
-                    .get_topmost_element_beneath_ray(Point2::new(args.x, args.y));
-                if let Some(topmost_node) = prospective_hit {
-                    let args_context_menu = ContextMenu {
-                        mouse: MouseEventArgs {
-                            x: args.x,
-                            y: args.y,
-                            button: MouseButton::from(args.button),
-                            modifiers: args
-                                .modifiers
-                                .iter()
-                                .map(|x| ModifierKey::from(x))
-                                .collect(),
-                        },
-                    };
-                    topmost_node.dispatch_context_menu(
-                        args_context_menu,
-                        globals,
-                        &engine.runtime_context,
-                    )
-                } else {
-                    false
-                }
-            }
+            interrupt => self.engine.borrow_mut().handle_interrupt(interrupt),
         };
 
         InterruptResult { prevent_default }
@@ -634,11 +238,15 @@ impl PaxChassisWeb {
         self.update_userland_component();
     }
 
-    pub fn tick(&mut self) -> MemorySlice {
+    /// `timestamp_ms` is the current time in milliseconds on a monotonic clock, e.g. `performance.now()`
+    pub fn tick(&mut self, timestamp_ms: f64) -> MemorySlice {
         #[cfg(feature = "designtime")]
         self.designtime_tick();
 
-        let message_queue = self.engine.borrow_mut().tick();
+        let mut engine = self.engine.borrow_mut();
+        engine.set_clock(Duration::from_secs_f64(timestamp_ms.max(0.0) / 1000.0));
+        let message_queue = engine.tick();
+        drop(engine);
 
         // Serialize data to a JSON string
         let json_string = serde_json::to_string(&message_queue).unwrap();
@@ -445,8 +445,37 @@ fn recurse_compile_expressions<'a>(
                     is_repeat_source_iterable_expression: is_repeat_source_iterable,
                 },
             );
+
+            // Handle the `elem.id` in `for elem in self.elements key elem.id`, evaluated per
+            // element with the predicate's symbols (attached above) in scope
+            if let Some(key_expression_paxel) = cfa.repeat_key_expression_paxel.clone() {
+                let (output_statement, invocations) =
+                    compile_paxel_to_ril(key_expression_paxel.clone(), &ctx)?;
+                let id = ctx.vtable_uid_gen.next().unwrap();
+
+                cfa.repeat_key_expression_vtable_id = Some(id);
+
+                let mut whitespace_removed_input = key_expression_paxel.clone().token_value;
+                whitespace_removed_input.retain(|c| !c.is_whitespace());
+
+                let source_map_id = source_map.insert(key_expression_paxel);
+                let input_statement =
+                    source_map.generate_mapped_string(whitespace_removed_input, source_map_id);
+
+                ctx.expression_specs.insert(
+                    id,
+                    ExpressionSpec {
+                        id,
+                        invocations,
+                        output_type: "RepeatKey".to_string(),
+                        output_statement,
+                        input_statement,
+                        is_repeat_source_iterable_expression: false,
+                    },
+                );
+            }
         } else if let Some(condition_expression_paxel) = &cfa.condition_expression_paxel {
-            //Handle `if` boolean expression, e.g. the `num_clicks > 5` in `if num_clicks > 5 { ... }`
+            //Handle `if` (and `else if`) boolean expression, e.g. the `num_clicks > 5` in `if num_clicks > 5 { ... }`
             let (output_statement, invocations) =
                 compile_paxel_to_ril(condition_expression_paxel.clone(), &ctx)?;
             let id = ctx.vtable_uid_gen.next().unwrap();
@@ -497,6 +526,8 @@ fn recurse_compile_expressions<'a>(
                     is_repeat_source_iterable_expression: false,
                 },
             );
+        } else if cfa.is_else_branch {
+            // A plain `else` has no condition to compile: it's shown whenever its `if` is false
         } else {
             unreachable!("encountered invalid control flow definition")
         }
@@ -118,7 +118,10 @@ fn get_formatting_rules(pest_rule: Rule) -> Vec<Box<dyn FormattingRule>> {
             vec![Box::new(XoObjectSettingsKeyValuePairDefaultRule)]
         }
         Rule::statement_for => vec![Box::new(StatementForDefaultRule)],
+        Rule::statement_for_key => vec![Box::new(StatementForKeyDefaultRule)],
         Rule::statement_if => vec![Box::new(StatementIfDefaultRule)],
+        Rule::statement_else_if => vec![Box::new(StatementElseIfDefaultRule)],
+        Rule::statement_else => vec![Box::new(StatementElseDefaultRule)],
         Rule::statement_slot => vec![Box::new(StatementSlotDefaultRule)],
         Rule::any_template_value | Rule::node_inner_content | Rule::settings_value => {
             vec![Box::new(WrapExpressionRule), Box::new(ForwardRule)]
@@ -170,7 +173,12 @@ fn get_formatting_rules(pest_rule: Rule) -> Vec<Box<dyn FormattingRule>> {
         | Rule::literal_color_space_func
         | Rule::xo_color_space_func
         | Rule::literal_color_const
-        | Rule::xo_range_exclusive => vec![Box::new(PrintRule)],
+        | Rule::xo_range_exclusive
+        | Rule::timeline_block_declaration
+        | Rule::timeline_setting
+        | Rule::timeline_selector_block
+        | Rule::timeline_property
+        | Rule::keyframe => vec![Box::new(PrintRule)],
 
         Rule::expression_wrapped
         | Rule::xo_primary
@@ -226,9 +234,10 @@ impl FormattingRule for PaxComponentDefinitionDefaultRule {
         let tags = children
             .iter()
             .filter(|child| child.node_type == Rule::root_tag_pair);
-        let settings = children
-            .iter()
-            .filter(|child| child.node_type == Rule::settings_block_declaration);
+        let settings = children.iter().filter(|child| {
+            child.node_type == Rule::settings_block_declaration
+                || child.node_type == Rule::timeline_block_declaration
+        });
 
         let mut component = vec![];
 
@@ -782,8 +791,12 @@ impl FormattingRule for StatementForDefaultRule {
     fn format(&self, _node: Pair<Rule>, children: Vec<Child>) -> String {
         let mut formatted_node = String::new();
         let sfpd = children[0].formatted_node.clone();
-        let sfs = children[1].formatted_node.clone();
-        let inner_nodes = children[2].formatted_node.clone();
+        let mut sfs = children[1].formatted_node.clone();
+        // A `key` sits between the source and the inner nodes
+        if children.len() > 3 {
+            sfs = format!("{} {}", sfs, children[2].formatted_node);
+        }
+        let inner_nodes = children.last().unwrap().formatted_node.clone();
         let inner_nodes_indented = indent_every_line_of_string(inner_nodes);
         formatted_node
             .push_str(format!("for {} in {} {{\n{}\n}}", sfpd, sfs, inner_nodes_indented).as_str());
@@ -791,6 +804,15 @@ impl FormattingRule for StatementForDefaultRule {
     }
 }
 
+#[derive(Clone)]
+struct StatementForKeyDefaultRule;
+
+impl FormattingRule for StatementForKeyDefaultRule {
+    fn format(&self, _node: Pair<Rule>, children: Vec<Child>) -> String {
+        format!("key {}", children[0].formatted_node)
+    }
+}
+
 #[derive(Clone)]
 struct StatementIfDefaultRule;
 
@@ -801,10 +823,35 @@ impl FormattingRule for StatementIfDefaultRule {
         let inner_nodes = children[1].formatted_node.clone();
         let inner_nodes_indented = indent_every_line_of_string(inner_nodes);
         formatted_node.push_str(format!("if {} {{\n{}\n}}", exp, inner_nodes_indented).as_str());
+        // Any `else if`s and `else` follow on from the closing brace
+        for branch in &children[2..] {
+            formatted_node.push_str(format!(" {}", branch.formatted_node).as_str());
+        }
         formatted_node
     }
 }
 
+#[derive(Clone)]
+struct StatementElseIfDefaultRule;
+
+impl FormattingRule for StatementElseIfDefaultRule {
+    fn format(&self, _node: Pair<Rule>, children: Vec<Child>) -> String {
+        let exp = children[0].formatted_node.clone();
+        let inner_nodes_indented = indent_every_line_of_string(children[1].formatted_node.clone());
+        format!("else if {} {{\n{}\n}}", exp, inner_nodes_indented)
+    }
+}
+
+#[derive(Clone)]
+struct StatementElseDefaultRule;
+
+impl FormattingRule for StatementElseDefaultRule {
+    fn format(&self, _node: Pair<Rule>, children: Vec<Child>) -> String {
+        let inner_nodes_indented = indent_every_line_of_string(children[0].formatted_node.clone());
+        format!("else {{\n{}\n}}", inner_nodes_indented)
+    }
+}
+
 #[derive(Clone)]
 struct StatementSlotDefaultRule;
 
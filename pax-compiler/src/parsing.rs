@@ -6,9 +6,10 @@ use pax_manifest::{escape_identifier, ComponentTemplate, TemplateNodeId, TreeLoc
 
 use pax_manifest::{
     get_primitive_type_table, ComponentDefinition, ControlFlowRepeatPredicateDefinition,
-    ControlFlowRepeatSourceDefinition, ControlFlowSettingsDefinition, LiteralBlockDefinition,
-    LocationInfo, PropertyDefinition, SettingElement, SettingsBlockElement, TemplateNodeDefinition,
-    Token, TokenType, TypeDefinition, TypeTable, ValueDefinition,
+    ControlFlowRepeatSourceDefinition, ControlFlowSettingsDefinition, KeyframeDefinition,
+    LiteralBlockDefinition, LocationInfo, PropertyDefinition, SettingElement, SettingsBlockElement,
+    TemplateNodeDefinition, TimelineDefinition, Token, TokenType, TypeDefinition, TypeTable,
+    ValueDefinition,
 };
 
 extern crate pest;
@@ -477,6 +478,9 @@ fn recurse_visit_tag_pairs_for_template(
                             slot_index_expression_vtable_id: None,
                             repeat_predicate_definition: None,
                             repeat_source_definition: None,
+                            repeat_key_expression_paxel: None,
+                            repeat_key_expression_vtable_id: None,
+                            is_else_branch: false,
                         }),
                         type_id: TypeId::build_if(),
                         settings: None,
@@ -500,14 +504,71 @@ fn recurse_visit_tag_pairs_for_template(
                             );
                         })
                     }
+
+                    // Each `else if` / `else` becomes an `if` appended as the last child of the
+                    // branch before it, rendered in place of that branch while it's false
+                    let mut branch_id = id.get_template_node_id();
+                    for branch in statement_if {
+                        let is_else_if = branch.as_rule() == Rule::statement_else_if;
+                        let mut branch = branch.into_inner();
+                        let condition_expression_paxel = if is_else_if {
+                            let expression_body = branch.next().unwrap();
+                            Some(Token::new(
+                                expression_body.as_str().to_string(),
+                                TokenType::IfExpression,
+                                span_to_location(&expression_body.as_span()),
+                                pax,
+                            ))
+                        } else {
+                            None
+                        };
+                        let template_node = TemplateNodeDefinition {
+                            control_flow_settings: Some(ControlFlowSettingsDefinition {
+                                condition_expression_paxel,
+                                is_else_branch: true,
+                                ..Default::default()
+                            }),
+                            type_id: TypeId::build_if(),
+                            settings: None,
+                            raw_comment_string: None,
+                        };
+                        branch_id = ctx
+                            .template
+                            .add_child_back(branch_id, template_node)
+                            .get_template_node_id();
+                        let inner_nodes = branch.next().unwrap();
+                        inner_nodes.into_inner().for_each(|sub_tag_pair| {
+                            recurse_visit_tag_pairs_for_template(
+                                ctx,
+                                sub_tag_pair,
+                                pax,
+                                TreeLocation::Parent(branch_id.clone()),
+                            );
+                        })
+                    }
                 }
                 Rule::statement_for => {
                     let mut cfavd = ControlFlowSettingsDefinition::default();
                     let mut for_statement = any_tag_pair.clone().into_inner();
                     let mut predicate_declaration = for_statement.next().unwrap().into_inner();
                     let source = for_statement.next().unwrap();
-
-                    let prospective_inner_nodes = for_statement.next();
+                    let mut prospective_inner_nodes = for_statement.next();
+
+                    if let Some(key) = prospective_inner_nodes
+                        .clone()
+                        .filter(|pair| pair.as_rule() == Rule::statement_for_key)
+                    {
+                        /* statement_for_key = { "key" ~ expression_body } */
+                        let expression_body = key.into_inner().next().unwrap();
+                        let expression_body_location = span_to_location(&expression_body.as_span());
+                        cfavd.repeat_key_expression_paxel = Some(Token::new(
+                            expression_body.as_str().to_string(),
+                            TokenType::ForKey,
+                            expression_body_location,
+                            pax,
+                        ));
+                        prospective_inner_nodes = for_statement.next();
+                    }
 
                     if predicate_declaration.clone().count() > 1 {
                         //tuple, like the `elem, i` in `for (elem, i) in self.some_list`
@@ -625,6 +686,9 @@ fn recurse_visit_tag_pairs_for_template(
                             slot_index_expression_vtable_id: None, //This will be written back to this data structure later, during expression compilation
                             repeat_predicate_definition: None,
                             repeat_source_definition: None,
+                            repeat_key_expression_paxel: None,
+                            repeat_key_expression_vtable_id: None,
+                            is_else_branch: false,
                         }),
                         type_id: TypeId::build_slot(),
                         settings: None,
@@ -1012,6 +1076,147 @@ pub fn parse_settings_from_component_definition_string(pax: &str) -> Vec<Setting
     settings
 }
 
+/// Parses the `@timeline` block of a component definition, if it has one
+pub fn parse_timeline_from_component_definition_string(pax: &str) -> Option<TimelineDefinition> {
+    let pax_component_definition = PaxParser::parse(Rule::pax_component_definition, pax)
+        .expect(&format!("unsuccessful parse from {}", &pax))
+        .next()
+        .unwrap();
+
+    let timeline_block = pax_component_definition
+        .into_inner()
+        .find(|top_level_pair| top_level_pair.as_rule() == Rule::timeline_block_declaration)?;
+
+    let mut frame_count = None;
+    let mut autoplay = true;
+    let mut looping = false;
+    let mut selector_blocks = vec![];
+    for timeline_entity in timeline_block.into_inner() {
+        match timeline_entity.as_rule() {
+            Rule::timeline_setting => {
+                //playback setting in the form of `frames: 120`
+                let mut pairs = timeline_entity.into_inner();
+                let key = pairs.next().unwrap().into_inner().next().unwrap();
+                let value = pairs.next().unwrap().as_str().trim();
+                match key.as_str() {
+                    "frames" => {
+                        frame_count = Some(value.parse().unwrap_or_else(|_| {
+                            panic!("@timeline `frames` must be a whole number, got {}", value)
+                        }))
+                    }
+                    "autoplay" => autoplay = value == "true",
+                    "loop" => looping = value == "true",
+                    other => panic!("Unknown @timeline setting: {}", other),
+                }
+            }
+            Rule::timeline_selector_block => {
+                let mut pairs = timeline_entity.into_inner();
+                let raw_selector = pairs.next().unwrap();
+                let selector: String = raw_selector
+                    .as_str()
+                    .chars()
+                    .filter(|c| !c.is_whitespace())
+                    .collect();
+                let token = Token::new(
+                    selector,
+                    TokenType::Selector,
+                    span_to_location(&raw_selector.as_span()),
+                    pax,
+                );
+                let elements = pairs
+                    .map(|property| match property.as_rule() {
+                        Rule::timeline_property => parse_timeline_property(property, pax),
+                        Rule::comment => SettingElement::Comment(property.as_str().to_string()),
+                        _ => unreachable!("Parsing error: {:?}", property.as_rule()),
+                    })
+                    .collect();
+                selector_blocks.push(SettingsBlockElement::SelectorBlock(
+                    token,
+                    LiteralBlockDefinition::new(elements),
+                ));
+            }
+            Rule::comment => {
+                let comment = timeline_entity.as_str().to_string();
+                selector_blocks.push(SettingsBlockElement::Comment(comment));
+            }
+            _ => {
+                unreachable!("Parsing error: {:?}", timeline_entity.as_rule());
+            }
+        }
+    }
+
+    // Unless specified, the timeline runs until its last keyframe
+    let frame_count = frame_count.unwrap_or_else(|| {
+        selector_blocks
+            .iter()
+            .filter_map(|block| match block {
+                SettingsBlockElement::SelectorBlock(_, block) => Some(block.get_all_settings()),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|(_, value)| match value {
+                ValueDefinition::Keyframes(keyframes) => keyframes.iter().map(|k| k.frame).max(),
+                _ => None,
+            })
+            .max()
+            .unwrap_or_default()
+    });
+
+    Some(TimelineDefinition {
+        frame_count,
+        autoplay,
+        looping,
+        selector_blocks,
+    })
+}
+
+/// Parses a keyframed property in the form of `x: [0: 0px, 60: 100px EasingCurve::OutQuad]`
+fn parse_timeline_property(property: Pair<Rule>, pax: &str) -> SettingElement {
+    let mut pairs = property.into_inner();
+    let key = pairs.next().unwrap().into_inner().next().unwrap();
+    let key_token = Token::new(
+        key.as_str().to_string(),
+        TokenType::SettingKey,
+        span_to_location(&key.as_span()),
+        pax,
+    );
+    let keyframes = pairs
+        .map(|keyframe| {
+            let mut keyframe_pairs = keyframe.into_inner();
+            let frame = keyframe_pairs.next().unwrap();
+            let value = keyframe_pairs.next().unwrap();
+            //we want to pratt-parse literals, mostly to unpack `px` and `%` (recursively)
+            let (output_string, _) = crate::parsing::run_pratt_parser(value.as_str());
+            let value_token = Token::new_with_raw_value(
+                output_string,
+                value.as_str().to_string(),
+                TokenType::LiteralValue,
+                span_to_location(&value.as_span()),
+                pax,
+            );
+            let curve_token = keyframe_pairs.next().map(|curve| {
+                Token::new(
+                    curve.as_str().to_string(),
+                    TokenType::LiteralValue,
+                    span_to_location(&curve.as_span()),
+                    pax,
+                )
+            });
+            KeyframeDefinition {
+                frame: frame.as_str().trim().parse().unwrap_or_else(|_| {
+                    panic!(
+                        "Keyframe frames must be whole numbers, got {}",
+                        frame.as_str()
+                    )
+                }),
+                value: value_token,
+                curve: curve_token,
+            }
+        })
+        .collect();
+    SettingElement::Setting(key_token, ValueDefinition::Keyframes(keyframes))
+}
+
 pub struct ParsingContext {
     /// Used to track which files/sources have been visited during parsing,
     /// to prevent duplicate parsing
@@ -1166,6 +1371,7 @@ pub fn assemble_component_definition(
     ctx.template_node_definitions = tpc.template.clone();
 
     let settings = parse_settings_from_component_definition_string(pax);
+    let timeline = parse_timeline_from_component_definition_string(pax);
 
     let new_def = ComponentDefinition {
         is_primitive: false,
@@ -1175,6 +1381,7 @@ pub fn assemble_component_definition(
         type_id: self_type_id,
         template: Some(tpc.template),
         settings: Some(settings),
+        timeline,
         module_path: modified_module_path,
     };
 
@@ -1205,6 +1412,7 @@ pub fn assemble_struct_only_component_definition(
         primitive_instance_import_path: None,
         template: None,
         settings: None,
+        timeline: None,
     };
     (ctx, new_def)
 }
@@ -1224,6 +1432,7 @@ pub fn assemble_primitive_definition(
         type_id: self_type_id,
         template: None,
         settings: None,
+        timeline: None,
         module_path: modified_module_path,
     }
 }
@@ -1567,6 +1776,22 @@ impl Reflectable for pax_runtime::api::Rotation {
     }
 }
 
+impl Reflectable for pax_runtime::api::EasingCurve {
+    fn get_import_path() -> String {
+        "pax_engine::api::EasingCurve".to_string()
+    }
+
+    fn get_self_pascal_identifier() -> String {
+        "EasingCurve".to_string()
+    }
+    fn get_type_id() -> TypeId {
+        TypeId::build_singleton(
+            &Self::get_import_path(),
+            Some(&Self::get_self_pascal_identifier()),
+        )
+    }
+}
+
 impl Reflectable for pax_runtime::api::Numeric {
     fn get_import_path() -> String {
         "pax_engine::api::Numeric".to_string()
@@ -0,0 +1,18 @@
+use std::borrow::Cow;
+
+use kurbo::Rect;
+use piet::{Color, FixedGradient, IntoBrush};
+
+/// Brush shared by the headless `piet::RenderContext`s: relative (unit-space) gradients
+/// arrive here already resolved against the bounding box of the shape being painted.
+#[derive(Clone)]
+pub enum Brush {
+    Solid(Color),
+    Gradient(FixedGradient),
+}
+
+impl<P: piet::RenderContext<Brush = Brush>> IntoBrush<P> for Brush {
+    fn make_brush<'a>(&'a self, _piet: &mut P, _bbox: impl FnOnce() -> Rect) -> Cow<'a, Brush> {
+        Cow::Borrowed(self)
+    }
+}
@@ -0,0 +1,362 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::Duration;
+
+use pax_message::{
+    ClickInterruptArgs, FormButtonClickArgs, FormCheckboxToggleArgs, FormTextboxChangeArgs,
+    FormTextboxInputArgs, KeyDownInterruptArgs, KeyPressInterruptArgs, KeyUpInterruptArgs,
+    ModifierKeyMessage, MouseButtonMessage, MouseDownInterruptArgs, MouseMoveInterruptArgs,
+    MouseUpInterruptArgs, NativeInterrupt, NativeMessage, TouchEndInterruptArgs, TouchMessage,
+    TouchMoveInterruptArgs, TouchStartInterruptArgs,
+};
+use pax_runtime::api::math::Point2;
+use pax_runtime::api::Cursor;
+use pax_runtime::{ExpandedNode, PaxEngine, TransformAndBounds};
+
+use crate::RasterRenderer;
+
+/// Drives a `PaxEngine` in-process, without a chassis: send synthetic `NativeInterrupt`s,
+/// advance ticks, query nodes by id and collect the `NativeMessage`s a chassis would
+/// otherwise receive.  Interrupts are routed through [`PaxEngine::handle_interrupt`], i.e.
+/// exactly as they are by the web and native chassis.
+pub struct TestHarness {
+    engine: PaxEngine,
+    messages: Vec<NativeMessage>,
+    /// Last reported position of each finger currently down, by identifier
+    touches: HashMap<i64, (f64, f64)>,
+    /// The simulated monotonic clock fed to the engine on each tick
+    clock: Duration,
+    frame_interval: Duration,
+}
+
+impl TestHarness {
+    /// Wraps `engine` and runs its first tick, so that nodes are expanded and laid out
+    /// before any interrupts are sent.
+    pub fn new(engine: PaxEngine) -> Self {
+        let mut harness = TestHarness {
+            engine,
+            messages: Vec::new(),
+            touches: HashMap::new(),
+            clock: Duration::ZERO,
+            frame_interval: Duration::from_nanos(1_000_000_000 / 60),
+        };
+        harness.tick_after(Duration::ZERO);
+        harness
+    }
+
+    pub fn engine(&self) -> &PaxEngine {
+        &self.engine
+    }
+
+    pub fn engine_mut(&mut self) -> &mut PaxEngine {
+        &mut self.engine
+    }
+
+    /// Runs a single engine tick one frame interval after the previous one, collecting any
+    /// emitted `NativeMessage`s
+    pub fn tick(&mut self) {
+        self.tick_after(self.frame_interval);
+    }
+
+    /// Runs a single engine tick `elapsed` after the previous one, e.g. to simulate a dropped
+    /// frame or a throttled background tab
+    pub fn tick_after(&mut self, elapsed: Duration) {
+        self.clock += elapsed;
+        self.engine.set_clock(self.clock);
+        let messages = self.engine.tick();
+        self.messages.extend(messages);
+    }
+
+    /// Sets the simulated time between ticks run by `tick` and `advance`, 1/60s by default
+    pub fn set_frame_interval(&mut self, frame_interval: Duration) {
+        self.frame_interval = frame_interval;
+    }
+
+    /// Runs `ticks` engine ticks
+    pub fn advance(&mut self, ticks: usize) {
+        for _ in 0..ticks {
+            self.tick();
+        }
+    }
+
+    /// Current engine tick count
+    pub fn frames_elapsed(&self) -> usize {
+        self.engine.runtime_context.globals().frames_elapsed
+    }
+
+    /// Current engine time, per the simulated clock
+    pub fn time_elapsed(&self) -> Duration {
+        self.engine.runtime_context.globals().time_elapsed
+    }
+
+    /// Sends an arbitrary interrupt to the engine.  Returns whether any handler called `prevent_default`.
+    pub fn interrupt(&mut self, interrupt: NativeInterrupt) -> bool {
+        self.engine.handle_interrupt(interrupt)
+    }
+
+    /// Sends the mouse-down, mouse-up and click sequence a browser would emit for a
+    /// left-button click at `(x, y)` in window coordinates.  Returns whether any handler
+    /// called `prevent_default`.
+    pub fn click(&mut self, x: f64, y: f64) -> bool {
+        let down = self.interrupt(NativeInterrupt::MouseDown(MouseDownInterruptArgs {
+            x,
+            y,
+            button: MouseButtonMessage::Left,
+            modifiers: vec![],
+        }));
+        let up = self.interrupt(NativeInterrupt::MouseUp(MouseUpInterruptArgs {
+            x,
+            y,
+            button: MouseButtonMessage::Left,
+            modifiers: vec![],
+        }));
+        let click = self.interrupt(NativeInterrupt::Click(ClickInterruptArgs {
+            x,
+            y,
+            button: MouseButtonMessage::Left,
+            modifiers: vec![],
+        }));
+        down || up || click
+    }
+
+    /// Sends a left-button click at the center of the node with the given `id`
+    pub fn click_node(&mut self, id: &str) -> bool {
+        let (x, y) = self.center_of(id);
+        self.click(x, y)
+    }
+
+    /// Moves the mouse to `(x, y)` without any buttons pressed.  Returns whether any handler
+    /// called `prevent_default`.
+    pub fn mouse_move(&mut self, x: f64, y: f64) -> bool {
+        self.interrupt(NativeInterrupt::MouseMove(MouseMoveInterruptArgs {
+            x,
+            y,
+            button: MouseButtonMessage::Left,
+            modifiers: vec![],
+        }))
+    }
+
+    /// Presses the left mouse button at `from`, moves it to `to` in `steps` evenly spaced
+    /// mouse moves and releases it there.  Returns whether any handler called `prevent_default`.
+    pub fn drag(&mut self, from: (f64, f64), to: (f64, f64), steps: usize) -> bool {
+        let mut prevent_default =
+            self.interrupt(NativeInterrupt::MouseDown(MouseDownInterruptArgs {
+                x: from.0,
+                y: from.1,
+                button: MouseButtonMessage::Left,
+                modifiers: vec![],
+            }));
+        for step in 1..=steps {
+            let t = step as f64 / steps as f64;
+            prevent_default |= self.interrupt(NativeInterrupt::MouseMove(MouseMoveInterruptArgs {
+                x: from.0 + (to.0 - from.0) * t,
+                y: from.1 + (to.1 - from.1) * t,
+                button: MouseButtonMessage::Left,
+                modifiers: vec![],
+            }));
+        }
+        prevent_default |= self.interrupt(NativeInterrupt::MouseUp(MouseUpInterruptArgs {
+            x: to.0,
+            y: to.1,
+            button: MouseButtonMessage::Left,
+            modifiers: vec![],
+        }));
+        prevent_default
+    }
+
+    /// Puts fingers down at the given `(identifier, x, y)` positions.  As in browsers, the
+    /// interrupt lists every finger down, not only the new ones.
+    pub fn touch_start(&mut self, touches: &[(i64, f64, f64)]) -> bool {
+        for (identifier, x, y) in touches {
+            self.touches.insert(*identifier, (*x, *y));
+        }
+        let touches = self.touch_messages(self.touches.keys().copied().collect());
+        self.interrupt(NativeInterrupt::TouchStart(TouchStartInterruptArgs {
+            touches,
+        }))
+    }
+
+    /// Moves fingers that are down to the given `(identifier, x, y)` positions
+    pub fn touch_move(&mut self, touches: &[(i64, f64, f64)]) -> bool {
+        let deltas = self.move_touches(touches);
+        let mut messages = self.touch_messages(self.touches.keys().copied().collect());
+        for message in messages.iter_mut() {
+            if let Some((delta_x, delta_y)) = deltas.get(&message.identifier) {
+                message.delta_x = *delta_x;
+                message.delta_y = *delta_y;
+            }
+        }
+        self.interrupt(NativeInterrupt::TouchMove(TouchMoveInterruptArgs {
+            touches: messages,
+        }))
+    }
+
+    /// Lifts the fingers with the given identifiers.  As in browsers, the interrupt lists only
+    /// the lifted fingers.
+    pub fn touch_end(&mut self, identifiers: &[i64]) -> bool {
+        let touches = self.touch_messages(identifiers.to_vec());
+        for identifier in identifiers {
+            self.touches.remove(identifier);
+        }
+        self.interrupt(NativeInterrupt::TouchEnd(TouchEndInterruptArgs { touches }))
+    }
+
+    fn move_touches(&mut self, touches: &[(i64, f64, f64)]) -> HashMap<i64, (f64, f64)> {
+        let mut deltas = HashMap::new();
+        for (identifier, x, y) in touches {
+            let last = self
+                .touches
+                .insert(*identifier, (*x, *y))
+                .expect("finger is down");
+            deltas.insert(*identifier, (x - last.0, y - last.1));
+        }
+        deltas
+    }
+
+    fn touch_messages(&self, mut identifiers: Vec<i64>) -> Vec<TouchMessage> {
+        identifiers.sort();
+        identifiers
+            .into_iter()
+            .map(|identifier| {
+                let (x, y) = self.touches[&identifier];
+                TouchMessage {
+                    x,
+                    y,
+                    identifier,
+                    delta_x: 0.0,
+                    delta_y: 0.0,
+                }
+            })
+            .collect()
+    }
+
+    /// Sends the key-down, key-press and key-up sequence a browser would emit for a single key stroke.
+    /// Returns whether any handler called `prevent_default`.
+    pub fn key_press(&mut self, key: &str) -> bool {
+        self.key_press_with_modifiers(key, &[])
+    }
+
+    pub fn key_press_with_modifiers(
+        &mut self,
+        key: &str,
+        modifiers: &[ModifierKeyMessage],
+    ) -> bool {
+        let down = self.interrupt(NativeInterrupt::KeyDown(KeyDownInterruptArgs {
+            key: key.to_string(),
+            modifiers: modifiers.to_vec(),
+            is_repeat: false,
+        }));
+        let press = self.interrupt(NativeInterrupt::KeyPress(KeyPressInterruptArgs {
+            key: key.to_string(),
+            modifiers: modifiers.to_vec(),
+            is_repeat: false,
+        }));
+        let up = self.interrupt(NativeInterrupt::KeyUp(KeyUpInterruptArgs {
+            key: key.to_string(),
+            modifiers: modifiers.to_vec(),
+            is_repeat: false,
+        }));
+        down || press || up
+    }
+
+    /// Simulates the user typing `text` into the textbox with the given `id`: an input event
+    /// followed by a change event, as emitted by native textboxes on commit.
+    pub fn textbox_input(&mut self, id: &str, text: &str) -> bool {
+        let id_chain = self.get_node_by_id(id).id_chain.clone();
+        let input = self.interrupt(NativeInterrupt::FormTextboxInput(FormTextboxInputArgs {
+            text: text.to_string(),
+            id_chain: id_chain.clone(),
+        }));
+        let change = self.interrupt(NativeInterrupt::FormTextboxChange(FormTextboxChangeArgs {
+            text: text.to_string(),
+            id_chain,
+        }));
+        input || change
+    }
+
+    /// Simulates a native button click on the button with the given `id`
+    pub fn button_click(&mut self, id: &str) -> bool {
+        let id_chain = self.get_node_by_id(id).id_chain.clone();
+        self.interrupt(NativeInterrupt::FormButtonClick(FormButtonClickArgs {
+            id_chain,
+        }))
+    }
+
+    /// Simulates toggling the checkbox with the given `id` to `state`
+    pub fn checkbox_toggle(&mut self, id: &str, state: bool) -> bool {
+        let id_chain = self.get_node_by_id(id).id_chain.clone();
+        self.interrupt(NativeInterrupt::FormCheckboxToggle(
+            FormCheckboxToggleArgs { state, id_chain },
+        ))
+    }
+
+    /// All nodes whose `id` common property matches `id`
+    pub fn get_nodes_by_id(&self, id: &str) -> Vec<Rc<ExpandedNode>> {
+        self.engine.runtime_context.get_expanded_nodes_by_id(id)
+    }
+
+    /// The single node whose `id` common property matches `id`.  Panics if there is
+    /// not exactly one such node, e.g. if `id` is used inside a `for` loop.
+    pub fn get_node_by_id(&self, id: &str) -> Rc<ExpandedNode> {
+        let mut nodes = self.get_nodes_by_id(id);
+        match nodes.len() {
+            1 => nodes.remove(0),
+            n => panic!("expected exactly one node with id \"{}\", found {}", id, n),
+        }
+    }
+
+    /// Moves keyboard focus to the node with the given `id`
+    pub fn focus(&mut self, id: &str) {
+        let node = self.get_node_by_id(id);
+        self.engine.focus(Some(node));
+    }
+
+    /// The node currently holding keyboard focus, if any
+    pub fn focused_node(&self) -> Option<Rc<ExpandedNode>> {
+        self.engine.runtime_context.focused_node()
+    }
+
+    /// The mouse cursor a chassis would currently show, as resolved from the hovered nodes
+    pub fn cursor(&self) -> Cursor {
+        self.engine.cursor()
+    }
+
+    /// Runs `f` against the computed properties of the node with the given `id`.
+    /// `T` must be the properties type of that node, e.g. `Rectangle` or a user component.
+    pub fn with_properties<T: 'static, R>(&self, id: &str, f: impl FnOnce(&mut T) -> R) -> R {
+        self.get_node_by_id(id).with_properties_unwrapped(f)
+    }
+
+    /// Computed transform and bounds of the node with the given `id`, as of the last tick
+    pub fn computed_tab(&self, id: &str) -> TransformAndBounds {
+        self.get_node_by_id(id)
+            .layout_properties
+            .borrow()
+            .as_ref()
+            .map(|layout| layout.computed_tab.clone())
+            .expect("node has been laid out; call tick() first")
+    }
+
+    /// Center of the node with the given `id` in window coordinates
+    pub fn center_of(&self, id: &str) -> (f64, f64) {
+        let tab = self.computed_tab(id);
+        let (width, height) = tab.bounds;
+        let center = tab.transform * Point2::new(width / 2.0, height / 2.0);
+        (center.x, center.y)
+    }
+
+    /// `NativeMessage`s emitted since the harness was created or last drained
+    pub fn messages(&self) -> &[NativeMessage] {
+        &self.messages
+    }
+
+    /// Drains collected `NativeMessage`s
+    pub fn take_messages(&mut self) -> Vec<NativeMessage> {
+        std::mem::take(&mut self.messages)
+    }
+
+    /// Renders the current state of the engine, e.g. for snapshot assertions
+    pub fn render(&mut self, renderer: &mut RasterRenderer) {
+        renderer.render(&mut self.engine);
+    }
+}
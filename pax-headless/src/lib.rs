@@ -0,0 +1,33 @@
+//! Chassis-less backends for Pax: render a `PaxEngine` on any machine, without a browser or native window,
+//! e.g. for visual regression testing of components in CI or exporting vector assets, and a
+//! [`TestHarness`] for driving an engine with synthetic user input in tests.
+
+use pax_runtime::PaxEngine;
+
+mod brush;
+pub mod harness;
+pub mod raster;
+pub mod svg;
+
+pub use brush::Brush;
+pub use harness::TestHarness;
+pub use raster::{RasterRenderContext, RasterRenderer};
+pub use svg::{SvgRenderContext, SvgRenderer};
+
+/// Ids of the occlusion layers `engine` will render into, as of its most recent tick.
+/// Layer ids follow the convention used by primitives, i.e. the stringified occlusion index.
+pub(crate) fn layer_ids(engine: &PaxEngine) -> impl Iterator<Item = String> {
+    let layer_count = engine
+        .runtime_context
+        .z_index_node_cache
+        .iter()
+        .map(|node| *node.occlusion_id.borrow() + 1)
+        .max()
+        .unwrap_or(1);
+    (0..layer_count).map(|layer| format!("{}", layer))
+}
+
+/// Sorts layer ids bottom-most first
+pub(crate) fn sort_layers<T>(layers: &mut [(&String, T)]) {
+    layers.sort_by_key(|(id, _)| id.parse::<u32>().unwrap_or(u32::MAX));
+}
@@ -0,0 +1,654 @@
+use std::path::Path;
+use std::rc::Rc;
+
+use kurbo::{Affine, BezPath, PathEl, Point, Rect, Shape, Size};
+use piet::{
+    Color, Error, FixedGradient, Image, ImageFormat, InterpolationMode, IntoBrush, LineCap,
+    LineJoin, NullText, NullTextLayout, StrokeStyle,
+};
+use tiny_skia::{
+    FillRule, FilterQuality, GradientStop, IntRect, LinearGradient, Mask, Paint, Pattern, Pixmap,
+    PixmapPaint, PremultipliedColorU8, RadialGradient, Shader, SpreadMode, Stroke, StrokeDash,
+    Transform,
+};
+
+use crate::brush::Brush;
+use pax_runtime::api::RenderContext;
+use pax_runtime::{LayerCompositing, NativeBlur, PaxEngine, Renderer};
+
+/// Tolerance used when flattening kurbo shapes (e.g. rounded rects, ellipses) into paths
+const SHAPE_TOLERANCE: f64 = 0.1;
+
+/// A software-rasterized, chassis-less renderer for a `PaxEngine`.  Owns one
+/// [`RasterRenderContext`] (and thus one pixmap) per occlusion layer, and composites them in
+/// occlusion order when producing the final image.  Translucent groups are composited offscreen,
+/// as a whole.
+///
+/// Native elements (Text, form controls) are rendered by chassis and thus are not present in the output.
+pub struct RasterRenderer {
+    renderer: Renderer<RasterRenderContext>,
+    width: u32,
+    height: u32,
+}
+
+impl RasterRenderer {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            renderer: Renderer::new().with_layer_compositing().with_native_blur(),
+            width,
+            height,
+        }
+    }
+
+    pub fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// Clears all layers and renders the current state of `engine` into them.
+    /// Expects `engine.tick()` to have been called at least once, so that layout and occlusion are computed.
+    pub fn render(&mut self, engine: &mut PaxEngine) {
+        for id in crate::layer_ids(engine) {
+            if !self.renderer.backends.contains_key(&id) {
+                self.renderer
+                    .add_context(&id, RasterRenderContext::new(self.width, self.height));
+            }
+        }
+        for backend in self.renderer.backends.values_mut() {
+            backend.reset();
+        }
+        engine.render(&mut self.renderer as &mut dyn RenderContext);
+    }
+
+    /// Makes an image available to `Image` primitives, as chassis do upon receiving `NativeMessage::ImageLoad`.
+    /// `buf` is expected to contain non-premultiplied RGBA pixels.
+    pub fn load_image(&mut self, path: &str, buf: &[u8], width: usize, height: usize) {
+        if self.renderer.backends.is_empty() {
+            self.renderer
+                .add_context("0", RasterRenderContext::new(self.width, self.height));
+        }
+        RenderContext::load_image(&mut self.renderer, path, buf, width, height);
+    }
+
+    pub fn image_loaded(&self, path: &str) -> bool {
+        self.renderer.image_loaded(path)
+    }
+
+    /// Returns the pixmap backing a single layer, if that layer has been rendered
+    pub fn layer(&self, layer: &str) -> Option<&Pixmap> {
+        self.renderer.backends.get(layer).map(|b| &b.pixmap)
+    }
+
+    /// Flattens all layers, bottom-most occlusion layer first, into a single pixmap
+    pub fn composite(&self) -> Pixmap {
+        let mut ret = Pixmap::new(self.width.max(1), self.height.max(1)).unwrap();
+        let mut layers: Vec<_> = self.renderer.backends.iter().collect();
+        crate::sort_layers(&mut layers);
+        for (_, backend) in layers {
+            ret.draw_pixmap(
+                0,
+                0,
+                backend.pixmap.as_ref(),
+                &PixmapPaint::default(),
+                Transform::identity(),
+                None,
+            );
+        }
+        ret
+    }
+
+    /// Composites all layers and encodes the result as a PNG
+    pub fn encode_png(&self) -> Result<Vec<u8>, std::io::Error> {
+        self.composite().encode_png().map_err(std::io::Error::other)
+    }
+
+    /// Composites all layers and writes the result as a PNG to `path`
+    pub fn save_png(&self, path: impl AsRef<Path>) -> Result<(), std::io::Error> {
+        std::fs::write(path, self.encode_png()?)
+    }
+}
+
+#[derive(Clone)]
+pub struct RasterImage(Rc<Pixmap>);
+
+impl RasterImage {
+    pub fn pixmap(&self) -> &Pixmap {
+        &self.0
+    }
+}
+
+impl Image for RasterImage {
+    fn size(&self) -> Size {
+        Size::new(self.0.width() as f64, self.0.height() as f64)
+    }
+}
+
+#[derive(Clone)]
+struct DrawState {
+    transform: Affine,
+    clip: Option<Mask>,
+}
+
+/// A `piet::RenderContext` drawing into a single in-memory pixmap via tiny-skia.
+/// Text is not supported (Pax renders text natively.)
+pub struct RasterRenderContext {
+    pixmap: Pixmap,
+    /// While drawing into offscreen layers, the pixmaps beneath `pixmap`, bottom-most first,
+    /// each with the opacity the layer above it is to be composited at
+    layers_beneath: Vec<(Pixmap, f64)>,
+    state: DrawState,
+    saved_states: Vec<DrawState>,
+    text: NullText,
+}
+
+impl RasterRenderContext {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            pixmap: Pixmap::new(width.max(1), height.max(1)).unwrap(),
+            layers_beneath: Vec::new(),
+            state: DrawState {
+                transform: Affine::IDENTITY,
+                clip: None,
+            },
+            saved_states: Vec::new(),
+            text: NullText,
+        }
+    }
+
+    pub fn pixmap(&self) -> &Pixmap {
+        &self.pixmap
+    }
+
+    /// Clears to transparent and drops any transform, clip, saved state or offscreen layer
+    pub fn reset(&mut self) {
+        if let Some((bottom, _)) = self.layers_beneath.drain(..).next() {
+            self.pixmap = bottom;
+        }
+        self.pixmap.fill(tiny_skia::Color::TRANSPARENT);
+        self.state = DrawState {
+            transform: Affine::IDENTITY,
+            clip: None,
+        };
+        self.saved_states.clear();
+    }
+
+    fn paint_for<'a>(&mut self, brush: &impl IntoBrush<Self>, bbox: Rect) -> Paint<'a> {
+        let brush = brush.make_brush(self, || bbox).into_owned();
+        Paint {
+            shader: to_shader(&brush),
+            anti_alias: true,
+            ..Default::default()
+        }
+    }
+
+    fn fill_with_rule(&mut self, shape: impl Shape, brush: &impl IntoBrush<Self>, rule: FillRule) {
+        let Some(path) = to_skia_path(shape.path_elements(SHAPE_TOLERANCE)) else {
+            return;
+        };
+        let paint = self.paint_for(brush, shape.bounding_box());
+        self.pixmap.fill_path(
+            &path,
+            &paint,
+            rule,
+            to_skia_transform(self.state.transform),
+            self.state.clip.as_ref(),
+        );
+    }
+
+    /// Draws what `draw` draws, unclipped and blurred by `blur_radius`, then clipped as usual
+    fn draw_blurred(&mut self, blur_radius: f64, draw: impl FnOnce(&mut Self)) {
+        let mut scratch = Pixmap::new(self.pixmap.width(), self.pixmap.height()).unwrap();
+        std::mem::swap(&mut self.pixmap, &mut scratch);
+        let clip = self.state.clip.take();
+        draw(self);
+        self.state.clip = clip;
+        std::mem::swap(&mut self.pixmap, &mut scratch);
+        box_blur(&mut scratch, blur_radius);
+        self.pixmap.draw_pixmap(
+            0,
+            0,
+            scratch.as_ref(),
+            &PixmapPaint::default(),
+            Transform::identity(),
+            self.state.clip.as_ref(),
+        );
+    }
+}
+
+impl NativeBlur for RasterRenderContext {
+    fn fill_blurred(&mut self, path: BezPath, color: Color, blur_radius: f64) {
+        self.draw_blurred(blur_radius, |rc| {
+            piet::RenderContext::fill(rc, path, &color)
+        });
+    }
+
+    fn stroke_blurred(&mut self, path: BezPath, color: Color, width: f64, blur_radius: f64) {
+        self.draw_blurred(blur_radius, |rc| {
+            piet::RenderContext::stroke(rc, path, &color, width)
+        });
+    }
+}
+
+impl LayerCompositing for RasterRenderContext {
+    fn push_layer(&mut self, opacity: f64) {
+        let layer = Pixmap::new(self.pixmap.width(), self.pixmap.height()).unwrap();
+        self.layers_beneath
+            .push((std::mem::replace(&mut self.pixmap, layer), opacity));
+    }
+
+    fn pop_layer(&mut self) {
+        let Some((beneath, opacity)) = self.layers_beneath.pop() else {
+            return;
+        };
+        let layer = std::mem::replace(&mut self.pixmap, beneath);
+        // Draws into the layer were already transformed and clipped
+        self.pixmap.draw_pixmap(
+            0,
+            0,
+            layer.as_ref(),
+            &PixmapPaint {
+                opacity: opacity as f32,
+                ..Default::default()
+            },
+            Transform::identity(),
+            None,
+        );
+    }
+}
+
+impl piet::RenderContext for RasterRenderContext {
+    type Brush = Brush;
+    type Text = NullText;
+    type TextLayout = NullTextLayout;
+    type Image = RasterImage;
+
+    fn status(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn solid_brush(&mut self, color: Color) -> Self::Brush {
+        Brush::Solid(color)
+    }
+
+    fn gradient(&mut self, gradient: impl Into<FixedGradient>) -> Result<Self::Brush, Error> {
+        Ok(Brush::Gradient(gradient.into()))
+    }
+
+    fn clear(&mut self, region: impl Into<Option<Rect>>, color: Color) {
+        // As with other piet backends, clearing ignores the current transform and clip
+        match region.into() {
+            None => self.pixmap.fill(to_skia_color(color)),
+            Some(rect) => {
+                let Some(rect) = tiny_skia::Rect::from_ltrb(
+                    rect.x0 as f32,
+                    rect.y0 as f32,
+                    rect.x1 as f32,
+                    rect.y1 as f32,
+                ) else {
+                    return;
+                };
+                let mut paint = Paint::default();
+                paint.set_color(to_skia_color(color));
+                paint.blend_mode = tiny_skia::BlendMode::Source;
+                self.pixmap
+                    .fill_rect(rect, &paint, Transform::identity(), None);
+            }
+        }
+    }
+
+    fn stroke(&mut self, shape: impl Shape, brush: &impl IntoBrush<Self>, width: f64) {
+        self.stroke_styled(shape, brush, width, &StrokeStyle::default());
+    }
+
+    fn stroke_styled(
+        &mut self,
+        shape: impl Shape,
+        brush: &impl IntoBrush<Self>,
+        width: f64,
+        style: &StrokeStyle,
+    ) {
+        let Some(path) = to_skia_path(shape.path_elements(SHAPE_TOLERANCE)) else {
+            return;
+        };
+        let paint = self.paint_for(brush, shape.bounding_box());
+        let mut stroke = Stroke {
+            width: width as f32,
+            line_cap: match style.line_cap {
+                LineCap::Butt => tiny_skia::LineCap::Butt,
+                LineCap::Round => tiny_skia::LineCap::Round,
+                LineCap::Square => tiny_skia::LineCap::Square,
+            },
+            ..Default::default()
+        };
+        match style.line_join {
+            LineJoin::Miter { limit } => {
+                stroke.line_join = tiny_skia::LineJoin::Miter;
+                stroke.miter_limit = limit as f32;
+            }
+            LineJoin::Round => stroke.line_join = tiny_skia::LineJoin::Round,
+            LineJoin::Bevel => stroke.line_join = tiny_skia::LineJoin::Bevel,
+        }
+        if !style.dash_pattern.is_empty() {
+            stroke.dash = StrokeDash::new(
+                style.dash_pattern.iter().map(|d| *d as f32).collect(),
+                style.dash_offset as f32,
+            );
+        }
+        self.pixmap.stroke_path(
+            &path,
+            &paint,
+            &stroke,
+            to_skia_transform(self.state.transform),
+            self.state.clip.as_ref(),
+        );
+    }
+
+    fn fill(&mut self, shape: impl Shape, brush: &impl IntoBrush<Self>) {
+        self.fill_with_rule(shape, brush, FillRule::Winding);
+    }
+
+    fn fill_even_odd(&mut self, shape: impl Shape, brush: &impl IntoBrush<Self>) {
+        self.fill_with_rule(shape, brush, FillRule::EvenOdd);
+    }
+
+    fn clip(&mut self, shape: impl Shape) {
+        let transform = to_skia_transform(self.state.transform);
+        let Some(path) = to_skia_path(shape.path_elements(SHAPE_TOLERANCE)) else {
+            // Clipping to an empty shape clips everything
+            self.state.clip = Mask::new(self.pixmap.width(), self.pixmap.height());
+            return;
+        };
+        match self.state.clip {
+            Some(ref mut mask) => mask.intersect_path(&path, FillRule::Winding, true, transform),
+            None => {
+                let mut mask = Mask::new(self.pixmap.width(), self.pixmap.height()).unwrap();
+                mask.fill_path(&path, FillRule::Winding, true, transform);
+                self.state.clip = Some(mask);
+            }
+        }
+    }
+
+    fn text(&mut self) -> &mut Self::Text {
+        &mut self.text
+    }
+
+    fn draw_text(&mut self, _layout: &Self::TextLayout, _pos: impl Into<Point>) {}
+
+    fn save(&mut self) -> Result<(), Error> {
+        self.saved_states.push(self.state.clone());
+        Ok(())
+    }
+
+    fn restore(&mut self) -> Result<(), Error> {
+        self.state = self.saved_states.pop().ok_or(Error::StackUnbalance)?;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn transform(&mut self, transform: Affine) {
+        self.state.transform *= transform;
+    }
+
+    fn make_image(
+        &mut self,
+        width: usize,
+        height: usize,
+        buf: &[u8],
+        format: ImageFormat,
+    ) -> Result<Self::Image, Error> {
+        let mut pixmap = Pixmap::new(width as u32, height as u32).ok_or(Error::InvalidInput)?;
+        let bytes_per_pixel = format.bytes_per_pixel();
+        if buf.len() < width * height * bytes_per_pixel {
+            return Err(Error::InvalidInput);
+        }
+        for (pixel, src) in pixmap
+            .pixels_mut()
+            .iter_mut()
+            .zip(buf.chunks_exact(bytes_per_pixel))
+        {
+            *pixel = match format {
+                ImageFormat::Grayscale => {
+                    tiny_skia::ColorU8::from_rgba(src[0], src[0], src[0], 255).premultiply()
+                }
+                ImageFormat::Rgb => {
+                    tiny_skia::ColorU8::from_rgba(src[0], src[1], src[2], 255).premultiply()
+                }
+                ImageFormat::RgbaSeparate => {
+                    tiny_skia::ColorU8::from_rgba(src[0], src[1], src[2], src[3]).premultiply()
+                }
+                ImageFormat::RgbaPremul => {
+                    PremultipliedColorU8::from_rgba(src[0], src[1], src[2], src[3])
+                        .ok_or(Error::InvalidInput)?
+                }
+                _ => return Err(Error::NotSupported),
+            };
+        }
+        Ok(RasterImage(Rc::new(pixmap)))
+    }
+
+    fn draw_image(
+        &mut self,
+        image: &Self::Image,
+        dst_rect: impl Into<Rect>,
+        interp: InterpolationMode,
+    ) {
+        let src_rect = Rect::from_origin_size(Point::ORIGIN, image.size());
+        self.draw_image_area(image, src_rect, dst_rect, interp);
+    }
+
+    fn draw_image_area(
+        &mut self,
+        image: &Self::Image,
+        src_rect: impl Into<Rect>,
+        dst_rect: impl Into<Rect>,
+        interp: InterpolationMode,
+    ) {
+        let (src_rect, dst_rect) = (src_rect.into(), dst_rect.into());
+        if src_rect.width() <= 0.0 || src_rect.height() <= 0.0 {
+            return;
+        }
+        let Some(dst) = tiny_skia::Rect::from_ltrb(
+            dst_rect.x0 as f32,
+            dst_rect.y0 as f32,
+            dst_rect.x1 as f32,
+            dst_rect.y1 as f32,
+        ) else {
+            return;
+        };
+        let scale_x = dst_rect.width() / src_rect.width();
+        let scale_y = dst_rect.height() / src_rect.height();
+        let pattern_transform = Transform::from_row(
+            scale_x as f32,
+            0.0,
+            0.0,
+            scale_y as f32,
+            (dst_rect.x0 - src_rect.x0 * scale_x) as f32,
+            (dst_rect.y0 - src_rect.y0 * scale_y) as f32,
+        );
+        let quality = match interp {
+            InterpolationMode::NearestNeighbor => FilterQuality::Nearest,
+            InterpolationMode::Bilinear => FilterQuality::Bilinear,
+        };
+        let paint = Paint {
+            shader: Pattern::new(
+                image.0.as_ref().as_ref(),
+                SpreadMode::Pad,
+                quality,
+                1.0,
+                pattern_transform,
+            ),
+            anti_alias: true,
+            ..Default::default()
+        };
+        self.pixmap.fill_rect(
+            dst,
+            &paint,
+            to_skia_transform(self.state.transform),
+            self.state.clip.as_ref(),
+        );
+    }
+
+    fn capture_image_area(&mut self, src_rect: impl Into<Rect>) -> Result<Self::Image, Error> {
+        let rect = src_rect.into();
+        let rect = IntRect::from_xywh(
+            rect.x0 as i32,
+            rect.y0 as i32,
+            rect.width() as u32,
+            rect.height() as u32,
+        )
+        .ok_or(Error::InvalidInput)?;
+        let captured = self.pixmap.clone_rect(rect).ok_or(Error::InvalidInput)?;
+        Ok(RasterImage(Rc::new(captured)))
+    }
+
+    fn blurred_rect(&mut self, rect: Rect, blur_radius: f64, brush: &impl IntoBrush<Self>) {
+        self.draw_blurred(blur_radius, |rc| rc.fill(rect, brush));
+    }
+
+    fn current_transform(&self) -> Affine {
+        self.state.transform
+    }
+}
+
+fn to_skia_color(color: Color) -> tiny_skia::Color {
+    let (r, g, b, a) = color.as_rgba8();
+    tiny_skia::Color::from_rgba8(r, g, b, a)
+}
+
+fn to_skia_transform(affine: Affine) -> Transform {
+    let [a, b, c, d, e, f] = affine.as_coeffs();
+    Transform::from_row(a as f32, b as f32, c as f32, d as f32, e as f32, f as f32)
+}
+
+fn to_skia_point(point: Point) -> tiny_skia::Point {
+    tiny_skia::Point::from_xy(point.x as f32, point.y as f32)
+}
+
+fn to_skia_stops(stops: &[piet::GradientStop]) -> Vec<GradientStop> {
+    stops
+        .iter()
+        .map(|stop| GradientStop::new(stop.pos, to_skia_color(stop.color)))
+        .collect()
+}
+
+fn to_shader(brush: &Brush) -> Shader<'static> {
+    let shader = match brush {
+        Brush::Solid(color) => return Shader::SolidColor(to_skia_color(*color)),
+        Brush::Gradient(FixedGradient::Linear(linear)) => LinearGradient::new(
+            to_skia_point(linear.start),
+            to_skia_point(linear.end),
+            to_skia_stops(&linear.stops),
+            SpreadMode::Pad,
+            Transform::identity(),
+        ),
+        Brush::Gradient(FixedGradient::Radial(radial)) => RadialGradient::new(
+            to_skia_point(radial.center + radial.origin_offset),
+            to_skia_point(radial.center),
+            radial.radius as f32,
+            to_skia_stops(&radial.stops),
+            SpreadMode::Pad,
+            Transform::identity(),
+        ),
+    };
+    // Degenerate gradients (e.g. zero length or radius) fall back to their final stop color, as browsers do
+    shader.unwrap_or_else(|| {
+        let stops = match brush {
+            Brush::Gradient(FixedGradient::Linear(linear)) => &linear.stops,
+            Brush::Gradient(FixedGradient::Radial(radial)) => &radial.stops,
+            Brush::Solid(_) => unreachable!(),
+        };
+        Shader::SolidColor(
+            stops
+                .last()
+                .map(|stop| to_skia_color(stop.color))
+                .unwrap_or(tiny_skia::Color::TRANSPARENT),
+        )
+    })
+}
+
+fn to_skia_path(elements: impl Iterator<Item = PathEl>) -> Option<tiny_skia::Path> {
+    let mut builder = tiny_skia::PathBuilder::new();
+    for el in elements {
+        match el {
+            PathEl::MoveTo(p) => builder.move_to(p.x as f32, p.y as f32),
+            PathEl::LineTo(p) => builder.line_to(p.x as f32, p.y as f32),
+            PathEl::QuadTo(p1, p2) => {
+                builder.quad_to(p1.x as f32, p1.y as f32, p2.x as f32, p2.y as f32)
+            }
+            PathEl::CurveTo(p1, p2, p3) => builder.cubic_to(
+                p1.x as f32,
+                p1.y as f32,
+                p2.x as f32,
+                p2.y as f32,
+                p3.x as f32,
+                p3.y as f32,
+            ),
+            PathEl::ClosePath => builder.close(),
+        }
+    }
+    builder.finish()
+}
+
+/// Approximates a gaussian blur with standard deviation `radius`, as in CSS and SVG, via three
+/// successive box blurs
+pub(crate) fn box_blur(pixmap: &mut Pixmap, radius: f64) {
+    let box_radii = boxes_for_gauss(radius).map(|width| (width - 1) / 2);
+    if box_radii.iter().all(|&box_radius| box_radius == 0) {
+        return;
+    }
+    let (width, height) = (pixmap.width() as usize, pixmap.height() as usize);
+    let data = pixmap.data_mut();
+    let mut scratch = vec![0u8; data.len()];
+    for box_radius in box_radii {
+        blur_pass(data, &mut scratch, width, height, box_radius, 4, width * 4);
+        blur_pass(&scratch, data, height, width, box_radius, width * 4, 4);
+    }
+}
+
+/// The odd widths of three successive box blurs that together approximate a gaussian blur with
+/// standard deviation `sigma` (see <http://blog.ivank.net/fastest-gaussian-blur.html>)
+fn boxes_for_gauss(sigma: f64) -> [usize; 3] {
+    let n = 3.0;
+    let ideal_width = (12.0 * sigma * sigma / n + 1.0).sqrt();
+    let mut lower = ideal_width.floor().max(1.0);
+    if lower % 2.0 == 0.0 {
+        lower -= 1.0;
+    }
+    let upper = lower + 2.0;
+    let ideal_lower_count = (12.0 * sigma * sigma - n * lower * lower - 4.0 * n * lower - 3.0 * n)
+        / (-4.0 * lower - 4.0);
+    let lower_count = ideal_lower_count.round().max(0.0) as usize;
+    std::array::from_fn(|i| (if i < lower_count { lower } else { upper }) as usize)
+}
+
+/// A single one-dimensional box blur over premultiplied RGBA bytes. `lines` lines of `len` pixels each,
+/// where consecutive pixels within a line are `step` bytes apart and consecutive lines `stride` bytes apart.
+fn blur_pass(
+    src: &[u8],
+    dst: &mut [u8],
+    len: usize,
+    lines: usize,
+    radius: usize,
+    step: usize,
+    stride: usize,
+) {
+    let window = (2 * radius + 1) as u32;
+    for line in 0..lines {
+        let base = line * stride;
+        for channel in 0..4 {
+            let at = |i: usize| src[base + i * step + channel] as u32;
+            let mut sum: u32 = (0..=radius.min(len - 1)).map(at).sum();
+            for i in 0..len {
+                dst[base + i * step + channel] = (sum / window) as u8;
+                if i + radius + 1 < len {
+                    sum += at(i + radius + 1);
+                }
+                if i >= radius {
+                    sum -= at(i - radius);
+                }
+            }
+        }
+    }
+}
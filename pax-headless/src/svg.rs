@@ -0,0 +1,601 @@
+use std::fmt::Write;
+use std::path::Path;
+use std::rc::Rc;
+
+use kurbo::{Affine, BezPath, Point, Rect, Shape, Size};
+use piet::{
+    Color, Error, FixedGradient, GradientStop, Image, ImageFormat, InterpolationMode, IntoBrush,
+    LineCap, LineJoin, NullText, NullTextLayout, StrokeStyle,
+};
+
+use crate::brush::Brush;
+use crate::RasterRenderContext;
+use pax_runtime::api::RenderContext;
+use pax_runtime::{LayerCompositing, NativeBlur, PaxEngine, Renderer};
+
+/// Tolerance used when flattening kurbo shapes (e.g. rounded rects, ellipses) into paths
+const SHAPE_TOLERANCE: f64 = 0.1;
+
+/// Records the rendering of a `PaxEngine` into an SVG document, with one `<g>` per occlusion layer
+/// (bottom-most first.)  Gradients are preserved as SVG `<linearGradient>` / `<radialGradient>`s, clips as `<clipPath>`s
+/// and translucent groups as `<g opacity="…">`s.
+///
+/// Native elements (Text, form controls) are rendered by chassis and thus are not present in the output.
+pub struct SvgRenderer {
+    renderer: Renderer<SvgRenderContext>,
+    width: f64,
+    height: f64,
+}
+
+impl SvgRenderer {
+    pub fn new(width: f64, height: f64) -> Self {
+        Self {
+            renderer: Renderer::new().with_layer_compositing().with_native_blur(),
+            width,
+            height,
+        }
+    }
+
+    /// Discards previously recorded content and records the current state of `engine`.
+    /// Expects `engine.tick()` to have been called at least once, so that layout and occlusion are computed.
+    pub fn render(&mut self, engine: &mut PaxEngine) {
+        for id in crate::layer_ids(engine) {
+            if !self.renderer.backends.contains_key(&id) {
+                let context = SvgRenderContext::new(&format!("layer-{}", id));
+                self.renderer.add_context(&id, context);
+            }
+        }
+        for backend in self.renderer.backends.values_mut() {
+            backend.reset();
+        }
+        engine.render(&mut self.renderer as &mut dyn RenderContext);
+    }
+
+    /// Makes an image available to `Image` primitives, as chassis do upon receiving `NativeMessage::ImageLoad`.
+    /// `buf` is expected to contain non-premultiplied RGBA pixels.  Images are embedded as PNG data URIs.
+    pub fn load_image(&mut self, path: &str, buf: &[u8], width: usize, height: usize) {
+        if self.renderer.backends.is_empty() {
+            self.renderer
+                .add_context("0", SvgRenderContext::new("layer-0"));
+        }
+        RenderContext::load_image(&mut self.renderer, path, buf, width, height);
+    }
+
+    pub fn image_loaded(&self, path: &str) -> bool {
+        self.renderer.image_loaded(path)
+    }
+
+    /// Serializes all recorded layers into a standalone SVG document
+    pub fn to_svg_string(&self) -> String {
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = self.width,
+            h = self.height
+        );
+        let mut layers: Vec<_> = self.renderer.backends.iter().collect();
+        crate::sort_layers(&mut layers);
+        for (_, backend) in layers {
+            backend.write_layer(&mut svg);
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    pub fn save_svg(&self, path: impl AsRef<Path>) -> Result<(), std::io::Error> {
+        std::fs::write(path, self.to_svg_string())
+    }
+}
+
+#[derive(Clone)]
+pub struct SvgImage {
+    size: Size,
+    data_uri: Rc<String>,
+}
+
+impl Image for SvgImage {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+#[derive(Clone, Copy)]
+struct DrawState {
+    transform: Affine,
+    /// Number of `<g>` elements opened (e.g. for clipping) since this state became current
+    open_groups: usize,
+}
+
+/// A `piet::RenderContext` recording draw calls as SVG elements for a single layer.
+/// Text is not supported (Pax renders text natively.)
+pub struct SvgRenderContext {
+    id: String,
+    defs: String,
+    body: String,
+    next_def_id: usize,
+    state: DrawState,
+    saved_states: Vec<DrawState>,
+    /// Per pushed layer, innermost last, the `open_groups` of the state it was pushed in
+    layer_depths: Vec<usize>,
+    text: NullText,
+}
+
+impl SvgRenderContext {
+    /// `id` is used as the `id` of this layer's `<g>`, and as a prefix for the ids of its defs
+    pub fn new(id: &str) -> Self {
+        Self {
+            id: id.to_owned(),
+            defs: String::new(),
+            body: String::new(),
+            next_def_id: 0,
+            state: DrawState {
+                transform: Affine::IDENTITY,
+                open_groups: 0,
+            },
+            saved_states: Vec::new(),
+            layer_depths: Vec::new(),
+            text: NullText,
+        }
+    }
+
+    /// Discards all recorded content, transform, clip and saved state
+    pub fn reset(&mut self) {
+        *self = Self::new(&self.id);
+    }
+
+    /// Writes this layer as a `<g>` element, closing any groups left open by unbalanced `save`s
+    pub fn write_layer(&self, svg: &mut String) {
+        let _ = writeln!(svg, r#"<g id="{}">"#, self.id);
+        if !self.defs.is_empty() {
+            let _ = write!(svg, "<defs>\n{}</defs>\n", self.defs);
+        }
+        svg.push_str(&self.body);
+        let open_groups = self.state.open_groups
+            + self
+                .saved_states
+                .iter()
+                .map(|s| s.open_groups)
+                .sum::<usize>();
+        for _ in 0..open_groups {
+            svg.push_str("</g>\n");
+        }
+        svg.push_str("</g>\n");
+    }
+
+    fn new_def_id(&mut self, kind: &str) -> String {
+        self.next_def_id += 1;
+        format!("{}-{}{}", self.id, kind, self.next_def_id)
+    }
+
+    /// Returns the SVG paint (e.g. `rgb(…)` or `url(#…)`) and opacity for a brush, registering a gradient def if needed
+    fn paint_for(&mut self, brush: &impl IntoBrush<Self>, bbox: Rect) -> (String, f64) {
+        let brush = brush.make_brush(self, || bbox).into_owned();
+        match brush {
+            Brush::Solid(color) => {
+                let (r, g, b, a) = color.as_rgba8();
+                (format!("rgb({},{},{})", r, g, b), a as f64 / 255.0)
+            }
+            Brush::Gradient(FixedGradient::Linear(linear)) => {
+                let id = self.new_def_id("gradient");
+                let _ = writeln!(
+                    self.defs,
+                    r#"<linearGradient id="{}" gradientUnits="userSpaceOnUse" x1="{}" y1="{}" x2="{}" y2="{}">{}</linearGradient>"#,
+                    id,
+                    linear.start.x,
+                    linear.start.y,
+                    linear.end.x,
+                    linear.end.y,
+                    svg_stops(&linear.stops)
+                );
+                (format!("url(#{})", id), 1.0)
+            }
+            Brush::Gradient(FixedGradient::Radial(radial)) => {
+                let id = self.new_def_id("gradient");
+                let focus = radial.center + radial.origin_offset;
+                let _ = writeln!(
+                    self.defs,
+                    r#"<radialGradient id="{}" gradientUnits="userSpaceOnUse" cx="{}" cy="{}" r="{}" fx="{}" fy="{}">{}</radialGradient>"#,
+                    id,
+                    radial.center.x,
+                    radial.center.y,
+                    radial.radius,
+                    focus.x,
+                    focus.y,
+                    svg_stops(&radial.stops)
+                );
+                (format!("url(#{})", id), 1.0)
+            }
+        }
+    }
+
+    fn transform_attr(&self) -> String {
+        svg_transform(self.state.transform)
+    }
+
+    fn fill_with_rule(&mut self, shape: impl Shape, brush: &impl IntoBrush<Self>, rule: &str) {
+        let (paint, opacity) = self.paint_for(brush, shape.bounding_box());
+        let _ = writeln!(
+            self.body,
+            r#"<path d="{}" fill="{}"{}{}{}/>"#,
+            svg_path(&shape),
+            paint,
+            opacity_attr("fill-opacity", opacity),
+            if rule == "nonzero" {
+                String::new()
+            } else {
+                format!(r#" fill-rule="{}""#, rule)
+            },
+            self.transform_attr()
+        );
+    }
+
+    /// Registers a `feGaussianBlur` filter blurring content within `bounds` by `blur_radius`, and
+    /// returns its id
+    fn blur_filter(&mut self, bounds: Rect, blur_radius: f64) -> String {
+        let id = self.new_def_id("blur");
+        // The filter region, in user space: blurred content spreads ~3 standard deviations
+        let region = bounds.inflate(3.0 * blur_radius, 3.0 * blur_radius);
+        let _ = writeln!(
+            self.defs,
+            r#"<filter id="{}" filterUnits="userSpaceOnUse" x="{}" y="{}" width="{}" height="{}"><feGaussianBlur stdDeviation="{}"/></filter>"#,
+            id,
+            region.x0,
+            region.y0,
+            region.width(),
+            region.height(),
+            blur_radius
+        );
+        id
+    }
+
+    /// Writes what `draw` draws within a group blurred by `blur_radius`, given the bounds of
+    /// what's drawn
+    fn write_blurred(&mut self, bounds: Rect, blur_radius: f64, draw: impl FnOnce(&mut Self)) {
+        let id = self.blur_filter(bounds, blur_radius);
+        // The group is transformed in place of its content, so that the filter blurs in the
+        // same space as `blurred_rect`
+        let _ = writeln!(
+            self.body,
+            r#"<g filter="url(#{})"{}>"#,
+            id,
+            self.transform_attr()
+        );
+        let transform = std::mem::replace(&mut self.state.transform, Affine::IDENTITY);
+        draw(self);
+        self.state.transform = transform;
+        self.body.push_str("</g>\n");
+    }
+
+    fn write_image(&mut self, image: &SvgImage, src_rect: Rect, dst_rect: Rect) {
+        let _ = writeln!(
+            self.body,
+            r#"<svg x="{}" y="{}" width="{}" height="{}" viewBox="{} {} {} {}" preserveAspectRatio="none" overflow="hidden"{}><image width="{}" height="{}" xlink:href="{}"/></svg>"#,
+            dst_rect.x0,
+            dst_rect.y0,
+            dst_rect.width(),
+            dst_rect.height(),
+            src_rect.x0,
+            src_rect.y0,
+            src_rect.width(),
+            src_rect.height(),
+            self.transform_attr(),
+            image.size.width,
+            image.size.height,
+            image.data_uri
+        );
+    }
+}
+
+/// Blurred shapes are drawn within `<g filter="…">` elements
+impl NativeBlur for SvgRenderContext {
+    fn fill_blurred(&mut self, path: BezPath, color: Color, blur_radius: f64) {
+        let bounds = path.bounding_box();
+        self.write_blurred(bounds, blur_radius, |rc| {
+            piet::RenderContext::fill(rc, path, &color)
+        });
+    }
+
+    fn stroke_blurred(&mut self, path: BezPath, color: Color, width: f64, blur_radius: f64) {
+        let bounds = path.bounding_box().inflate(width / 2.0, width / 2.0);
+        self.write_blurred(bounds, blur_radius, |rc| {
+            piet::RenderContext::stroke(rc, path, &color, width)
+        });
+    }
+}
+
+/// Layers are `<g opacity="…">` elements, which SVG composites as a whole
+impl LayerCompositing for SvgRenderContext {
+    fn push_layer(&mut self, opacity: f64) {
+        self.layer_depths.push(self.state.open_groups);
+        let _ = writeln!(self.body, r#"<g opacity="{}">"#, opacity);
+        self.state.open_groups += 1;
+    }
+
+    fn pop_layer(&mut self) {
+        let Some(depth) = self.layer_depths.pop() else {
+            return;
+        };
+        // Also closes any groups (e.g. for clipping) opened within the layer
+        for _ in depth..self.state.open_groups {
+            self.body.push_str("</g>\n");
+        }
+        self.state.open_groups = depth;
+    }
+}
+
+impl piet::RenderContext for SvgRenderContext {
+    type Brush = Brush;
+    type Text = NullText;
+    type TextLayout = NullTextLayout;
+    type Image = SvgImage;
+
+    fn status(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn solid_brush(&mut self, color: Color) -> Self::Brush {
+        Brush::Solid(color)
+    }
+
+    fn gradient(&mut self, gradient: impl Into<FixedGradient>) -> Result<Self::Brush, Error> {
+        Ok(Brush::Gradient(gradient.into()))
+    }
+
+    fn clear(&mut self, region: impl Into<Option<Rect>>, color: Color) {
+        // As with other piet backends, clearing ignores the current transform and clip;
+        // a clear over the whole layer discards everything recorded so far.
+        let region = region.into();
+        if region.is_none() && self.state.open_groups == 0 && self.saved_states.is_empty() {
+            self.body.clear();
+        }
+        let (r, g, b, a) = color.as_rgba8();
+        if a == 0 {
+            return;
+        }
+        let (x, y, width, height) = match region {
+            Some(rect) => (
+                rect.x0.to_string(),
+                rect.y0.to_string(),
+                rect.width().to_string(),
+                rect.height().to_string(),
+            ),
+            None => ("0".into(), "0".into(), "100%".into(), "100%".into()),
+        };
+        let _ = writeln!(
+            self.body,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="rgb({},{},{})"{}/>"#,
+            x,
+            y,
+            width,
+            height,
+            r,
+            g,
+            b,
+            opacity_attr("fill-opacity", a as f64 / 255.0)
+        );
+    }
+
+    fn stroke(&mut self, shape: impl Shape, brush: &impl IntoBrush<Self>, width: f64) {
+        self.stroke_styled(shape, brush, width, &StrokeStyle::default());
+    }
+
+    fn stroke_styled(
+        &mut self,
+        shape: impl Shape,
+        brush: &impl IntoBrush<Self>,
+        width: f64,
+        style: &StrokeStyle,
+    ) {
+        let (paint, opacity) = self.paint_for(brush, shape.bounding_box());
+        let mut attrs = format!(r#" stroke-width="{}""#, width);
+        match style.line_cap {
+            LineCap::Butt => {}
+            LineCap::Round => attrs.push_str(r#" stroke-linecap="round""#),
+            LineCap::Square => attrs.push_str(r#" stroke-linecap="square""#),
+        }
+        match style.line_join {
+            LineJoin::Miter { limit } => {
+                let _ = write!(attrs, r#" stroke-miterlimit="{}""#, limit);
+            }
+            LineJoin::Round => attrs.push_str(r#" stroke-linejoin="round""#),
+            LineJoin::Bevel => attrs.push_str(r#" stroke-linejoin="bevel""#),
+        }
+        if !style.dash_pattern.is_empty() {
+            let dashes: Vec<String> = style.dash_pattern.iter().map(|d| d.to_string()).collect();
+            let _ = write!(
+                attrs,
+                r#" stroke-dasharray="{}" stroke-dashoffset="{}""#,
+                dashes.join(" "),
+                style.dash_offset
+            );
+        }
+        let _ = writeln!(
+            self.body,
+            r#"<path d="{}" fill="none" stroke="{}"{}{}{}/>"#,
+            svg_path(&shape),
+            paint,
+            opacity_attr("stroke-opacity", opacity),
+            attrs,
+            self.transform_attr()
+        );
+    }
+
+    fn fill(&mut self, shape: impl Shape, brush: &impl IntoBrush<Self>) {
+        self.fill_with_rule(shape, brush, "nonzero");
+    }
+
+    fn fill_even_odd(&mut self, shape: impl Shape, brush: &impl IntoBrush<Self>) {
+        self.fill_with_rule(shape, brush, "evenodd");
+    }
+
+    fn clip(&mut self, shape: impl Shape) {
+        let id = self.new_def_id("clip");
+        let _ = writeln!(
+            self.defs,
+            r#"<clipPath id="{}"><path d="{}"{}/></clipPath>"#,
+            id,
+            svg_path(&shape),
+            self.transform_attr()
+        );
+        let _ = writeln!(self.body, r#"<g clip-path="url(#{})">"#, id);
+        self.state.open_groups += 1;
+    }
+
+    fn text(&mut self) -> &mut Self::Text {
+        &mut self.text
+    }
+
+    fn draw_text(&mut self, _layout: &Self::TextLayout, _pos: impl Into<Point>) {}
+
+    fn save(&mut self) -> Result<(), Error> {
+        self.saved_states.push(self.state);
+        self.state.open_groups = 0;
+        Ok(())
+    }
+
+    fn restore(&mut self) -> Result<(), Error> {
+        let restored = self.saved_states.pop().ok_or(Error::StackUnbalance)?;
+        for _ in 0..self.state.open_groups {
+            self.body.push_str("</g>\n");
+        }
+        self.state = restored;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn transform(&mut self, transform: Affine) {
+        self.state.transform *= transform;
+    }
+
+    fn make_image(
+        &mut self,
+        width: usize,
+        height: usize,
+        buf: &[u8],
+        format: ImageFormat,
+    ) -> Result<Self::Image, Error> {
+        // Reuse the raster backend's pixel format handling, then embed as PNG
+        let raster = piet::RenderContext::make_image(
+            &mut RasterRenderContext::new(1, 1),
+            width,
+            height,
+            buf,
+            format,
+        )?;
+        let png = raster
+            .pixmap()
+            .encode_png()
+            .map_err(|e| Error::BackendError(Box::new(e)))?;
+        Ok(SvgImage {
+            size: Size::new(width as f64, height as f64),
+            data_uri: Rc::new(format!("data:image/png;base64,{}", base64(&png))),
+        })
+    }
+
+    fn draw_image(
+        &mut self,
+        image: &Self::Image,
+        dst_rect: impl Into<Rect>,
+        _interp: InterpolationMode,
+    ) {
+        let src_rect = Rect::from_origin_size(Point::ORIGIN, image.size);
+        self.write_image(image, src_rect, dst_rect.into());
+    }
+
+    fn draw_image_area(
+        &mut self,
+        image: &Self::Image,
+        src_rect: impl Into<Rect>,
+        dst_rect: impl Into<Rect>,
+        _interp: InterpolationMode,
+    ) {
+        self.write_image(image, src_rect.into(), dst_rect.into());
+    }
+
+    fn capture_image_area(&mut self, _src_rect: impl Into<Rect>) -> Result<Self::Image, Error> {
+        Err(Error::NotSupported)
+    }
+
+    fn blurred_rect(&mut self, rect: Rect, blur_radius: f64, brush: &impl IntoBrush<Self>) {
+        let id = self.blur_filter(rect, blur_radius);
+        let (paint, opacity) = self.paint_for(brush, rect);
+        let _ = writeln!(
+            self.body,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"{} filter="url(#{})"{}/>"#,
+            rect.x0,
+            rect.y0,
+            rect.width(),
+            rect.height(),
+            paint,
+            opacity_attr("fill-opacity", opacity),
+            id,
+            self.transform_attr()
+        );
+    }
+
+    fn current_transform(&self) -> Affine {
+        self.state.transform
+    }
+}
+
+fn svg_path(shape: &impl Shape) -> String {
+    BezPath::from_iter(shape.path_elements(SHAPE_TOLERANCE)).to_svg()
+}
+
+fn svg_transform(affine: Affine) -> String {
+    if affine == Affine::IDENTITY {
+        return String::new();
+    }
+    let [a, b, c, d, e, f] = affine.as_coeffs();
+    format!(
+        r#" transform="matrix({} {} {} {} {} {})""#,
+        a, b, c, d, e, f
+    )
+}
+
+fn opacity_attr(name: &str, opacity: f64) -> String {
+    if opacity >= 1.0 {
+        String::new()
+    } else {
+        format!(r#" {}="{}""#, name, opacity)
+    }
+}
+
+fn svg_stops(stops: &[GradientStop]) -> String {
+    stops
+        .iter()
+        .map(|stop| {
+            let (r, g, b, a) = stop.color.as_rgba8();
+            format!(
+                r#"<stop offset="{}" stop-color="rgb({},{},{})"{}/>"#,
+                stop.pos,
+                r,
+                g,
+                b,
+                opacity_attr("stop-opacity", a as f64 / 255.0)
+            )
+        })
+        .collect()
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut ret = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = (chunk[0] as u32) << 16
+            | (*chunk.get(1).unwrap_or(&0) as u32) << 8
+            | *chunk.get(2).unwrap_or(&0) as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                ret.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                ret.push('=');
+            }
+        }
+    }
+    ret
+}
@@ -0,0 +1,247 @@
+mod common;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use common::{engine, engine_with_template, Log, ProbeSpec};
+use pax_headless::TestHarness;
+use pax_message::{MeasureArgs, NativeInterrupt, NativeMessage};
+use pax_runtime::api::{CommonProperties, PropertyInstance, PropertyLiteral, Size};
+use pax_runtime::{
+    ComponentInstance, ConditionalInstance, ConditionalProperties, Handler, HandlerRegistry,
+    InstanceNode, InstanceNodePtr, InstantiationArgs,
+};
+use pax_std::primitives::{Flex, Group, Text};
+use pax_std::stacker::Stacker;
+use pax_std_primitives::flex::FlexInstance;
+use pax_std_primitives::group::GroupInstance;
+use pax_std_primitives::text::TextInstance;
+
+/// Common properties for an element with the given `id`, sized to its content
+fn auto_sized(id: &'static str) -> Rc<RefCell<CommonProperties>> {
+    Rc::new(RefCell::new(CommonProperties {
+        id: Some(Box::new(PropertyLiteral::new(id.to_string()))),
+        width: Box::new(PropertyLiteral::new(Size::Auto)),
+        height: Box::new(PropertyLiteral::new(Size::Auto)),
+        ..Default::default()
+    }))
+}
+
+fn instantiate<T: InstanceNode + 'static, P: 'static>(
+    id: &'static str,
+    properties: impl Fn() -> P + 'static,
+    children: Vec<InstanceNodePtr>,
+) -> InstanceNodePtr {
+    T::instantiate(InstantiationArgs {
+        prototypical_common_properties_factory: Box::new(move || auto_sized(id)),
+        prototypical_properties_factory: Box::new(move || Rc::new(RefCell::new(properties()))),
+        handler_registry: None,
+        children: Some(RefCell::new(children)),
+        component_template: None,
+        compute_properties_fn: None,
+        timeline: None,
+        template_node_identifier: None,
+    })
+}
+
+fn measure(harness: &mut TestHarness, id: &str, width: f64, height: f64) {
+    let id_chain = harness.get_node_by_id(id).id_chain.clone();
+    harness.interrupt(NativeInterrupt::Measure(MeasureArgs {
+        id_chain,
+        width,
+        height,
+    }));
+    harness.tick();
+}
+
+#[test]
+fn auto_sized_nodes_fill_their_container_until_measured() {
+    let log = Log::default();
+    let mut harness = TestHarness::new(engine(
+        vec![
+            ProbeSpec::new("label", 0.0, 0.0, 0.0, 0.0).common_properties(|common| {
+                common.width = Box::new(PropertyLiteral::new(Size::Auto));
+                common.height = Box::new(PropertyLiteral::new(Size::Auto));
+            }),
+        ],
+        &log,
+    ));
+    assert_eq!(harness.computed_tab("label").bounds, (400.0, 300.0));
+
+    measure(&mut harness, "label", 120.0, 16.0);
+    assert_eq!(harness.computed_tab("label").bounds, (120.0, 16.0));
+    measure(&mut harness, "label", 80.0, 32.0);
+    assert_eq!(harness.computed_tab("label").bounds, (80.0, 32.0));
+}
+
+#[test]
+fn text_is_patched_with_its_measured_size() {
+    let mut harness = TestHarness::new(engine_with_template(
+        vec![instantiate::<TextInstance, _>(
+            "text",
+            Text::default,
+            vec![],
+        )],
+        None,
+    ));
+    harness.take_messages();
+
+    measure(&mut harness, "text", 120.0, 16.0);
+    let sizes: Vec<_> = harness
+        .take_messages()
+        .into_iter()
+        .filter_map(|message| match message {
+            NativeMessage::TextUpdate(patch) => Some((patch.size_x, patch.size_y)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(sizes, [(Some(120.0), Some(16.0))]);
+}
+
+#[test]
+fn groups_reach_as_far_as_their_children() {
+    let log = Log::default();
+    let children = vec![
+        ProbeSpec::new("a", 10.0, 20.0, 50.0, 30.0).instantiate(&log),
+        ProbeSpec::new("b", 0.0, 0.0, 100.0, 10.0).instantiate(&log),
+    ];
+    let harness = TestHarness::new(engine_with_template(
+        vec![instantiate::<GroupInstance, _>(
+            "group",
+            Group::default,
+            children,
+        )],
+        None,
+    ));
+
+    assert_eq!(harness.computed_tab("group").bounds, (100.0, 50.0));
+}
+
+#[test]
+fn groups_resize_as_their_if_children_come_and_go() {
+    let log = Log::default();
+    let conditional = ConditionalInstance::instantiate(InstantiationArgs {
+        prototypical_common_properties_factory: Box::new(|| {
+            Rc::new(RefCell::new(CommonProperties {
+                id: Some(Box::new(PropertyLiteral::new("if".to_string()))),
+                ..Default::default()
+            }))
+        }),
+        prototypical_properties_factory: Box::new(|| {
+            let mut properties = ConditionalProperties::default();
+            properties.boolean_expression = Box::new(PropertyLiteral::new(true));
+            Rc::new(RefCell::new(properties))
+        }),
+        handler_registry: None,
+        children: Some(RefCell::new(vec![ProbeSpec::new(
+            "b", 0.0, 0.0, 100.0, 50.0,
+        )
+        .instantiate(&log)])),
+        component_template: None,
+        compute_properties_fn: None,
+        timeline: None,
+        template_node_identifier: None,
+    });
+    let children = vec![
+        ProbeSpec::new("a", 0.0, 0.0, 20.0, 10.0).instantiate(&log),
+        conditional,
+    ];
+    let mut harness = TestHarness::new(engine_with_template(
+        vec![instantiate::<GroupInstance, _>(
+            "group",
+            Group::default,
+            children,
+        )],
+        None,
+    ));
+    let show = |harness: &mut TestHarness, shown: bool| {
+        harness.with_properties("if", |properties: &mut ConditionalProperties| {
+            properties.boolean_expression.set(shown)
+        });
+        harness.tick();
+    };
+    assert_eq!(harness.computed_tab("group").bounds, (100.0, 50.0));
+
+    show(&mut harness, false);
+    assert_eq!(harness.computed_tab("group").bounds, (20.0, 10.0));
+    show(&mut harness, true);
+    assert_eq!(harness.computed_tab("group").bounds, (100.0, 50.0));
+}
+
+#[test]
+fn flex_fits_its_items_on_one_line() {
+    let log = Log::default();
+    let children = vec![
+        ProbeSpec::new("a", 0.0, 0.0, 50.0, 20.0).instantiate(&log),
+        ProbeSpec::new("b", 0.0, 0.0, 30.0, 40.0).instantiate(&log),
+    ];
+    let harness = TestHarness::new(engine_with_template(
+        vec![instantiate::<FlexInstance, _>(
+            "flex",
+            || Flex {
+                gap: Box::new(PropertyLiteral::new(Size::Pixels(10.into()))),
+                ..Default::default()
+            },
+            children,
+        )],
+        None,
+    ));
+
+    assert_eq!(harness.computed_tab("flex").bounds, (90.0, 40.0));
+    assert_eq!(harness.computed_tab("b").bounds, (30.0, 40.0));
+}
+
+#[test]
+fn stacker_fits_auto_cells_to_their_children() {
+    let log = Log::default();
+    let children = vec![
+        ProbeSpec::new("a", 0.0, 0.0, 50.0, 20.0).instantiate(&log),
+        ProbeSpec::new("b", 0.0, 0.0, 30.0, 40.0).instantiate(&log),
+    ];
+    let mut handlers = HashMap::new();
+    handlers.insert(
+        "tick".to_string(),
+        vec![Handler::new_component_handler(|properties, ctx, _| {
+            let mut properties = properties.borrow_mut();
+            properties
+                .downcast_mut::<Stacker>()
+                .unwrap()
+                .handle_tick(ctx);
+        })],
+    );
+    let stacker = ComponentInstance::instantiate(InstantiationArgs {
+        prototypical_common_properties_factory: Box::new(|| auto_sized("stacker")),
+        prototypical_properties_factory: Box::new(|| {
+            Rc::new(RefCell::new(Stacker {
+                cells: Box::new(PropertyLiteral::new(2.into())),
+                gutter: Box::new(PropertyLiteral::new(Size::Pixels(10.into()))),
+                sizes: Box::new(PropertyLiteral::new(vec![Some(Size::Auto); 2])),
+                ..Default::default()
+            }))
+        }),
+        handler_registry: Some(Rc::new(RefCell::new(HandlerRegistry { handlers }))),
+        children: Some(RefCell::new(children)),
+        component_template: Some(RefCell::new(vec![])),
+        compute_properties_fn: Some(Box::new(|_, _, _| {})),
+        timeline: None,
+        template_node_identifier: None,
+    });
+    let mut harness = TestHarness::new(engine_with_template(vec![stacker], None));
+    harness.tick();
+    harness.tick();
+
+    assert_eq!(harness.computed_tab("stacker").bounds, (90.0, 40.0));
+    let mut cells = Vec::new();
+    harness
+        .get_node_by_id("stacker")
+        .with_properties_unwrapped(|stacker: &mut Stacker| {
+            cells = stacker
+                ._cell_specs
+                .get()
+                .iter()
+                .map(|cell| (cell.x_px, cell.width_px))
+                .collect();
+        });
+    assert_eq!(cells, [(0.0, 50.0), (60.0, 30.0)]);
+}
@@ -0,0 +1,277 @@
+//! A minimal instance tree for driving `PaxEngine` in tests without a compiled cartridge.
+//! Tests register the handlers and set the properties for the features they exercise
+//! themselves, through `ProbeSpec::on` and `ProbeSpec::common_properties`.
+// each test crate uses only some of these helpers
+#![allow(dead_code)]
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use kurbo::{Affine, Rect, Shape};
+use pax_runtime::api::{
+    Click, CommonProperties, Event, Layer, NodeContext, PropertyLiteral, RenderContext, Size,
+    Timeline,
+};
+use pax_runtime::{
+    BaseInstance, ComponentInstance, ExpandedNode, ExpressionContext, ExpressionTable, Handler,
+    HandlerRegistry, InstanceFlags, InstanceNode, InstanceNodePtr, InstantiationArgs, PaxEngine,
+    RuntimeContext,
+};
+use piet::Color;
+
+/// Shared record of the events received by `Probe`s, e.g. `"click a (70, 35)"`
+pub type Log = Rc<RefCell<Vec<String>>>;
+
+/// The entries in `log` for `event`, e.g. `"pan"`
+pub fn entries(log: &Log, event: &str) -> Vec<String> {
+    log.borrow()
+        .iter()
+        .filter(|entry| entry.starts_with(&format!("{} ", event)))
+        .cloned()
+        .collect()
+}
+
+/// Properties of a `Probe`: its name and where to record received events
+pub struct ProbeProperties {
+    pub name: String,
+    log: Log,
+}
+
+/// Minimal raycastable primitive, standing in for e.g. a `Rectangle`, that records
+/// the events it receives
+pub struct Probe {
+    base: BaseInstance,
+}
+
+impl InstanceNode for Probe {
+    fn base(&self) -> &BaseInstance {
+        &self.base
+    }
+
+    fn instantiate(args: InstantiationArgs) -> Rc<Self> {
+        // Like `Group`, probes with children are containers, hit only through their children
+        let is_container = args
+            .children
+            .as_ref()
+            .is_some_and(|children| !children.borrow().is_empty());
+        Rc::new(Probe {
+            base: BaseInstance::new(
+                args,
+                InstanceFlags {
+                    invisible_to_slot: false,
+                    invisible_to_raycasting: is_container,
+                    layer: Layer::Canvas,
+                    is_component: false,
+                },
+            ),
+        })
+    }
+
+    /// Leaf probes fill their bounds with opaque black
+    fn render(
+        &self,
+        expanded_node: &ExpandedNode,
+        _context: &mut RuntimeContext,
+        rcs: &mut dyn RenderContext,
+    ) {
+        if self.base.flags().invisible_to_raycasting {
+            return;
+        }
+        let layout_properties = expanded_node.layout_properties.borrow();
+        let computed_tab = &layout_properties.as_ref().unwrap().computed_tab;
+        let (width, height) = computed_tab.bounds;
+        let path = Rect::new(0.0, 0.0, width, height).to_path(0.1);
+        let layer = expanded_node.occlusion_id.borrow().to_string();
+        let transformed_path = Affine::from(computed_tab.transform) * path;
+        rcs.fill(&layer, transformed_path, &Color::BLACK.into());
+    }
+
+    #[cfg(debug_assertions)]
+    fn resolve_debug(
+        &self,
+        f: &mut std::fmt::Formatter,
+        _expanded_node: Option<&ExpandedNode>,
+    ) -> std::fmt::Result {
+        f.debug_struct("Probe").finish()
+    }
+}
+
+pub type HandlerFn = fn(Rc<RefCell<dyn Any>>, &NodeContext, Option<Box<dyn Any>>);
+
+/// Appends `entry`, given the probe's name, to the log of the probe with `properties`
+pub fn record(properties: Rc<RefCell<dyn Any>>, entry: impl FnOnce(&str) -> String) {
+    let properties = properties.borrow();
+    let probe = properties.downcast_ref::<ProbeProperties>().unwrap();
+    probe.log.borrow_mut().push(entry(&probe.name));
+}
+
+/// Records e.g. `"click a (70, 35)"`
+pub fn on_click(properties: Rc<RefCell<dyn Any>>, _ctx: &NodeContext, args: Option<Box<dyn Any>>) {
+    let event = args.unwrap().downcast::<Event<Click>>().unwrap();
+    record(properties, |name| {
+        format!("click {} ({}, {})", name, event.mouse.x, event.mouse.y)
+    });
+}
+
+/// Records e.g. `"click_capture a -> b"`, where `b` is the click's target
+pub fn on_click_capture(
+    properties: Rc<RefCell<dyn Any>>,
+    ctx: &NodeContext,
+    _args: Option<Box<dyn Any>>,
+) {
+    let mut target = String::new();
+    ctx.target()
+        .unwrap()
+        .with_properties(|probe: &mut ProbeProperties| target = probe.name.clone());
+    record(properties, |name| {
+        format!("click_capture {} -> {}", name, target)
+    });
+}
+
+/// Describes a `Probe` to instantiate
+pub struct ProbeSpec {
+    pub name: &'static str,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    handlers: Vec<(String, HandlerFn)>,
+    common_properties: Vec<Box<dyn Fn(&mut CommonProperties)>>,
+    pub children: Vec<ProbeSpec>,
+}
+
+impl ProbeSpec {
+    pub fn new(name: &'static str, x: f64, y: f64, width: f64, height: f64) -> Self {
+        ProbeSpec {
+            name,
+            x,
+            y,
+            width,
+            height,
+            handlers: vec![],
+            common_properties: vec![],
+            children: vec![],
+        }
+    }
+
+    /// Registers `handler` under `key`, e.g. `CLICK_HANDLERS`, in place of any registered before.
+    /// Probes have no handlers but those registered this way.
+    pub fn on(mut self, key: impl Into<String>, handler: HandlerFn) -> Self {
+        self.handlers.push((key.into(), handler));
+        self
+    }
+
+    /// Sets further common properties, after `id`, `x`, `y`, `width` and `height`
+    pub fn common_properties(mut self, set: impl Fn(&mut CommonProperties) + 'static) -> Self {
+        self.common_properties.push(Box::new(set));
+        self
+    }
+
+    pub fn children(mut self, children: Vec<ProbeSpec>) -> Self {
+        self.children = children;
+        self
+    }
+
+    pub fn instantiate(self, log: &Log) -> InstanceNodePtr {
+        let mut handlers = HashMap::new();
+        for (key, function) in self.handlers {
+            handlers.insert(key, vec![Handler::new_component_handler(function)]);
+        }
+        let children = self
+            .children
+            .into_iter()
+            .map(|child| child.instantiate(log))
+            .collect();
+        let ProbeSpec {
+            name,
+            x,
+            y,
+            width,
+            height,
+            common_properties,
+            ..
+        } = self;
+        let pixels = |pixels: f64| Box::new(PropertyLiteral::new(Size::Pixels(pixels.into()))) as _;
+        let log = Rc::clone(log);
+        Probe::instantiate(InstantiationArgs {
+            prototypical_common_properties_factory: Box::new(move || {
+                let mut properties = CommonProperties {
+                    id: Some(Box::new(PropertyLiteral::new(name.to_string()))),
+                    x: Some(pixels(x)),
+                    y: Some(pixels(y)),
+                    width: pixels(width),
+                    height: pixels(height),
+                    ..Default::default()
+                };
+                for set in &common_properties {
+                    set(&mut properties);
+                }
+                Rc::new(RefCell::new(properties))
+            }),
+            prototypical_properties_factory: Box::new(move || {
+                Rc::new(RefCell::new(ProbeProperties {
+                    name: name.to_string(),
+                    log: Rc::clone(&log),
+                }))
+            }),
+            handler_registry: Some(Rc::new(RefCell::new(HandlerRegistry { handlers }))),
+            children: Some(RefCell::new(children)),
+            component_template: None,
+            compute_properties_fn: None,
+            timeline: None,
+            template_node_identifier: None,
+        })
+    }
+}
+
+/// A 400x300 engine whose root component contains the given probes
+pub fn engine(probes: Vec<ProbeSpec>, log: &Log) -> PaxEngine {
+    engine_with_timeline(probes, log, None)
+}
+
+/// Like `engine`, with the root component playing its probes' keyframes on `timeline`
+pub fn engine_with_timeline(
+    probes: Vec<ProbeSpec>,
+    log: &Log,
+    timeline: Option<Timeline>,
+) -> PaxEngine {
+    let template = probes
+        .into_iter()
+        .map(|probe| probe.instantiate(log))
+        .collect();
+    engine_with_template(template, timeline)
+}
+
+/// Builds an engine whose root component renders an already-instantiated `template`
+pub fn engine_with_template(
+    template: Vec<InstanceNodePtr>,
+    timeline: Option<Timeline>,
+) -> PaxEngine {
+    engine_with_expressions(template, timeline, HashMap::new())
+}
+
+/// Expressions by vtable id, as they'd be compiled into a cartridge
+pub type Expressions = HashMap<usize, Box<dyn Fn(ExpressionContext) -> Box<dyn Any>>>;
+
+/// Like `engine_with_template`, evaluating the vtable ids in `template` with `expressions`
+pub fn engine_with_expressions(
+    template: Vec<InstanceNodePtr>,
+    timeline: Option<Timeline>,
+    expressions: Expressions,
+) -> PaxEngine {
+    let root = ComponentInstance::instantiate(InstantiationArgs {
+        prototypical_common_properties_factory: Box::new(|| {
+            Rc::new(RefCell::new(CommonProperties::default()))
+        }),
+        prototypical_properties_factory: Box::new(|| Rc::new(RefCell::new(()))),
+        handler_registry: None,
+        children: None,
+        component_template: Some(RefCell::new(template)),
+        compute_properties_fn: Some(Box::new(|_, _, _| {})),
+        timeline,
+        template_node_identifier: None,
+    });
+    PaxEngine::new(root, ExpressionTable { table: expressions }, (400.0, 300.0))
+}
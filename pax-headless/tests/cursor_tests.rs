@@ -0,0 +1,72 @@
+mod common;
+
+use common::{engine, Log, ProbeSpec};
+use pax_headless::TestHarness;
+use pax_message::{CursorMessage, NativeMessage};
+use pax_runtime::api::{Cursor, PropertyLiteral};
+
+trait WithCursor {
+    fn cursor(self, cursor: Cursor) -> Self;
+}
+
+impl WithCursor for ProbeSpec {
+    fn cursor(self, cursor: Cursor) -> Self {
+        self.common_properties(move |common| {
+            common.cursor = Some(Box::new(PropertyLiteral::new(cursor)))
+        })
+    }
+}
+
+fn cursor_updates(harness: &mut TestHarness) -> Vec<CursorMessage> {
+    harness.tick();
+    harness
+        .take_messages()
+        .into_iter()
+        .filter_map(|message| match message {
+            NativeMessage::CursorUpdate(cursor) => Some(cursor),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn cursor_follows_the_innermost_hovered_node_that_sets_one() {
+    let log = Log::default();
+    let mut harness = TestHarness::new(engine(
+        vec![ProbeSpec::new("panel", 0.0, 0.0, 100.0, 100.0)
+            .cursor(Cursor::Grab)
+            .children(vec![
+                ProbeSpec::new("link", 0.0, 0.0, 20.0, 20.0).cursor(Cursor::Pointer),
+                ProbeSpec::new("body", 0.0, 50.0, 100.0, 50.0),
+            ])],
+        &log,
+    ));
+    assert_eq!(harness.cursor(), Cursor::Default);
+
+    harness.mouse_move(10.0, 10.0);
+    assert_eq!(harness.cursor(), Cursor::Pointer);
+
+    // nodes without a cursor of their own show their ancestor's
+    harness.mouse_move(10.0, 60.0);
+    assert_eq!(harness.cursor(), Cursor::Grab);
+
+    harness.mouse_move(200.0, 200.0);
+    assert_eq!(harness.cursor(), Cursor::Default);
+}
+
+#[test]
+fn chassis_is_only_told_about_changes() {
+    let log = Log::default();
+    let mut harness = TestHarness::new(engine(
+        vec![ProbeSpec::new("handle", 0.0, 0.0, 20.0, 20.0).cursor(Cursor::EwResize)],
+        &log,
+    ));
+    harness.take_messages();
+
+    harness.mouse_move(5.0, 5.0);
+    harness.mouse_move(10.0, 10.0);
+    assert_eq!(cursor_updates(&mut harness), [CursorMessage::EwResize]);
+
+    harness.mouse_move(50.0, 50.0);
+    assert_eq!(cursor_updates(&mut harness), [CursorMessage::Default]);
+}
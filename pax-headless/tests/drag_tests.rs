@@ -0,0 +1,157 @@
+mod common;
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use common::{engine, entries, record, Log, ProbeSpec};
+use pax_headless::TestHarness;
+use pax_message::{
+    MouseButtonMessage, MouseDownInterruptArgs, MouseUpInterruptArgs, NativeInterrupt,
+};
+use pax_runtime::api::{Drag, DragEnd, DragStart, Event, MouseUp, NodeContext};
+use pax_runtime::constants::{
+    DRAG_END_HANDLERS, DRAG_HANDLERS, DRAG_START_HANDLERS, MOUSE_UP_HANDLERS,
+};
+
+fn on_drag_start(properties: Rc<RefCell<dyn Any>>, _ctx: &NodeContext, args: Option<Box<dyn Any>>) {
+    let event = args.unwrap().downcast::<Event<DragStart>>().unwrap();
+    record(properties, |name| {
+        format!(
+            "drag_start {} ({}, {})",
+            name, event.drag.local_x, event.drag.local_y
+        )
+    });
+}
+
+fn on_drag(properties: Rc<RefCell<dyn Any>>, _ctx: &NodeContext, args: Option<Box<dyn Any>>) {
+    let event = args.unwrap().downcast::<Event<Drag>>().unwrap();
+    record(properties, |name| {
+        format!(
+            "drag {} +({}, {})",
+            name, event.drag.delta_x, event.drag.delta_y
+        )
+    });
+}
+
+fn on_drag_end(properties: Rc<RefCell<dyn Any>>, _ctx: &NodeContext, args: Option<Box<dyn Any>>) {
+    let event = args.unwrap().downcast::<Event<DragEnd>>().unwrap();
+    record(properties, |name| {
+        format!(
+            "drag_end {} total ({}, {})",
+            name, event.drag.total_delta_x, event.drag.total_delta_y
+        )
+    });
+}
+
+fn on_mouse_up(properties: Rc<RefCell<dyn Any>>, _ctx: &NodeContext, args: Option<Box<dyn Any>>) {
+    let event = args.unwrap().downcast::<Event<MouseUp>>().unwrap();
+    record(properties, |name| {
+        format!("mouse_up {} ({}, {})", name, event.mouse.x, event.mouse.y)
+    });
+}
+
+/// A probe that records the drags it receives
+fn draggable(name: &'static str, x: f64, y: f64) -> ProbeSpec {
+    ProbeSpec::new(name, x, y, 40.0, 40.0)
+        .on(DRAG_START_HANDLERS, on_drag_start)
+        .on(DRAG_HANDLERS, on_drag)
+        .on(DRAG_END_HANDLERS, on_drag_end)
+}
+
+#[test]
+fn drag_stays_with_pressed_node_outside_its_bounds() {
+    let log = Log::default();
+    let mut harness = TestHarness::new(engine(
+        vec![
+            draggable("handle", 20.0, 20.0),
+            draggable("other", 100.0, 20.0),
+        ],
+        &log,
+    ));
+    // ends over "other", which must not receive any drag events
+    harness.drag((30.0, 30.0), (130.0, 50.0), 2);
+    let drag_events: Vec<String> = ["drag_start", "drag", "drag_end"]
+        .iter()
+        .flat_map(|event| entries(&log, event))
+        .collect();
+    assert_eq!(
+        drag_events,
+        vec![
+            "drag_start handle (60, 20)",
+            "drag handle +(50, 10)",
+            "drag handle +(50, 10)",
+            "drag_end handle total (100, 20)",
+        ]
+    );
+}
+
+#[test]
+fn small_movements_do_not_start_a_drag() {
+    let log = Log::default();
+    let mut harness = TestHarness::new(engine(vec![draggable("handle", 0.0, 0.0)], &log));
+    harness.drag((10.0, 10.0), (11.0, 12.0), 3);
+    assert!(entries(&log, "drag_start").is_empty());
+    assert!(entries(&log, "drag").is_empty());
+    assert!(entries(&log, "drag_end").is_empty());
+}
+
+#[test]
+fn touches_do_not_interrupt_a_mouse_drag() {
+    let log = Log::default();
+    let mut harness = TestHarness::new(engine(
+        vec![
+            draggable("handle", 20.0, 20.0),
+            draggable("other", 100.0, 20.0),
+        ],
+        &log,
+    ));
+    harness.interrupt(NativeInterrupt::MouseDown(MouseDownInterruptArgs {
+        x: 30.0,
+        y: 30.0,
+        button: MouseButtonMessage::Left,
+        modifiers: vec![],
+    }));
+    harness.mouse_move(40.0, 30.0);
+    harness.touch_start(&[(1, 120.0, 40.0)]);
+    harness.touch_end(&[1]);
+    harness.mouse_move(50.0, 30.0);
+    harness.interrupt(NativeInterrupt::MouseUp(MouseUpInterruptArgs {
+        x: 50.0,
+        y: 30.0,
+        button: MouseButtonMessage::Left,
+        modifiers: vec![],
+    }));
+    let drag_events: Vec<String> = ["drag_start", "drag", "drag_end"]
+        .iter()
+        .flat_map(|event| entries(&log, event))
+        .collect();
+    assert_eq!(
+        drag_events,
+        vec![
+            "drag_start handle (20, 10)",
+            "drag handle +(10, 0)",
+            "drag handle +(10, 0)",
+            "drag_end handle total (20, 0)",
+        ]
+    );
+}
+
+#[test]
+fn mouse_up_goes_to_the_node_beneath_the_pointer() {
+    let log = Log::default();
+    let mut harness = TestHarness::new(engine(
+        vec![
+            draggable("handle", 20.0, 20.0).on(MOUSE_UP_HANDLERS, on_mouse_up),
+            draggable("other", 100.0, 20.0).on(MOUSE_UP_HANDLERS, on_mouse_up),
+        ],
+        &log,
+    ));
+    // e.g. dropping the handle onto a drop target
+    harness.drag((30.0, 30.0), (130.0, 50.0), 2);
+    assert_eq!(entries(&log, "mouse_up"), vec!["mouse_up other (130, 50)"]);
+    assert_eq!(
+        entries(&log, "drag_end"),
+        vec!["drag_end handle total (100, 20)"]
+    );
+}
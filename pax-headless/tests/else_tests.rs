@@ -0,0 +1,132 @@
+mod common;
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use common::{engine_with_expressions, Expressions, Log, ProbeSpec};
+use pax_headless::TestHarness;
+use pax_runtime::api::expressions::PropertyExpression;
+use pax_runtime::api::{CommonProperties, PropertyInstance, PropertyLiteral};
+use pax_runtime::{
+    ConditionalInstance, ConditionalProperties, InstanceNode, InstanceNodePtr, InstantiationArgs,
+};
+
+const IS_ZERO: usize = 0;
+const IS_ONE: usize = 1;
+
+/// A conditional on `vtable_id`, or a plain `else` without one
+fn conditional(
+    vtable_id: Option<usize>,
+    has_else: bool,
+    children: Vec<InstanceNodePtr>,
+) -> InstanceNodePtr {
+    ConditionalInstance::instantiate(InstantiationArgs {
+        prototypical_common_properties_factory: Box::new(|| {
+            Rc::new(RefCell::new(CommonProperties::default()))
+        }),
+        prototypical_properties_factory: Box::new(move || {
+            let mut properties = ConditionalProperties::default();
+            properties.boolean_expression = match vtable_id {
+                Some(vtable_id) => Box::new(PropertyExpression::new(vtable_id)),
+                None => Box::new(PropertyLiteral::new(true)),
+            };
+            properties.has_else = has_else;
+            Rc::new(RefCell::new(properties))
+        }),
+        handler_registry: None,
+        children: Some(RefCell::new(children)),
+        component_template: None,
+        compute_properties_fn: None,
+        timeline: None,
+        template_node_identifier: None,
+    })
+}
+
+struct Chain {
+    harness: TestHarness,
+    count: Rc<RefCell<PropertyLiteral<u32>>>,
+    /// How many times the `else if`'s condition has been evaluated
+    else_if_evaluations: Rc<Cell<usize>>,
+}
+
+impl Chain {
+    /// `if count == 0 { zero } else if count == 1 { one } else { many }`
+    fn new(log: &Log) -> Self {
+        let count = Rc::new(RefCell::new(PropertyLiteral::new(0)));
+        let else_if_evaluations = Rc::new(Cell::new(0));
+        let mut expressions = Expressions::new();
+        let is_zero = Rc::clone(&count);
+        expressions.insert(
+            IS_ZERO,
+            Box::new(move |_| Box::new(*is_zero.borrow().get() == 0)),
+        );
+        let is_one = Rc::clone(&count);
+        let evaluations = Rc::clone(&else_if_evaluations);
+        expressions.insert(
+            IS_ONE,
+            Box::new(move |_| {
+                evaluations.set(evaluations.get() + 1);
+                Box::new(*is_one.borrow().get() == 1)
+            }),
+        );
+        let probe = |id| ProbeSpec::new(id, 0.0, 0.0, 10.0, 10.0).instantiate(log);
+        let template = conditional(
+            Some(IS_ZERO),
+            true,
+            vec![
+                probe("zero"),
+                conditional(
+                    Some(IS_ONE),
+                    true,
+                    vec![probe("one"), conditional(None, false, vec![probe("many")])],
+                ),
+            ],
+        );
+        Chain {
+            harness: TestHarness::new(engine_with_expressions(vec![template], None, expressions)),
+            count,
+            else_if_evaluations,
+        }
+    }
+
+    fn set_count(&mut self, count: u32) {
+        self.count.borrow_mut().set(count);
+        self.harness.tick();
+    }
+
+    /// The ids of the probes that are mounted
+    fn shown(&self) -> Vec<&'static str> {
+        ["zero", "one", "many"]
+            .into_iter()
+            .filter(|id| !self.harness.get_nodes_by_id(id).is_empty())
+            .collect()
+    }
+}
+
+#[test]
+fn exactly_one_branch_is_shown() {
+    let log = Log::default();
+    let mut chain = Chain::new(&log);
+    assert_eq!(chain.shown(), ["zero"]);
+
+    chain.set_count(1);
+    assert_eq!(chain.shown(), ["one"]);
+
+    chain.set_count(5);
+    assert_eq!(chain.shown(), ["many"]);
+
+    chain.set_count(0);
+    assert_eq!(chain.shown(), ["zero"]);
+}
+
+#[test]
+fn else_if_conditions_are_only_evaluated_while_the_if_is_false() {
+    let log = Log::default();
+    let mut chain = Chain::new(&log);
+    chain.set_count(0);
+    chain.harness.tick();
+    assert_eq!(chain.else_if_evaluations.get(), 0);
+
+    chain.set_count(1);
+    assert_eq!(chain.else_if_evaluations.get(), 1);
+}
@@ -0,0 +1,246 @@
+mod common;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use common::{engine_with_template, Log, ProbeSpec};
+use pax_headless::TestHarness;
+use pax_runtime::api::{CommonProperties, PropertyLiteral, Size};
+use pax_runtime::{
+    InstanceNode, InstanceNodePtr, InstantiationArgs, RepeatInstance, RepeatProperties,
+    TransformAndBounds,
+};
+use pax_std::primitives::Flex;
+use pax_std::types::{AlignItems, FlexDirection, FlexItem, JustifyContent};
+use pax_std_primitives::flex::FlexInstance;
+
+fn px(value: f64) -> Size {
+    Size::Pixels(value.into())
+}
+
+/// A 300x100 `Flex` at the origin, with the properties `flex` returns, laying out `children`
+fn flex(flex: impl Fn() -> Flex + 'static, children: Vec<InstanceNodePtr>) -> InstanceNodePtr {
+    FlexInstance::instantiate(InstantiationArgs {
+        prototypical_common_properties_factory: Box::new(|| {
+            Rc::new(RefCell::new(CommonProperties {
+                width: Box::new(PropertyLiteral::new(px(300.0))),
+                height: Box::new(PropertyLiteral::new(px(100.0))),
+                ..Default::default()
+            }))
+        }),
+        prototypical_properties_factory: Box::new(move || Rc::new(RefCell::new(flex()))),
+        handler_registry: None,
+        children: Some(RefCell::new(children)),
+        component_template: None,
+        compute_properties_fn: None,
+        timeline: None,
+        template_node_identifier: None,
+    })
+}
+
+fn probes(specs: Vec<ProbeSpec>, log: &Log) -> Vec<InstanceNodePtr> {
+    specs
+        .into_iter()
+        .map(|spec| spec.instantiate(log))
+        .collect()
+}
+
+/// `tab`'s position and size, rounded to hundredths of a pixel
+fn rect(tab: &TransformAndBounds) -> (f64, f64, f64, f64) {
+    let round = |value: f64| (value * 100.0).round() / 100.0;
+    let [.., x, y] = tab.transform.coeffs();
+    (round(x), round(y), round(tab.bounds.0), round(tab.bounds.1))
+}
+
+#[test]
+fn items_grow_into_free_space() {
+    let log = Log::default();
+    let children = probes(
+        vec![
+            ProbeSpec::new("a", 0.0, 0.0, 10.0, 10.0),
+            ProbeSpec::new("b", 0.0, 0.0, 10.0, 10.0),
+            ProbeSpec::new("c", 0.0, 0.0, 100.0, 10.0),
+        ],
+        &log,
+    );
+    let harness = TestHarness::new(engine_with_template(
+        vec![flex(
+            || Flex {
+                gap: Box::new(PropertyLiteral::new(px(10.0))),
+                items: Box::new(PropertyLiteral::new(vec![
+                    FlexItem::new(1.into(), 1.into(), px(50.0)),
+                    FlexItem::new(2.into(), 1.into(), px(50.0)),
+                ])),
+                ..Default::default()
+            },
+            children,
+        )],
+        None,
+    ));
+
+    // 80px are left over, split 1:2 between `a` and `b`; items stretch across the line
+    assert_eq!(rect(&harness.computed_tab("a")), (0.0, 0.0, 76.67, 100.0));
+    assert_eq!(
+        rect(&harness.computed_tab("b")),
+        (86.67, 0.0, 103.33, 100.0)
+    );
+    assert_eq!(rect(&harness.computed_tab("c")), (200.0, 0.0, 100.0, 100.0));
+}
+
+#[test]
+fn overflowing_items_shrink_in_proportion_to_their_basis() {
+    let log = Log::default();
+    let children = probes(
+        vec![
+            ProbeSpec::new("a", 0.0, 0.0, 100.0, 10.0),
+            ProbeSpec::new("b", 0.0, 0.0, 300.0, 10.0),
+            ProbeSpec::new("c", 0.0, 0.0, 100.0, 10.0),
+        ],
+        &log,
+    );
+    let harness = TestHarness::new(engine_with_template(
+        vec![flex(
+            || Flex {
+                items: Box::new(PropertyLiteral::new(vec![
+                    FlexItem::default(),
+                    FlexItem::default(),
+                    FlexItem::auto(0.into(), 0.into()),
+                ])),
+                ..Default::default()
+            },
+            children,
+        )],
+        None,
+    ));
+
+    // `c` doesn't shrink; `a` and `b` give up the 200px overflow 1:3
+    assert_eq!(rect(&harness.computed_tab("a")), (0.0, 0.0, 50.0, 100.0));
+    assert_eq!(rect(&harness.computed_tab("b")), (50.0, 0.0, 150.0, 100.0));
+    assert_eq!(rect(&harness.computed_tab("c")), (200.0, 0.0, 100.0, 100.0));
+}
+
+#[test]
+fn columns_justify_along_and_align_across_the_main_axis() {
+    let log = Log::default();
+    let children = probes(
+        vec![
+            ProbeSpec::new("a", 0.0, 0.0, 100.0, 20.0),
+            ProbeSpec::new("b", 0.0, 0.0, 50.0, 20.0),
+            ProbeSpec::new("c", 0.0, 0.0, 300.0, 20.0),
+        ],
+        &log,
+    );
+    let harness = TestHarness::new(engine_with_template(
+        vec![flex(
+            || Flex {
+                direction: Box::new(PropertyLiteral::new(FlexDirection::Column)),
+                justify_content: Box::new(PropertyLiteral::new(JustifyContent::SpaceBetween)),
+                align_items: Box::new(PropertyLiteral::new(AlignItems::Center)),
+                ..Default::default()
+            },
+            children,
+        )],
+        None,
+    ));
+
+    assert_eq!(rect(&harness.computed_tab("a")), (100.0, 0.0, 100.0, 20.0));
+    assert_eq!(rect(&harness.computed_tab("b")), (125.0, 40.0, 50.0, 20.0));
+    assert_eq!(rect(&harness.computed_tab("c")), (0.0, 80.0, 300.0, 20.0));
+}
+
+#[test]
+fn items_wrap_onto_further_lines() {
+    let log = Log::default();
+    let children = probes(
+        vec![
+            ProbeSpec::new("a", 0.0, 0.0, 120.0, 30.0),
+            ProbeSpec::new("b", 0.0, 0.0, 120.0, 40.0),
+            ProbeSpec::new("c", 5.0, 0.0, 120.0, 20.0),
+        ],
+        &log,
+    );
+    let harness = TestHarness::new(engine_with_template(
+        vec![flex(
+            || Flex {
+                wrap: Box::new(PropertyLiteral::new(true)),
+                justify_content: Box::new(PropertyLiteral::new(JustifyContent::Center)),
+                align_items: Box::new(PropertyLiteral::new(AlignItems::Start)),
+                gap: Box::new(PropertyLiteral::new(px(10.0))),
+                line_gap: Box::new(PropertyLiteral::new(px(5.0))),
+                ..Default::default()
+            },
+            children,
+        )],
+        None,
+    ));
+
+    assert_eq!(rect(&harness.computed_tab("a")), (25.0, 0.0, 120.0, 30.0));
+    assert_eq!(rect(&harness.computed_tab("b")), (155.0, 0.0, 120.0, 40.0));
+    // the first line is as thick as `b`; `c`'s `x` offsets it from its cell
+    assert_eq!(rect(&harness.computed_tab("c")), (95.0, 45.0, 120.0, 20.0));
+}
+
+#[test]
+fn items_from_for_are_laid_out_on_their_first_tick() {
+    let log = Log::default();
+    let repeat = RepeatInstance::instantiate(InstantiationArgs {
+        prototypical_common_properties_factory: Box::new(|| {
+            Rc::new(RefCell::new(CommonProperties::default()))
+        }),
+        prototypical_properties_factory: Box::new(|| {
+            let mut properties = RepeatProperties::default();
+            properties.source_expression_range = Some(Box::new(PropertyLiteral::new(0..3)));
+            Rc::new(RefCell::new(properties))
+        }),
+        handler_registry: None,
+        children: Some(RefCell::new(probes(
+            vec![ProbeSpec::new("item", 0.0, 0.0, 10.0, 10.0)],
+            &log,
+        ))),
+        component_template: None,
+        compute_properties_fn: None,
+        timeline: None,
+        template_node_identifier: None,
+    });
+    let harness = TestHarness::new(engine_with_template(
+        vec![flex(
+            || Flex {
+                items: Box::new(PropertyLiteral::new(vec![
+                    FlexItem::new(
+                        1.into(),
+                        1.into(),
+                        px(0.0)
+                    );
+                    3
+                ])),
+                ..Default::default()
+            },
+            vec![repeat],
+        )],
+        None,
+    ));
+
+    let mut items: Vec<_> = harness
+        .get_nodes_by_id("item")
+        .iter()
+        .map(|item| {
+            rect(
+                &item
+                    .layout_properties
+                    .borrow()
+                    .as_ref()
+                    .unwrap()
+                    .computed_tab,
+            )
+        })
+        .collect();
+    items.sort_by(|a, b| a.0.total_cmp(&b.0));
+    assert_eq!(
+        items,
+        [
+            (0.0, 0.0, 100.0, 100.0),
+            (100.0, 0.0, 100.0, 100.0),
+            (200.0, 0.0, 100.0, 100.0)
+        ]
+    );
+}
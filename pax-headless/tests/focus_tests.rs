@@ -0,0 +1,141 @@
+mod common;
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use common::{engine, record, Log, ProbeSpec};
+use pax_headless::TestHarness;
+use pax_message::ModifierKeyMessage;
+use pax_runtime::api::{Blur, Event, Focus, KeyDown, NodeContext, Numeric, PropertyLiteral};
+use pax_runtime::constants::{BLUR_HANDLERS, FOCUS_HANDLERS, KEY_DOWN_HANDLERS};
+
+fn on_focus(properties: Rc<RefCell<dyn Any>>, _ctx: &NodeContext, args: Option<Box<dyn Any>>) {
+    args.unwrap().downcast::<Event<Focus>>().unwrap();
+    record(properties, |name| format!("focus {}", name));
+}
+
+fn on_blur(properties: Rc<RefCell<dyn Any>>, _ctx: &NodeContext, args: Option<Box<dyn Any>>) {
+    args.unwrap().downcast::<Event<Blur>>().unwrap();
+    record(properties, |name| format!("blur {}", name));
+}
+
+fn on_key_down(properties: Rc<RefCell<dyn Any>>, _ctx: &NodeContext, args: Option<Box<dyn Any>>) {
+    let event = args.unwrap().downcast::<Event<KeyDown>>().unwrap();
+    record(properties, |name| {
+        format!("key_down {} {}", name, event.keyboard.key)
+    });
+}
+
+/// A probe that records its focus, blur and key down events
+fn probe(name: &'static str, x: f64, y: f64, width: f64, height: f64) -> ProbeSpec {
+    ProbeSpec::new(name, x, y, width, height)
+        .on(FOCUS_HANDLERS, on_focus)
+        .on(BLUR_HANDLERS, on_blur)
+        .on(KEY_DOWN_HANDLERS, on_key_down)
+}
+
+trait Focusable {
+    fn tab_index(self, tab_index: isize) -> Self;
+}
+
+impl Focusable for ProbeSpec {
+    fn tab_index(self, tab_index: isize) -> Self {
+        self.common_properties(move |common| {
+            common.tab_index = Some(Box::new(PropertyLiteral::new(Numeric::from(tab_index))))
+        })
+    }
+}
+
+fn focused_id(harness: &TestHarness) -> Option<String> {
+    harness.focused_node().map(|node| {
+        let common_properties = node.get_common_properties();
+        let id = common_properties
+            .borrow()
+            .id
+            .as_ref()
+            .unwrap()
+            .get()
+            .clone();
+        id
+    })
+}
+
+#[test]
+fn tab_follows_tab_index_then_template_order() {
+    let log = Log::default();
+    let mut harness = TestHarness::new(engine(
+        vec![
+            probe("a", 0.0, 0.0, 10.0, 10.0).tab_index(0),
+            probe("skipped", 0.0, 20.0, 10.0, 10.0).tab_index(-1),
+            probe("b", 0.0, 40.0, 10.0, 10.0).tab_index(2),
+            probe("c", 0.0, 60.0, 10.0, 10.0).tab_index(1),
+            probe("unfocusable", 0.0, 80.0, 10.0, 10.0),
+            probe("d", 0.0, 100.0, 10.0, 10.0).tab_index(0),
+        ],
+        &log,
+    ));
+
+    let mut order = vec![];
+    for _ in 0..5 {
+        assert!(harness.key_press("Tab"));
+        order.push(focused_id(&harness).unwrap());
+    }
+    assert_eq!(order, vec!["c", "b", "a", "d", "c"]);
+
+    harness.key_press_with_modifiers("Tab", &[ModifierKeyMessage::Shift]);
+    assert_eq!(focused_id(&harness).as_deref(), Some("d"));
+}
+
+#[test]
+fn focus_change_fires_blur_then_focus() {
+    let log = Log::default();
+    let mut harness = TestHarness::new(engine(
+        vec![
+            probe("a", 0.0, 0.0, 10.0, 10.0).tab_index(0),
+            probe("b", 0.0, 20.0, 10.0, 10.0).tab_index(0),
+        ],
+        &log,
+    ));
+    harness.focus("a");
+    harness.focus("b");
+    assert_eq!(*log.borrow(), vec!["focus a", "blur a", "focus b"]);
+}
+
+#[test]
+fn clicking_focuses_closest_focusable_ancestor() {
+    let log = Log::default();
+    let mut harness = TestHarness::new(engine(
+        vec![probe("field", 0.0, 0.0, 100.0, 100.0)
+            .tab_index(-1)
+            .children(vec![probe("label", 10.0, 10.0, 20.0, 20.0)])],
+        &log,
+    ));
+    harness.click_node("label");
+    assert_eq!(focused_id(&harness).as_deref(), Some("field"));
+
+    // clicking outside any focusable node clears focus
+    harness.click(300.0, 200.0);
+    assert_eq!(focused_id(&harness), None);
+}
+
+#[test]
+fn key_events_bubble_from_focused_node() {
+    let log = Log::default();
+    let mut harness = TestHarness::new(engine(
+        vec![
+            probe("form", 0.0, 0.0, 100.0, 100.0)
+                .children(vec![probe("input", 10.0, 10.0, 20.0, 20.0).tab_index(0)]),
+            probe("sibling", 200.0, 0.0, 10.0, 10.0),
+        ],
+        &log,
+    ));
+    harness.focus("input");
+    log.borrow_mut().clear();
+
+    harness.key_press("Enter");
+    assert_eq!(
+        *log.borrow(),
+        vec!["key_down input Enter", "key_down form Enter"]
+    );
+}
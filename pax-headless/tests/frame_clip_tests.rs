@@ -0,0 +1,153 @@
+mod common;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use common::{engine_with_template, entries, on_click, Log, ProbeSpec};
+use kurbo::{Point, Shape};
+use pax_headless::{RasterRenderer, TestHarness};
+use pax_runtime::api::reactivity;
+use pax_runtime::api::{CommonProperties, PropertyLiteral, Size};
+use pax_runtime::constants::CLICK_HANDLERS;
+use pax_runtime::{InstanceNode, InstanceNodePtr, InstantiationArgs};
+use pax_std::primitives::Frame;
+use pax_std::types::{PathElement, RectangleCornerRadii};
+use pax_std_primitives::frame::FrameInstance;
+
+/// A 40x40 frame with the id `frame` at the origin, wrapping `children`
+fn frame(
+    corner_radius: f64,
+    clip_path: Vec<PathElement>,
+    children: Vec<ProbeSpec>,
+    log: &Log,
+) -> InstanceNodePtr {
+    let children = children
+        .into_iter()
+        .map(|child| child.instantiate(log))
+        .collect();
+    FrameInstance::instantiate(InstantiationArgs {
+        prototypical_common_properties_factory: Box::new(|| {
+            Rc::new(RefCell::new(CommonProperties {
+                id: Some(Box::new(PropertyLiteral::new("frame".to_string()))),
+                x: Some(Box::new(PropertyLiteral::new(Size::Pixels(0.into())))),
+                y: Some(Box::new(PropertyLiteral::new(Size::Pixels(0.into())))),
+                width: Box::new(PropertyLiteral::new(Size::Pixels(40.into()))),
+                height: Box::new(PropertyLiteral::new(Size::Pixels(40.into()))),
+                ..Default::default()
+            }))
+        }),
+        prototypical_properties_factory: Box::new(move || {
+            let radius = corner_radius.into();
+            Rc::new(RefCell::new(Frame {
+                corner_radii: Box::new(PropertyLiteral::new(RectangleCornerRadii::radii(
+                    radius, radius, radius, radius,
+                ))),
+                clip_path: Box::new(PropertyLiteral::new(clip_path.clone())),
+            }))
+        }),
+        handler_registry: None,
+        children: Some(RefCell::new(children)),
+        component_template: None,
+        compute_properties_fn: None,
+        timeline: None,
+        template_node_identifier: None,
+    })
+}
+
+fn px(value: f64) -> Size {
+    Size::Pixels(value.into())
+}
+
+#[test]
+fn rounded_corners_clip_drawing_and_hits() {
+    let log = Log::default();
+    let content = ProbeSpec::new("content", 0.0, 0.0, 40.0, 40.0).on(CLICK_HANDLERS, on_click);
+    let mut harness = TestHarness::new(engine_with_template(
+        vec![frame(20.0, vec![], vec![content], &log)],
+        None,
+    ));
+
+    harness.click(2.0, 2.0);
+    assert!(entries(&log, "click").is_empty());
+    harness.click(20.0, 20.0);
+    assert_eq!(entries(&log, "click"), ["click content (20, 20)"]);
+
+    let mut renderer = RasterRenderer::new(40, 40);
+    harness.render(&mut renderer);
+    let composite = renderer.composite();
+    let alpha = |x, y| composite.pixel(x, y).unwrap().alpha();
+    assert_eq!(alpha(1, 1), 0);
+    assert_eq!(alpha(38, 38), 0);
+    assert_eq!(alpha(20, 20), 255);
+    assert_eq!(alpha(20, 1), 255);
+}
+
+#[test]
+fn clip_path_overrides_corner_radii() {
+    let log = Log::default();
+    let triangle = vec![
+        PathElement::point(px(0.0), px(0.0)),
+        PathElement::line(),
+        PathElement::point(Size::Percent(100.into()), px(0.0)),
+        PathElement::line(),
+        PathElement::point(px(0.0), Size::Percent(100.into())),
+        PathElement::close(),
+    ];
+    let content = ProbeSpec::new("content", 0.0, 0.0, 40.0, 40.0).on(CLICK_HANDLERS, on_click);
+    let mut harness = TestHarness::new(engine_with_template(
+        vec![frame(20.0, triangle, vec![content], &log)],
+        None,
+    ));
+
+    // inside the triangle, in what would otherwise be a rounded-off corner
+    harness.click(2.0, 2.0);
+    assert_eq!(entries(&log, "click"), ["click content (2, 2)"]);
+    log.borrow_mut().clear();
+    harness.click(30.0, 30.0);
+    assert!(entries(&log, "click").is_empty());
+
+    let mut renderer = RasterRenderer::new(40, 40);
+    harness.render(&mut renderer);
+    let composite = renderer.composite();
+    let alpha = |x, y| composite.pixel(x, y).unwrap().alpha();
+    assert_eq!(alpha(2, 2), 255);
+    assert_eq!(alpha(30, 30), 0);
+}
+
+#[test]
+fn ancestral_clip_path_is_in_the_descendants_local_space() {
+    let log = Log::default();
+    let content = ProbeSpec::new("content", 10.0, 10.0, 20.0, 20.0);
+    let harness = TestHarness::new(engine_with_template(
+        vec![frame(20.0, vec![], vec![content], &log)],
+        None,
+    ));
+
+    let clip_path = harness
+        .get_node_by_id("content")
+        .get_ancestral_clip_path()
+        .unwrap();
+    let bounds = clip_path.bounding_box();
+    assert!((bounds.x0 + 10.0).abs() < 0.5 && (bounds.y0 + 10.0).abs() < 0.5);
+    assert!((bounds.x1 - 30.0).abs() < 0.5 && (bounds.y1 - 30.0).abs() < 0.5);
+    assert!(clip_path.contains(Point::new(10.0, 10.0)));
+    assert!(!clip_path.contains(Point::new(-9.0, -9.0)));
+}
+
+#[test]
+fn literal_corner_radii_are_not_marked_changed_by_ticks() {
+    let log = Log::default();
+    let mut harness = TestHarness::new(engine_with_template(
+        vec![frame(10.0, vec![], vec![], &log)],
+        None,
+    ));
+    let (_, dependencies) = reactivity::track_dependencies(|| {
+        harness.with_properties("frame", |frame: &mut Frame| {
+            frame.corner_radii.get().top_left.get().to_float()
+        })
+    });
+
+    harness.advance(2);
+
+    assert!(!reactivity::any_changed(&dependencies));
+}
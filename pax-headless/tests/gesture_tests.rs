@@ -0,0 +1,172 @@
+mod common;
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use common::{engine, entries, record, Log, ProbeSpec};
+use pax_headless::TestHarness;
+use pax_runtime::api::{
+    Event, LongPress, NodeContext, Pan, Pinch, RotateGesture, Swipe, SwipeDirection,
+};
+use pax_runtime::constants::{
+    LONG_PRESS_HANDLERS, PAN_HANDLERS, PINCH_HANDLERS, ROTATE_GESTURE_HANDLERS, SWIPE_HANDLERS,
+};
+
+fn on_pinch(properties: Rc<RefCell<dyn Any>>, _ctx: &NodeContext, args: Option<Box<dyn Any>>) {
+    let event = args.unwrap().downcast::<Event<Pinch>>().unwrap();
+    record(properties, |name| format!("pinch {} {}", name, event.scale));
+}
+
+fn on_rotate_gesture(
+    properties: Rc<RefCell<dyn Any>>,
+    _ctx: &NodeContext,
+    args: Option<Box<dyn Any>>,
+) {
+    let event = args.unwrap().downcast::<Event<RotateGesture>>().unwrap();
+    record(properties, |name| {
+        format!("rotate_gesture {} {}", name, event.angle.round())
+    });
+}
+
+fn on_pan(properties: Rc<RefCell<dyn Any>>, _ctx: &NodeContext, args: Option<Box<dyn Any>>) {
+    let event = args.unwrap().downcast::<Event<Pan>>().unwrap();
+    record(properties, |name| {
+        format!(
+            "pan {} +({}, {}) total ({}, {})",
+            name, event.delta_x, event.delta_y, event.total_delta_x, event.total_delta_y
+        )
+    });
+}
+
+fn on_long_press(properties: Rc<RefCell<dyn Any>>, _ctx: &NodeContext, args: Option<Box<dyn Any>>) {
+    let event = args.unwrap().downcast::<Event<LongPress>>().unwrap();
+    record(properties, |name| {
+        format!("long_press {} ({}, {})", name, event.x, event.y)
+    });
+}
+
+fn on_swipe(properties: Rc<RefCell<dyn Any>>, _ctx: &NodeContext, args: Option<Box<dyn Any>>) {
+    let event = args.unwrap().downcast::<Event<Swipe>>().unwrap();
+    let direction = match event.direction {
+        SwipeDirection::Up => "up",
+        SwipeDirection::Down => "down",
+        SwipeDirection::Left => "left",
+        SwipeDirection::Right => "right",
+    };
+    record(properties, |name| format!("swipe {} {}", name, direction));
+}
+
+fn harness(log: &Log) -> TestHarness {
+    let canvas = ProbeSpec::new("canvas", 0.0, 0.0, 400.0, 300.0)
+        .on(PINCH_HANDLERS, on_pinch)
+        .on(ROTATE_GESTURE_HANDLERS, on_rotate_gesture)
+        .on(PAN_HANDLERS, on_pan)
+        .on(LONG_PRESS_HANDLERS, on_long_press)
+        .on(SWIPE_HANDLERS, on_swipe);
+    TestHarness::new(engine(vec![canvas], log))
+}
+
+#[test]
+fn pan_starts_past_threshold_and_reports_deltas() {
+    let log = Log::default();
+    let mut harness = harness(&log);
+    harness.touch_start(&[(0, 100.0, 100.0)]);
+    harness.touch_move(&[(0, 105.0, 100.0)]);
+    assert!(entries(&log, "pan").is_empty());
+
+    harness.touch_move(&[(0, 120.0, 100.0)]);
+    harness.touch_move(&[(0, 130.0, 110.0)]);
+    harness.touch_end(&[0]);
+    assert_eq!(
+        entries(&log, "pan"),
+        vec![
+            "pan canvas +(20, 0) total (20, 0)",
+            "pan canvas +(10, 10) total (30, 10)",
+        ]
+    );
+}
+
+#[test]
+fn two_fingers_pinch_and_rotate() {
+    let log = Log::default();
+    let mut harness = harness(&log);
+    harness.touch_start(&[(0, 100.0, 100.0)]);
+    harness.touch_start(&[(1, 200.0, 100.0)]);
+
+    harness.touch_move(&[(1, 300.0, 100.0)]);
+    // clockwise quarter turn, back to the original distance
+    harness.touch_move(&[(1, 100.0, 200.0)]);
+    harness.touch_end(&[0, 1]);
+    assert_eq!(
+        entries(&log, "pinch"),
+        vec!["pinch canvas 2", "pinch canvas 1"]
+    );
+    assert_eq!(
+        entries(&log, "rotate_gesture"),
+        vec!["rotate_gesture canvas 0", "rotate_gesture canvas 90"]
+    );
+    assert!(entries(&log, "swipe").is_empty());
+}
+
+#[test]
+fn quick_flick_swipes_but_slow_one_does_not() {
+    let log = Log::default();
+    let mut harness = harness(&log);
+    harness.touch_start(&[(0, 200.0, 200.0)]);
+    harness.touch_move(&[(0, 200.0, 100.0)]);
+    harness.touch_end(&[0]);
+    assert_eq!(entries(&log, "swipe"), vec!["swipe canvas up"]);
+
+    log.borrow_mut().clear();
+    harness.touch_start(&[(1, 100.0, 100.0)]);
+    harness.advance(20);
+    harness.touch_move(&[(1, 200.0, 100.0)]);
+    harness.touch_end(&[1]);
+    assert!(entries(&log, "swipe").is_empty());
+}
+
+#[test]
+fn holding_still_long_presses_once() {
+    let log = Log::default();
+    let mut harness = harness(&log);
+    harness.touch_start(&[(0, 50.0, 60.0)]);
+    harness.advance(10);
+    assert!(entries(&log, "long_press").is_empty());
+
+    harness.advance(40);
+    harness.touch_end(&[0]);
+    assert_eq!(
+        entries(&log, "long_press"),
+        vec!["long_press canvas (50, 60)"]
+    );
+}
+
+#[test]
+fn fingers_touching_down_beside_every_node_join_the_gesture() {
+    let log = Log::default();
+    let canvas = ProbeSpec::new("canvas", 0.0, 0.0, 200.0, 300.0).on(PINCH_HANDLERS, on_pinch);
+    let mut harness = TestHarness::new(engine(vec![canvas], &log));
+    harness.touch_start(&[(5, 100.0, 100.0)]);
+    // listed first, beside the canvas
+    harness.touch_start(&[(1, 300.0, 100.0)]);
+    harness.touch_move(&[(1, 500.0, 100.0)]);
+    assert_eq!(entries(&log, "pinch"), vec!["pinch canvas 2"]);
+}
+
+#[test]
+fn fingers_touching_down_together_pinch_from_where_they_part() {
+    let log = Log::default();
+    let mut harness = harness(&log);
+    harness.touch_start(&[(0, 100.0, 100.0)]);
+    harness.touch_start(&[(1, 100.0, 100.0)]);
+
+    harness.touch_move(&[(1, 200.0, 100.0)]);
+    assert!(entries(&log, "pinch").is_empty());
+    harness.touch_move(&[(1, 300.0, 100.0)]);
+    assert_eq!(entries(&log, "pinch"), vec!["pinch canvas 2"]);
+    assert_eq!(
+        entries(&log, "rotate_gesture"),
+        vec!["rotate_gesture canvas 0"]
+    );
+}
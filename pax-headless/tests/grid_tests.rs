@@ -0,0 +1,117 @@
+mod common;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use common::{engine_with_template, Log, ProbeSpec};
+use pax_headless::TestHarness;
+use pax_runtime::api::{CommonProperties, PropertyLiteral, Size};
+use pax_runtime::{InstanceNode, InstanceNodePtr, InstantiationArgs, TransformAndBounds};
+use pax_std::primitives::Grid;
+use pax_std::types::{GridPlacement, GridTrack};
+use pax_std_primitives::grid::GridInstance;
+
+fn px(value: f64) -> Size {
+    Size::Pixels(value.into())
+}
+
+/// A 300x100 `Grid` at the origin, with the properties `grid` returns, laying out `children`
+fn grid(grid: impl Fn() -> Grid + 'static, children: Vec<ProbeSpec>, log: &Log) -> InstanceNodePtr {
+    let children = children
+        .into_iter()
+        .map(|child| child.instantiate(log))
+        .collect();
+    GridInstance::instantiate(InstantiationArgs {
+        prototypical_common_properties_factory: Box::new(|| {
+            Rc::new(RefCell::new(CommonProperties {
+                width: Box::new(PropertyLiteral::new(px(300.0))),
+                height: Box::new(PropertyLiteral::new(px(100.0))),
+                ..Default::default()
+            }))
+        }),
+        prototypical_properties_factory: Box::new(move || Rc::new(RefCell::new(grid()))),
+        handler_registry: None,
+        children: Some(RefCell::new(children)),
+        component_template: None,
+        compute_properties_fn: None,
+        timeline: None,
+        template_node_identifier: None,
+    })
+}
+
+/// `tab`'s position and size, rounded to hundredths of a pixel
+fn rect(tab: &TransformAndBounds) -> (f64, f64, f64, f64) {
+    let round = |value: f64| (value * 100.0).round() / 100.0;
+    let [.., x, y] = tab.transform.coeffs();
+    (round(x), round(y), round(tab.bounds.0), round(tab.bounds.1))
+}
+
+#[test]
+fn fractional_tracks_share_what_fixed_tracks_and_gaps_leave() {
+    let log = Log::default();
+    let children = ["a", "b", "c", "d"]
+        .map(|id| ProbeSpec::new(id, 0.0, 0.0, 10.0, 10.0))
+        .into();
+    let harness = TestHarness::new(engine_with_template(
+        vec![grid(
+            || Grid {
+                columns: Box::new(PropertyLiteral::new(vec![
+                    GridTrack::fixed(px(50.0)),
+                    GridTrack::fr(1.into()),
+                    GridTrack::fr(2.into()),
+                ])),
+                column_gap: Box::new(PropertyLiteral::new(px(10.0))),
+                row_gap: Box::new(PropertyLiteral::new(px(10.0))),
+                ..Default::default()
+            },
+            children,
+            &log,
+        )],
+        None,
+    ));
+
+    // 230px are left for the fractional columns; the second row is added to fit `d`
+    assert_eq!(rect(&harness.computed_tab("a")), (0.0, 0.0, 50.0, 45.0));
+    assert_eq!(rect(&harness.computed_tab("b")), (60.0, 0.0, 76.67, 45.0));
+    assert_eq!(
+        rect(&harness.computed_tab("c")),
+        (146.67, 0.0, 153.33, 45.0)
+    );
+    assert_eq!(rect(&harness.computed_tab("d")), (0.0, 55.0, 50.0, 45.0));
+}
+
+#[test]
+fn children_flow_around_explicitly_placed_areas() {
+    let log = Log::default();
+    let children = ["a", "b", "c", "d"]
+        .map(|id| ProbeSpec::new(id, 0.0, 0.0, 10.0, 10.0))
+        .into();
+    let harness = TestHarness::new(engine_with_template(
+        vec![grid(
+            || Grid {
+                columns: Box::new(PropertyLiteral::new(vec![GridTrack::default(); 3])),
+                rows: Box::new(PropertyLiteral::new(vec![GridTrack::fixed(Size::Percent(
+                    50.into(),
+                ))])),
+                placements: Box::new(PropertyLiteral::new(vec![
+                    GridPlacement::area(0.into(), 1.into(), 2.into(), 2.into()),
+                    GridPlacement::default(),
+                    GridPlacement::span(1.into(), 2.into()),
+                ])),
+                ..Default::default()
+            },
+            children,
+            &log,
+        )],
+        None,
+    ));
+
+    assert_eq!(rect(&harness.computed_tab("a")), (100.0, 0.0, 200.0, 100.0));
+    assert_eq!(rect(&harness.computed_tab("b")), (0.0, 0.0, 100.0, 50.0));
+    // `c` is too wide for what's left of the first two rows; the third is sized like the first
+    assert_eq!(rect(&harness.computed_tab("c")), (0.0, 100.0, 200.0, 50.0));
+    assert_eq!(
+        rect(&harness.computed_tab("d")),
+        (200.0, 100.0, 100.0, 50.0)
+    );
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use common::{engine, on_click, on_click_capture, record, Log, ProbeSpec};
+use pax_headless::TestHarness;
+use pax_runtime::api::{Event, KeyDown, NodeContext, Numeric, PropertyLiteral};
+use pax_runtime::constants::{CAPTURE_SUFFIX, CLICK_HANDLERS, KEY_DOWN_HANDLERS};
+
+fn on_key_down(properties: Rc<RefCell<dyn Any>>, _ctx: &NodeContext, args: Option<Box<dyn Any>>) {
+    let event = args.unwrap().downcast::<Event<KeyDown>>().unwrap();
+    record(properties, |name| {
+        format!("key_down {} {}", name, event.keyboard.key)
+    });
+}
+
+#[test]
+fn click_is_routed_to_node_beneath_pointer() {
+    let log = Log::default();
+    let mut harness = TestHarness::new(engine(
+        vec![ProbeSpec::new("probe", 20.0, 10.0, 100.0, 50.0)
+            .on(CLICK_HANDLERS, on_click)
+            .on(
+                format!("{}{}", CLICK_HANDLERS, CAPTURE_SUFFIX),
+                on_click_capture,
+            )],
+        &log,
+    ));
+    assert_eq!(harness.center_of("probe"), (70.0, 35.0));
+
+    harness.click(5.0, 5.0);
+    assert!(log.borrow().is_empty());
+
+    harness.click_node("probe");
+    assert_eq!(
+        *log.borrow(),
+        vec!["click_capture probe -> probe", "click probe (70, 35)"]
+    );
+}
+
+#[test]
+fn key_press_dispatches_key_down() {
+    let log = Log::default();
+    let mut harness = TestHarness::new(engine(
+        vec![ProbeSpec::new("probe", 0.0, 0.0, 100.0, 50.0)
+            .on(KEY_DOWN_HANDLERS, on_key_down)
+            .common_properties(|common| {
+                common.tab_index = Some(Box::new(PropertyLiteral::new(Numeric::from(0))))
+            })],
+        &log,
+    ));
+    harness.focus("probe");
+    log.borrow_mut().clear();
+
+    harness.key_press("a");
+    harness.key_press("b");
+    assert_eq!(*log.borrow(), vec!["key_down probe a", "key_down probe b"]);
+}
+
+#[test]
+fn advance_runs_ticks() {
+    let log = Log::default();
+    let mut harness = TestHarness::new(engine(vec![], &log));
+    let start = harness.frames_elapsed();
+    harness.advance(3);
+    assert_eq!(harness.frames_elapsed(), start + 3);
+    harness.take_messages();
+    assert!(harness.messages().is_empty());
+}
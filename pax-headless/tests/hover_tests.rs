@@ -0,0 +1,76 @@
+mod common;
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use common::{engine, record, Log, ProbeSpec};
+use pax_headless::TestHarness;
+use pax_message::{MouseButtonMessage, MouseOutInterruptArgs, NativeInterrupt};
+use pax_runtime::api::{Event, MouseOut, MouseOver, NodeContext};
+use pax_runtime::constants::{MOUSE_OUT_HANDLERS, MOUSE_OVER_HANDLERS};
+
+fn on_mouse_over(properties: Rc<RefCell<dyn Any>>, _ctx: &NodeContext, args: Option<Box<dyn Any>>) {
+    args.unwrap().downcast::<Event<MouseOver>>().unwrap();
+    record(properties, |name| format!("mouse_over {}", name));
+}
+
+fn on_mouse_out(properties: Rc<RefCell<dyn Any>>, _ctx: &NodeContext, args: Option<Box<dyn Any>>) {
+    args.unwrap().downcast::<Event<MouseOut>>().unwrap();
+    record(properties, |name| format!("mouse_out {}", name));
+}
+
+/// A probe that records the pointer entering and leaving it
+fn probe(name: &'static str, x: f64, y: f64, width: f64, height: f64) -> ProbeSpec {
+    ProbeSpec::new(name, x, y, width, height)
+        .on(MOUSE_OVER_HANDLERS, on_mouse_over)
+        .on(MOUSE_OUT_HANDLERS, on_mouse_out)
+}
+
+fn card(log: &Log) -> TestHarness {
+    TestHarness::new(engine(
+        vec![probe("card", 0.0, 0.0, 200.0, 100.0).children(vec![
+            probe("icon", 10.0, 10.0, 20.0, 20.0),
+            probe("label", 50.0, 10.0, 100.0, 20.0),
+        ])],
+        log,
+    ))
+}
+
+#[test]
+fn hover_changes_fire_on_every_affected_node() {
+    let log = Log::default();
+    let mut harness = card(&log);
+
+    harness.mouse_move(20.0, 20.0);
+    assert_eq!(*log.borrow(), vec!["mouse_over card", "mouse_over icon"]);
+
+    log.borrow_mut().clear();
+    harness.mouse_move(22.0, 21.0);
+    assert!(log.borrow().is_empty());
+
+    // moving between siblings leaves their shared ancestor hovered
+    harness.mouse_move(60.0, 20.0);
+    assert_eq!(*log.borrow(), vec!["mouse_out icon", "mouse_over label"]);
+
+    log.borrow_mut().clear();
+    harness.mouse_move(300.0, 250.0);
+    assert_eq!(*log.borrow(), vec!["mouse_out label", "mouse_out card"]);
+    assert!(harness.engine().hovered_nodes().is_empty());
+}
+
+#[test]
+fn leaving_the_viewport_clears_hover() {
+    let log = Log::default();
+    let mut harness = card(&log);
+    harness.mouse_move(20.0, 20.0);
+    log.borrow_mut().clear();
+
+    harness.interrupt(NativeInterrupt::MouseOut(MouseOutInterruptArgs {
+        x: 20.0,
+        y: -1.0,
+        button: MouseButtonMessage::Left,
+        modifiers: vec![],
+    }));
+    assert_eq!(*log.borrow(), vec!["mouse_out icon", "mouse_out card"]);
+}
@@ -0,0 +1,233 @@
+mod common;
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use common::{engine_with_expressions, Expressions, Log, ProbeSpec};
+use pax_headless::TestHarness;
+use pax_runtime::api::reactivity::{self, Dependency};
+use pax_runtime::api::{CommonProperties, PropertyLiteral};
+use pax_runtime::{
+    ExpandedNode, ExpressionContext, InstanceNode, InstanceNodePtr, InstantiationArgs,
+    RepeatInstance, RepeatItem, RepeatKey, RepeatProperties,
+};
+use pax_std::primitives::Flex;
+use pax_std_primitives::flex::FlexInstance;
+
+const KEY_EXPRESSION: usize = 0;
+
+fn names(names: &[&'static str]) -> Vec<Rc<RefCell<dyn Any>>> {
+    names
+        .iter()
+        .map(|&name| Rc::new(RefCell::new(name)) as Rc<RefCell<dyn Any>>)
+        .collect()
+}
+
+/// A `for name in <names> key name` with the id `list`, repeating a probe named `item`
+fn keyed_for(initial: &[&'static str], log: &Log) -> TestHarness {
+    keyed_for_within(initial, log, |repeat| repeat)
+}
+
+/// Like `keyed_for`, with the `for` placed in the template by `within`
+fn keyed_for_within(
+    initial: &[&'static str],
+    log: &Log,
+    within: impl FnOnce(InstanceNodePtr) -> InstanceNodePtr,
+) -> TestHarness {
+    let initial = initial.to_vec();
+    let repeat = RepeatInstance::instantiate(InstantiationArgs {
+        prototypical_common_properties_factory: Box::new(|| {
+            Rc::new(RefCell::new(CommonProperties {
+                id: Some(Box::new(PropertyLiteral::new("list".to_string()))),
+                ..Default::default()
+            }))
+        }),
+        prototypical_properties_factory: Box::new(move || {
+            let mut properties = RepeatProperties::default();
+            properties.source_expression_vec =
+                Some(Box::new(PropertyLiteral::new(names(&initial))));
+            properties.key_expression_vtable_id = Some(KEY_EXPRESSION);
+            Rc::new(RefCell::new(properties))
+        }),
+        handler_registry: None,
+        children: Some(RefCell::new(vec![ProbeSpec::new(
+            "item", 0.0, 0.0, 10.0, 10.0,
+        )
+        .instantiate(log)])),
+        component_template: None,
+        compute_properties_fn: None,
+        timeline: None,
+        template_node_identifier: None,
+    });
+    let mut expressions = Expressions::new();
+    expressions.insert(
+        KEY_EXPRESSION,
+        Box::new(|ec: ExpressionContext| {
+            let item = ec.stack_frame.peek_nth(0).unwrap();
+            let item = item.borrow();
+            let name = *item
+                .downcast_ref::<RepeatItem>()
+                .unwrap()
+                .elem()
+                .borrow()
+                .downcast_ref::<&str>()
+                .unwrap();
+            Box::new(RepeatKey::from(name))
+        }),
+    );
+    TestHarness::new(engine_with_expressions(
+        vec![within(repeat)],
+        None,
+        expressions,
+    ))
+}
+
+fn set_names(harness: &mut TestHarness, new_names: &[&'static str]) {
+    harness.get_node_by_id("list").with_properties_unwrapped(
+        |properties: &mut RepeatProperties| {
+            properties
+                .source_expression_vec
+                .as_mut()
+                .unwrap()
+                .set(names(new_names))
+        },
+    );
+    harness.tick();
+}
+
+/// The stack frame of `node`, an item of the `for`
+fn repeat_item(node: &ExpandedNode) -> Rc<RefCell<dyn Any>> {
+    node.stack.peek_nth(0).unwrap()
+}
+
+/// What expressions reading the `i` of `node`, an item of the `for`, depend on
+fn index_dependencies(node: &ExpandedNode) -> Vec<Dependency> {
+    let item = repeat_item(node);
+    let (_, dependencies) =
+        reactivity::track_dependencies(|| item.borrow().downcast_ref::<RepeatItem>().unwrap().i());
+    dependencies
+}
+
+/// The `for`'s items, in order, as their node's id and their element and index
+fn items(harness: &TestHarness) -> Vec<(u32, &'static str, usize)> {
+    let list = harness.get_node_by_id("list");
+    let children = list.children.borrow();
+    children
+        .iter()
+        .map(|node| {
+            let item = repeat_item(node);
+            let item = item.borrow();
+            let item = item.downcast_ref::<RepeatItem>().unwrap();
+            let name = *item.elem().borrow().downcast_ref::<&str>().unwrap();
+            (node.id_chain[0], name, item.i())
+        })
+        .collect()
+}
+
+#[test]
+fn inserting_an_element_keeps_the_nodes_of_the_others() {
+    let log = Log::default();
+    let mut harness = keyed_for(&["a", "b", "c"], &log);
+    let before = items(&harness);
+    let b = harness.get_node_by_id("list").children.borrow()[1].clone();
+    let b_dependencies = index_dependencies(&b);
+
+    set_names(&mut harness, &["a", "x", "b", "c"]);
+    let after = items(&harness);
+
+    assert_eq!(
+        after
+            .iter()
+            .map(|&(_, name, i)| (name, i))
+            .collect::<Vec<_>>(),
+        [("a", 0), ("x", 1), ("b", 2), ("c", 3)]
+    );
+    assert_eq!(after[0].0, before[0].0);
+    assert_eq!(after[2].0, before[1].0);
+    assert_eq!(after[3].0, before[2].0);
+    assert!(!before.iter().any(|&(id, ..)| id == after[1].0));
+    // Expressions that read `b`'s index are re-evaluated now that it's moved
+    assert!(reactivity::any_changed(&b_dependencies));
+}
+
+#[test]
+fn elements_sharing_a_key_keep_their_nodes_in_order() {
+    let log = Log::default();
+    let mut harness = keyed_for(&["a", "a", "b"], &log);
+    let ids = |harness: &TestHarness| items(harness).into_iter().map(|(id, ..)| id);
+    let before: Vec<u32> = ids(&harness).collect();
+
+    set_names(&mut harness, &["b", "a", "a"]);
+    let moved: Vec<u32> = ids(&harness).collect();
+    assert_eq!(moved, [before[2], before[0], before[1]]);
+
+    set_names(&mut harness, &["a", "b", "a", "a"]);
+    let after: Vec<u32> = ids(&harness).collect();
+    assert_eq!(after[..3], [before[0], before[2], before[1]]);
+    assert!(!before.contains(&after[3]));
+}
+
+#[test]
+fn removed_elements_are_unmounted_and_moved_ones_stay_mounted() {
+    let log = Log::default();
+    let mut harness = keyed_for(&["a", "b", "c"], &log);
+    let before = items(&harness);
+
+    set_names(&mut harness, &["c", "a"]);
+    let after = items(&harness);
+
+    assert_eq!(
+        after
+            .iter()
+            .map(|&(id, name, _)| (id, name))
+            .collect::<Vec<_>>(),
+        [(before[2].0, "c"), (before[0].0, "a")]
+    );
+    let mut mounted: Vec<_> = harness
+        .get_nodes_by_id("item")
+        .iter()
+        .map(|node| node.id_chain[0])
+        .collect();
+    mounted.sort();
+    let mut expected = vec![before[0].0, before[2].0];
+    expected.sort();
+    assert_eq!(mounted, expected);
+}
+
+#[test]
+fn moved_items_are_laid_out_in_their_new_cells() {
+    let log = Log::default();
+    let mut harness = keyed_for_within(&["a", "b", "c"], &log, |repeat| {
+        FlexInstance::instantiate(InstantiationArgs {
+            prototypical_common_properties_factory: Box::new(|| {
+                Rc::new(RefCell::new(CommonProperties::default()))
+            }),
+            prototypical_properties_factory: Box::new(|| Rc::new(RefCell::new(Flex::default()))),
+            handler_registry: None,
+            children: Some(RefCell::new(vec![repeat])),
+            component_template: None,
+            compute_properties_fn: None,
+            timeline: None,
+            template_node_identifier: None,
+        })
+    });
+    let x_by_name = |harness: &TestHarness| {
+        let list = harness.get_node_by_id("list");
+        let children = list.children.borrow();
+        items(harness)
+            .into_iter()
+            .zip(children.iter())
+            .map(|((_, name, _), node)| {
+                let layout = node.layout_properties.borrow();
+                let [.., x, _] = layout.as_ref().unwrap().computed_tab.transform.coeffs();
+                (name, x)
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(x_by_name(&harness), [("a", 0.0), ("b", 10.0), ("c", 20.0)]);
+
+    set_names(&mut harness, &["c", "a", "b"]);
+
+    assert_eq!(x_by_name(&harness), [("c", 0.0), ("a", 10.0), ("b", 20.0)]);
+}
@@ -0,0 +1,128 @@
+mod common;
+
+use common::{engine, Log, ProbeSpec};
+use kurbo::{Rect, Shape};
+use pax_headless::{RasterRenderContext, RasterRenderer, TestHarness};
+use pax_runtime::api::{Numeric, PropertyLiteral, RenderContext};
+use pax_runtime::Renderer;
+use piet::{Color, LinearGradient, UnitPoint};
+
+trait Translucent {
+    fn opacity(self, opacity: f64) -> Self;
+}
+
+impl Translucent for ProbeSpec {
+    fn opacity(self, opacity: f64) -> Self {
+        self.common_properties(move |common| {
+            common.opacity = Some(Box::new(PropertyLiteral::new(Numeric::from(opacity))))
+        })
+    }
+}
+
+#[test]
+fn save_layer_fades_draws_until_restored() {
+    let mut renderer = Renderer::new();
+    renderer.add_context("0", RasterRenderContext::new(10, 10));
+    let square = |x: f64| Rect::new(x, 0.0, x + 2.0, 2.0).to_path(0.1);
+    renderer.save_layer("0", 0.5);
+    renderer.fill("0", square(0.0), &Color::BLACK.into());
+    renderer.save_layer("0", 0.5);
+    renderer.fill("0", square(3.0), &Color::BLACK.into());
+    renderer.restore("0");
+    renderer.restore("0");
+    renderer.fill("0", square(6.0), &Color::BLACK.into());
+
+    let alpha = |x| renderer.backends["0"].pixmap().pixel(x, 1).unwrap().alpha();
+    assert_eq!(alpha(1), 128);
+    assert_eq!(alpha(4), 64);
+    assert_eq!(alpha(7), 255);
+}
+
+#[test]
+fn save_layer_fades_unit_space_gradients() {
+    let mut renderer = Renderer::new();
+    renderer.add_context("0", RasterRenderContext::new(10, 10));
+    let gradient = LinearGradient::new(
+        UnitPoint::LEFT,
+        UnitPoint::RIGHT,
+        (Color::BLACK, Color::BLACK),
+    );
+    renderer.save_layer("0", 0.5);
+    renderer.fill(
+        "0",
+        Rect::new(0.0, 0.0, 4.0, 2.0).to_path(0.1),
+        &gradient.into(),
+    );
+    renderer.restore("0");
+
+    let alpha = |x| renderer.backends["0"].pixmap().pixel(x, 1).unwrap().alpha();
+    assert_eq!(alpha(1), 128);
+    assert_eq!(alpha(3), 128);
+}
+
+#[test]
+fn save_layer_fades_images_until_restored() {
+    let mut renderer = Renderer::new().with_layer_compositing();
+    renderer.add_context("0", RasterRenderContext::new(10, 10));
+    renderer.load_image("black", &[0, 0, 0, 255].repeat(4), 2, 2);
+    let square = |x: f64| Rect::new(x, 0.0, x + 2.0, 2.0);
+    renderer.save_layer("0", 0.5);
+    renderer.draw_image("0", "black", square(0.0));
+    renderer.restore("0");
+    renderer.draw_image("0", "black", square(3.0));
+
+    let alpha = |x| renderer.backends["0"].pixmap().pixel(x, 1).unwrap().alpha();
+    assert_eq!(alpha(1), 128);
+    assert_eq!(alpha(4), 255);
+}
+
+#[test]
+fn opacity_composites_subtrees() {
+    let log = Log::default();
+    let mut harness = TestHarness::new(engine(
+        vec![
+            ProbeSpec::new("group", 0.0, 0.0, 40.0, 10.0)
+                .opacity(0.5)
+                .children(vec![
+                    ProbeSpec::new("half", 0.0, 0.0, 10.0, 10.0),
+                    ProbeSpec::new("quarter", 20.0, 0.0, 10.0, 10.0).opacity(0.5),
+                ]),
+            ProbeSpec::new("opaque", 0.0, 20.0, 10.0, 10.0),
+        ],
+        &log,
+    ));
+    assert_eq!(
+        harness.get_node_by_id("quarter").get_computed_opacity(),
+        0.25
+    );
+
+    let mut renderer = RasterRenderer::new(40, 40);
+    harness.render(&mut renderer);
+    let composite = renderer.composite();
+    let alpha = |x, y| composite.pixel(x, y).unwrap().alpha();
+    assert_eq!(alpha(5, 5), 128);
+    assert_eq!(alpha(25, 5), 64);
+    assert_eq!(alpha(5, 25), 255);
+}
+
+#[test]
+fn overlapping_children_of_a_translucent_group_do_not_show_through() {
+    let log = Log::default();
+    let mut harness = TestHarness::new(engine(
+        vec![ProbeSpec::new("group", 0.0, 0.0, 40.0, 10.0)
+            .opacity(0.5)
+            .children(vec![
+                ProbeSpec::new("left", 0.0, 0.0, 20.0, 10.0),
+                ProbeSpec::new("right", 10.0, 0.0, 20.0, 10.0),
+            ])],
+        &log,
+    ));
+
+    let mut renderer = RasterRenderer::new(40, 40);
+    harness.render(&mut renderer);
+    let composite = renderer.composite();
+    let alpha = |x| composite.pixel(x, 5).unwrap().alpha();
+    assert_eq!(alpha(5), 128);
+    assert_eq!(alpha(15), alpha(5));
+    assert_eq!(alpha(25), alpha(5));
+}
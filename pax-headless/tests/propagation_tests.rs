@@ -0,0 +1,91 @@
+mod common;
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use common::{engine, on_click, on_click_capture, Log, ProbeSpec};
+use pax_headless::TestHarness;
+use pax_runtime::api::{Click, Event, NodeContext};
+use pax_runtime::constants::{CAPTURE_SUFFIX, CLICK_HANDLERS};
+
+fn stop_click(properties: Rc<RefCell<dyn Any>>, ctx: &NodeContext, args: Option<Box<dyn Any>>) {
+    let event = args.unwrap().downcast::<Event<Click>>().unwrap();
+    event.stop_propagation();
+    on_click(properties, ctx, Some(event));
+}
+
+fn stop_click_capture(
+    properties: Rc<RefCell<dyn Any>>,
+    ctx: &NodeContext,
+    args: Option<Box<dyn Any>>,
+) {
+    let event = args.unwrap().downcast::<Event<Click>>().unwrap();
+    event.stop_propagation();
+    on_click_capture(properties, ctx, Some(event));
+}
+
+/// Makes `probe` record its clicks, in both the capture and bubble phases
+fn recording_clicks(probe: ProbeSpec) -> ProbeSpec {
+    probe.on(CLICK_HANDLERS, on_click).on(
+        format!("{}{}", CLICK_HANDLERS, CAPTURE_SUFFIX),
+        on_click_capture,
+    )
+}
+
+/// Makes `probe`'s click handlers (both capture and bubble) stop propagation
+fn stopping_propagation(probe: ProbeSpec) -> ProbeSpec {
+    probe.on(CLICK_HANDLERS, stop_click).on(
+        format!("{}{}", CLICK_HANDLERS, CAPTURE_SUFFIX),
+        stop_click_capture,
+    )
+}
+
+fn nested(overlay: ProbeSpec, log: &Log) -> TestHarness {
+    TestHarness::new(engine(
+        vec![overlay.children(vec![recording_clicks(ProbeSpec::new(
+            "button", 10.0, 10.0, 20.0, 20.0,
+        ))])],
+        log,
+    ))
+}
+
+#[test]
+fn capture_runs_root_to_target_then_bubbles_back() {
+    let log = Log::default();
+    let mut harness = nested(
+        recording_clicks(ProbeSpec::new("overlay", 0.0, 0.0, 100.0, 100.0)),
+        &log,
+    );
+    harness.click(20.0, 20.0);
+    assert_eq!(
+        *log.borrow(),
+        vec![
+            "click_capture overlay -> button",
+            "click_capture button -> button",
+            "click button (20, 20)",
+            "click overlay (20, 20)",
+        ]
+    );
+}
+
+#[test]
+fn stop_propagation_in_capture_phase_hides_event_from_children() {
+    let log = Log::default();
+    let mut harness = nested(
+        stopping_propagation(ProbeSpec::new("overlay", 0.0, 0.0, 100.0, 100.0)),
+        &log,
+    );
+    harness.click(20.0, 20.0);
+    assert_eq!(*log.borrow(), vec!["click_capture overlay -> button"]);
+}
+
+#[test]
+fn stop_propagation_does_not_prevent_default() {
+    let log = Log::default();
+    let mut harness = nested(
+        stopping_propagation(ProbeSpec::new("overlay", 0.0, 0.0, 100.0, 100.0)),
+        &log,
+    );
+    assert!(!harness.click(20.0, 20.0));
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use common::engine_with_template;
+use kurbo::{Affine, Rect};
+use pax_headless::{RasterRenderContext, RasterRenderer, TestHarness};
+use pax_runtime::api::{Color as PaxColor, CommonProperties, PropertyLiteral, Size};
+use pax_runtime::{InstanceNode, InstanceNodePtr, InstantiationArgs};
+use pax_std::primitives::Rectangle;
+use pax_std::types::Fill;
+use pax_std_primitives::rectangle::RectangleInstance;
+use piet::{Color, RenderContext};
+use tiny_skia::Pixmap;
+
+#[test]
+fn fill_respects_transform_and_clip() {
+    let mut rc = RasterRenderContext::new(20, 20);
+    rc.save().unwrap();
+    rc.clip(Rect::new(0.0, 0.0, 10.0, 20.0));
+    rc.transform(Affine::translate((5.0, 0.0)));
+    rc.fill(Rect::new(0.0, 0.0, 10.0, 10.0), &Color::rgb8(255, 0, 0));
+    rc.restore().unwrap();
+
+    let pixel = |x, y| rc.pixmap().pixel(x, y).unwrap();
+    // inside both the translated rect and the clip
+    assert_eq!(pixel(7, 5).red(), 255);
+    assert_eq!(pixel(7, 5).alpha(), 255);
+    // left of the translated rect
+    assert_eq!(pixel(2, 5).alpha(), 0);
+    // inside the translated rect, but clipped away
+    assert_eq!(pixel(12, 5).alpha(), 0);
+}
+
+/// A 10x10 rectangle at (`x`, `y`), filled with `color`
+fn rectangle(x: f64, y: f64, color: PaxColor) -> InstanceNodePtr {
+    RectangleInstance::instantiate(InstantiationArgs {
+        prototypical_common_properties_factory: Box::new(move || {
+            Rc::new(RefCell::new(CommonProperties {
+                x: Some(Box::new(PropertyLiteral::new(Size::Pixels(x.into())))),
+                y: Some(Box::new(PropertyLiteral::new(Size::Pixels(y.into())))),
+                width: Box::new(PropertyLiteral::new(Size::Pixels(10.into()))),
+                height: Box::new(PropertyLiteral::new(Size::Pixels(10.into()))),
+                ..Default::default()
+            }))
+        }),
+        prototypical_properties_factory: Box::new(move || {
+            Rc::new(RefCell::new(Rectangle {
+                fill: Box::new(PropertyLiteral::new(Fill::Solid(color.clone()))),
+                ..Default::default()
+            }))
+        }),
+        handler_registry: None,
+        children: None,
+        component_template: None,
+        compute_properties_fn: None,
+        timeline: None,
+        template_node_identifier: None,
+    })
+}
+
+#[test]
+fn engine_renders_to_png() {
+    let mut harness = TestHarness::new(engine_with_template(
+        vec![
+            rectangle(0.0, 0.0, PaxColor::rgb(255.into(), 0.into(), 0.into())),
+            rectangle(20.0, 10.0, PaxColor::rgb(0.into(), 0.into(), 255.into())),
+        ],
+        None,
+    ));
+    let mut renderer = RasterRenderer::new(40, 30);
+    harness.render(&mut renderer);
+    let png = renderer.encode_png().unwrap();
+    assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+
+    let decoded = Pixmap::decode_png(&png).unwrap();
+    assert_eq!((decoded.width(), decoded.height()), (40, 30));
+    let rgba = |x, y| {
+        let pixel = decoded.pixel(x, y).unwrap();
+        (pixel.red(), pixel.green(), pixel.blue(), pixel.alpha())
+    };
+    assert_eq!(rgba(5, 5), (255, 0, 0, 255));
+    assert_eq!(rgba(25, 15), (0, 0, 255, 255));
+    assert_eq!(rgba(35, 25).3, 0);
+}
@@ -0,0 +1,187 @@
+mod common;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use common::engine_with_template;
+use kurbo::{BezPath, Circle, Rect, Shape};
+use pax_headless::{RasterRenderContext, RasterRenderer, TestHarness};
+use pax_runtime::api::{Color, CommonProperties, PropertyLiteral, RenderContext, Size};
+use pax_runtime::{InstanceNode, InstanceNodePtr, InstantiationArgs, Renderer};
+use pax_std::primitives::Rectangle;
+use pax_std::types::{Fill, Shadow};
+use pax_std_primitives::rectangle::RectangleInstance;
+
+fn renderer(width: u32, height: u32) -> Renderer<RasterRenderContext> {
+    let mut renderer = Renderer::new();
+    renderer.add_context("0", RasterRenderContext::new(width, height));
+    renderer
+}
+
+fn native_blur_renderer(width: u32, height: u32) -> Renderer<RasterRenderContext> {
+    let mut renderer = Renderer::new().with_native_blur();
+    renderer.add_context("0", RasterRenderContext::new(width, height));
+    renderer
+}
+
+fn alpha_at(renderer: &Renderer<RasterRenderContext>, x: u32, y: u32) -> u8 {
+    renderer.backends["0"].pixmap().pixel(x, y).unwrap().alpha()
+}
+
+#[test]
+fn fill_blurred_softens_rectangle_edges() {
+    let mut renderer = renderer(60, 20);
+    let rect = Rect::new(20.0, 0.0, 40.0, 20.0).to_path(0.1);
+    renderer.fill_blurred("0", rect, &piet::Color::BLACK.into(), 3.0);
+
+    let alpha = |x| alpha_at(&renderer, x, 10);
+    assert_eq!(alpha(30), 255);
+    assert!((96..=160).contains(&alpha(20)));
+    assert_eq!(alpha(5), 0);
+    assert!(alpha(17) > alpha(14) && alpha(17) < alpha(20));
+}
+
+#[test]
+fn fill_blurred_spreads_edges_by_the_standard_deviation() {
+    let mut renderer = renderer(100, 60);
+    let rect = Rect::new(40.0, -20.0, 200.0, 80.0).to_path(0.1);
+    renderer.fill_blurred("0", rect, &piet::Color::BLACK.into(), 4.5);
+
+    // As for a gaussian blur with a standard deviation of 4.5px, as in CSS and SVG, pixels
+    // centered one standard deviation either side of the edge are 16% and 84% covered
+    let alpha = |x| alpha_at(&renderer, x, 30) as f64;
+    assert!((alpha(35) - 0.159 * 255.0).abs() <= 8.0);
+    assert!((alpha(44) - 0.841 * 255.0).abs() <= 8.0);
+}
+
+#[test]
+fn fill_blurred_softens_other_shapes_edges() {
+    let mut renderer = renderer(60, 60);
+    let circle = Circle::new((30.0, 30.0), 15.0).to_path(0.1);
+    renderer.fill_blurred("0", circle, &piet::Color::BLACK.into(), 3.0);
+
+    let alpha = |x| alpha_at(&renderer, x, 30);
+    assert_eq!(alpha(30), 255);
+    assert!((64..=192).contains(&alpha(45)));
+    assert_eq!(alpha(55), 0);
+    assert!(alpha(42) > alpha(45) && alpha(45) > alpha(48));
+}
+
+#[test]
+fn native_blur_spreads_concave_shapes_inner_edges_by_the_standard_deviation() {
+    let mut renderer = native_blur_renderer(60, 60);
+    // an L whose vertical arm's inner edge is at x = 20
+    let mut l = BezPath::new();
+    l.move_to((10.0, 10.0));
+    l.line_to((50.0, 10.0));
+    l.line_to((50.0, 20.0));
+    l.line_to((20.0, 20.0));
+    l.line_to((20.0, 50.0));
+    l.line_to((10.0, 50.0));
+    l.close_path();
+    renderer.fill_blurred("0", l, &piet::Color::BLACK.into(), 2.5);
+
+    // As for `fill_blurred_spreads_edges_by_the_standard_deviation`, on either side of the
+    // inner edge, well away from the corner
+    let alpha = |x| alpha_at(&renderer, x, 40) as f64;
+    assert!((alpha(17) - 0.841 * 255.0).abs() <= 8.0);
+    assert!((alpha(22) - 0.159 * 255.0).abs() <= 8.0);
+    assert_eq!(alpha_at(&renderer, 40, 40), 0);
+}
+
+#[test]
+fn stroke_blurred_softens_both_sides_of_the_stroke() {
+    let mut renderer = renderer(60, 20);
+    let line = kurbo::Line::new((30.0, 0.0), (30.0, 20.0)).to_path(0.1);
+    renderer.stroke_blurred("0", line, &piet::Color::BLACK.into(), 8.0, 2.0);
+
+    let alpha = |x| alpha_at(&renderer, x, 10);
+    assert_eq!(alpha(30), 255);
+    assert!(alpha(34) > 0 && alpha(34) < 255);
+    assert!(alpha(25) > 0 && alpha(25) < 255);
+    assert_eq!(alpha(40), 0);
+    assert_eq!(alpha(20), 0);
+}
+
+/// A white 20x20 rectangle at (20, 20) casting `shadow`
+fn rectangle(shadow: Vec<Shadow>) -> InstanceNodePtr {
+    RectangleInstance::instantiate(InstantiationArgs {
+        prototypical_common_properties_factory: Box::new(|| {
+            Rc::new(RefCell::new(CommonProperties {
+                x: Some(Box::new(PropertyLiteral::new(Size::Pixels(20.into())))),
+                y: Some(Box::new(PropertyLiteral::new(Size::Pixels(20.into())))),
+                width: Box::new(PropertyLiteral::new(Size::Pixels(20.into()))),
+                height: Box::new(PropertyLiteral::new(Size::Pixels(20.into()))),
+                ..Default::default()
+            }))
+        }),
+        prototypical_properties_factory: Box::new(move || {
+            Rc::new(RefCell::new(Rectangle {
+                fill: Box::new(PropertyLiteral::new(Fill::Solid(Color::WHITE))),
+                shadow: Box::new(PropertyLiteral::new(shadow.clone())),
+                ..Default::default()
+            }))
+        }),
+        handler_registry: None,
+        children: None,
+        component_template: None,
+        compute_properties_fn: None,
+        timeline: None,
+        template_node_identifier: None,
+    })
+}
+
+fn render(rectangle: InstanceNodePtr) -> tiny_skia::Pixmap {
+    let mut harness = TestHarness::new(engine_with_template(vec![rectangle], None));
+    let mut renderer = RasterRenderer::new(60, 60);
+    harness.render(&mut renderer);
+    renderer.composite()
+}
+
+#[test]
+fn shadows_are_drawn_beneath_the_shape() {
+    let composite = render(rectangle(vec![
+        Shadow::new(
+            Size::Pixels(10.into()),
+            Size::Pixels(10.into()),
+            0.into(),
+            0.into(),
+            Color::BLACK,
+        ),
+        Shadow::new(
+            Size::Pixels(0.into()),
+            Size::Pixels(0.into()),
+            0.into(),
+            5.into(),
+            Color::RED,
+        ),
+    ]));
+    let rgba = |x, y| {
+        let pixel = composite.pixel(x, y).unwrap();
+        (pixel.red(), pixel.green(), pixel.blue(), pixel.alpha())
+    };
+
+    assert_eq!(rgba(30, 30), (255, 255, 255, 255));
+    // the offset shadow
+    assert_eq!(rgba(45, 45), (0, 0, 0, 255));
+    // the spread shadow, beneath the offset one where they overlap
+    assert_eq!(rgba(17, 30).3, 255);
+    assert!(rgba(17, 30).0 > 200 && rgba(17, 30).1 < 100);
+    assert_eq!(rgba(42, 42), (0, 0, 0, 255));
+    assert_eq!(rgba(10, 10).3, 0);
+}
+
+#[test]
+fn blurred_shadows_fade_out() {
+    let composite = render(rectangle(vec![Shadow::new(
+        Size::Pixels(0.into()),
+        Size::Pixels(10.into()),
+        4.into(),
+        0.into(),
+        Color::BLACK,
+    )]));
+    let alpha = |y| composite.pixel(30, y).unwrap().alpha();
+    assert_eq!(alpha(30), 255);
+    assert!(alpha(44) > alpha(48) && alpha(48) > alpha(52));
+    assert_eq!(alpha(59), 0);
+}
@@ -0,0 +1,169 @@
+mod common;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use common::{engine, engine_with_template, Log, ProbeSpec};
+use pax_headless::TestHarness;
+use pax_runtime::api::{CommonProperties, PropertyLiteral, Size};
+use pax_runtime::{InstanceNode, InstanceNodePtr, InstantiationArgs, TransformAndBounds};
+use pax_std::primitives::{Flex, Group};
+use pax_std::types::FlexItem;
+use pax_std_primitives::flex::FlexInstance;
+use pax_std_primitives::group::GroupInstance;
+
+fn px(value: f64) -> Size {
+    Size::Pixels(value.into())
+}
+
+trait Limited {
+    /// Sets this probe's `min_width` and `min_height`, in pixels
+    fn min_size(self, width: f64, height: f64) -> Self;
+    /// Sets this probe's `max_width` and `max_height`, in pixels
+    fn max_size(self, width: f64, height: f64) -> Self;
+    fn padding(self, padding: f64) -> Self;
+}
+
+impl Limited for ProbeSpec {
+    fn min_size(self, width: f64, height: f64) -> Self {
+        self.common_properties(move |common| {
+            common.min_width = Some(Box::new(PropertyLiteral::new(px(width))));
+            common.min_height = Some(Box::new(PropertyLiteral::new(px(height))));
+        })
+    }
+
+    fn max_size(self, width: f64, height: f64) -> Self {
+        self.common_properties(move |common| {
+            common.max_width = Some(Box::new(PropertyLiteral::new(px(width))));
+            common.max_height = Some(Box::new(PropertyLiteral::new(px(height))));
+        })
+    }
+
+    fn padding(self, padding: f64) -> Self {
+        self.common_properties(move |common| {
+            common.padding = Some(Box::new(PropertyLiteral::new(px(padding))))
+        })
+    }
+}
+
+/// `tab`'s position and size
+fn rect(tab: &TransformAndBounds) -> (f64, f64, f64, f64) {
+    let [.., x, y] = tab.transform.coeffs();
+    (x, y, tab.bounds.0, tab.bounds.1)
+}
+
+/// Instantiates a `T` with the properties `properties` returns, sized `width` by `height` and
+/// padded by `padding`
+fn instantiate<T: InstanceNode + 'static, P: 'static>(
+    (width, height): (Size, Size),
+    padding: f64,
+    properties: impl Fn() -> P + 'static,
+    children: Vec<InstanceNodePtr>,
+) -> InstanceNodePtr {
+    T::instantiate(InstantiationArgs {
+        prototypical_common_properties_factory: Box::new(move || {
+            Rc::new(RefCell::new(CommonProperties {
+                id: Some(Box::new(PropertyLiteral::new("container".to_string()))),
+                width: Box::new(PropertyLiteral::new(width)),
+                height: Box::new(PropertyLiteral::new(height)),
+                padding: Some(Box::new(PropertyLiteral::new(px(padding)))),
+                ..Default::default()
+            }))
+        }),
+        prototypical_properties_factory: Box::new(move || Rc::new(RefCell::new(properties()))),
+        handler_registry: None,
+        children: Some(RefCell::new(children)),
+        component_template: None,
+        compute_properties_fn: None,
+        timeline: None,
+        template_node_identifier: None,
+    })
+}
+
+#[test]
+fn sizes_are_kept_within_their_limits() {
+    let log = Log::default();
+    let harness = TestHarness::new(engine(
+        vec![
+            ProbeSpec::new("wide", 0.0, 0.0, 500.0, 10.0)
+                .max_size(300.0, 100.0)
+                .min_size(0.0, 50.0),
+            // Where the limits conflict, the minimum wins
+            ProbeSpec::new("conflicting", 0.0, 0.0, 10.0, 10.0)
+                .min_size(40.0, 0.0)
+                .max_size(20.0, 100.0),
+        ],
+        &log,
+    ));
+
+    assert_eq!(harness.computed_tab("wide").bounds, (300.0, 50.0));
+    assert_eq!(harness.computed_tab("conflicting").bounds, (40.0, 10.0));
+}
+
+#[test]
+fn children_are_laid_out_inside_the_padding() {
+    let log = Log::default();
+    let harness = TestHarness::new(engine(
+        vec![ProbeSpec::new("panel", 20.0, 30.0, 100.0, 80.0)
+            .padding(10.0)
+            .children(vec![ProbeSpec::new("child", 5.0, 5.0, 20.0, 20.0)])],
+        &log,
+    ));
+
+    assert_eq!(
+        rect(&harness.computed_tab("panel")),
+        (20.0, 30.0, 100.0, 80.0)
+    );
+    assert_eq!(
+        rect(&harness.computed_tab("child")),
+        (35.0, 45.0, 20.0, 20.0)
+    );
+}
+
+#[test]
+fn flex_grows_items_no_further_than_their_limits() {
+    let log = Log::default();
+    let children = vec![
+        ProbeSpec::new("a", 0.0, 0.0, 50.0, 20.0)
+            .max_size(100.0, 100.0)
+            .instantiate(&log),
+        ProbeSpec::new("b", 0.0, 0.0, 50.0, 20.0).instantiate(&log),
+    ];
+    let growing = || FlexItem {
+        grow: 1.into(),
+        ..Default::default()
+    };
+    let harness = TestHarness::new(engine_with_template(
+        vec![instantiate::<FlexInstance, _>(
+            (px(320.0), px(100.0)),
+            10.0,
+            move || Flex {
+                items: Box::new(PropertyLiteral::new(vec![growing(), growing()])),
+                ..Default::default()
+            },
+            children,
+        )],
+        None,
+    ));
+
+    // The items share the 300px inside the padding, but `a` grows no wider than 100px
+    assert_eq!(rect(&harness.computed_tab("a")), (10.0, 10.0, 100.0, 80.0));
+    assert_eq!(rect(&harness.computed_tab("b")), (110.0, 10.0, 150.0, 80.0));
+}
+
+#[test]
+fn auto_sized_containers_include_their_padding() {
+    let log = Log::default();
+    let children = vec![ProbeSpec::new("child", 0.0, 0.0, 100.0, 50.0).instantiate(&log)];
+    let harness = TestHarness::new(engine_with_template(
+        vec![instantiate::<GroupInstance, _>(
+            (Size::Auto, Size::Auto),
+            10.0,
+            Group::default,
+            children,
+        )],
+        None,
+    ));
+
+    assert_eq!(harness.computed_tab("container").bounds, (120.0, 70.0));
+}
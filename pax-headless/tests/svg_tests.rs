@@ -0,0 +1,67 @@
+use kurbo::{Affine, Rect, Shape};
+use pax_headless::SvgRenderContext;
+use pax_runtime::{LayerCompositing, NativeBlur};
+use piet::{Color, LinearGradient, RenderContext, UnitPoint};
+
+#[test]
+fn gradients_and_clips_are_preserved() {
+    let mut rc = SvgRenderContext::new("layer-0");
+    rc.save().unwrap();
+    rc.clip(Rect::new(0.0, 0.0, 10.0, 10.0));
+    let gradient = LinearGradient::new(
+        UnitPoint::LEFT,
+        UnitPoint::RIGHT,
+        (Color::rgb8(255, 0, 0), Color::rgb8(0, 0, 255)),
+    );
+    rc.fill(Rect::new(0.0, 0.0, 20.0, 10.0), &gradient);
+    rc.restore().unwrap();
+
+    let mut svg = String::new();
+    rc.write_layer(&mut svg);
+    assert!(svg.starts_with(r#"<g id="layer-0">"#));
+    assert!(svg.contains(
+        r#"<linearGradient id="layer-0-gradient2" gradientUnits="userSpaceOnUse" x1="0" y1="5" x2="20" y2="5">"#
+    ));
+    assert!(svg.contains(r#"<g clip-path="url(#layer-0-clip1)">"#));
+    assert!(svg.contains(r#"fill="url(#layer-0-gradient2)""#));
+    // clip group and layer group are both closed
+    assert_eq!(svg.matches("<g ").count(), svg.matches("</g>").count());
+}
+
+#[test]
+fn layers_are_translucent_groups() {
+    let mut rc = SvgRenderContext::new("layer-0");
+    rc.save().unwrap();
+    rc.push_layer(0.5);
+    rc.clip(Rect::new(0.0, 0.0, 10.0, 10.0));
+    rc.fill(Rect::new(0.0, 0.0, 20.0, 10.0), &Color::BLACK);
+    rc.pop_layer();
+    rc.restore().unwrap();
+    rc.fill(Rect::new(0.0, 0.0, 20.0, 10.0), &Color::BLACK);
+
+    let mut svg = String::new();
+    rc.write_layer(&mut svg);
+    assert!(svg.contains("<g opacity=\"0.5\">\n<g clip-path=\"url(#layer-0-clip1)\">\n<path"));
+    // the layer's clip group is closed along with the layer, before the last fill
+    assert!(svg.contains("</g>\n</g>\n<path"));
+    assert_eq!(svg.matches("<g ").count(), svg.matches("</g>").count());
+}
+
+#[test]
+fn blurred_shapes_are_filtered_groups() {
+    let mut rc = SvgRenderContext::new("layer-0");
+    rc.transform(Affine::translate((5.0, 0.0)));
+    let square = Rect::new(5.0, 5.0, 15.0, 15.0).to_path(0.1);
+    NativeBlur::fill_blurred(&mut rc, square, Color::BLACK, 2.0);
+
+    let mut svg = String::new();
+    rc.write_layer(&mut svg);
+    assert!(svg.contains(
+        r#"<filter id="layer-0-blur1" filterUnits="userSpaceOnUse" x="-1" y="-1" width="22" height="22"><feGaussianBlur stdDeviation="2"/></filter>"#
+    ));
+    // the group is transformed, so that the blur is too
+    assert!(
+        svg.contains("<g filter=\"url(#layer-0-blur1)\" transform=\"matrix(1 0 0 1 5 0)\">\n<path")
+    );
+    assert_eq!(svg.matches("<g ").count(), svg.matches("</g>").count());
+}
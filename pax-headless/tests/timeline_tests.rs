@@ -0,0 +1,156 @@
+mod common;
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use common::{engine_with_timeline, on_click, Log, ProbeSpec};
+use pax_headless::TestHarness;
+use pax_runtime::api::{EasingCurve, Keyframe, NodeContext, PropertyTimeline, Size, Timeline};
+use pax_runtime::constants::CLICK_HANDLERS;
+
+/// Plays the containing component's timeline if paused, and pauses it if playing
+fn toggle_timeline(
+    properties: Rc<RefCell<dyn Any>>,
+    ctx: &NodeContext,
+    args: Option<Box<dyn Any>>,
+) {
+    if ctx.is_timeline_playing() {
+        ctx.pause_timeline();
+    } else {
+        ctx.play_timeline();
+    }
+    on_click(properties, ctx, args);
+}
+
+trait Animated {
+    /// Animates this probe's `x` along its containing component's timeline
+    fn x_keyframes(self, keyframes: Vec<Keyframe<Size>>) -> Self;
+}
+
+impl Animated for ProbeSpec {
+    fn x_keyframes(self, keyframes: Vec<Keyframe<Size>>) -> Self {
+        self.common_properties(move |common| {
+            common.x = Some(Box::new(PropertyTimeline::new(keyframes.clone())))
+        })
+    }
+}
+
+fn keyframe(frame: usize, x: f64, curve: EasingCurve) -> Keyframe<Size> {
+    Keyframe::new(frame, Size::Pixels(x.into()), curve)
+}
+
+fn harness(log: &Log, probe: ProbeSpec, timeline: Timeline) -> TestHarness {
+    TestHarness::new(engine_with_timeline(vec![probe], log, Some(timeline)))
+}
+
+fn timeline(frame_count: usize, is_playing: bool, is_looping: bool) -> Timeline {
+    Timeline {
+        playhead_position: 0,
+        frame_count,
+        is_playing,
+        is_looping,
+    }
+}
+
+fn sliding_box() -> ProbeSpec {
+    ProbeSpec::new("box", 0.0, 0.0, 10.0, 10.0).x_keyframes(vec![
+        keyframe(0, 0.0, EasingCurve::Linear),
+        keyframe(10, 100.0, EasingCurve::Linear),
+    ])
+}
+
+fn x(harness: &TestHarness) -> f64 {
+    let node = harness.get_node_by_id("box");
+    let common_properties = node.get_common_properties();
+    let x = common_properties.borrow().x.as_ref().unwrap().get().clone();
+    match x {
+        Size::Pixels(x) => x.to_float(),
+        _ => unreachable!(),
+    }
+}
+
+fn playhead(harness: &TestHarness) -> usize {
+    let node = harness.get_node_by_id("box");
+    let component = node.containing_component.upgrade().unwrap();
+    let timeline = component.timeline.borrow().clone().unwrap();
+    let playhead = timeline.borrow().playhead_position;
+    playhead
+}
+
+#[test]
+fn keyframes_are_interpolated_along_the_playhead() {
+    let log = Log::default();
+    let mut harness = harness(&log, sliding_box(), timeline(10, true, false));
+    // each tick shows the frame at the playhead, then moves it forward
+    assert_eq!(x(&harness), 0.0);
+    assert_eq!(playhead(&harness), 1);
+    harness.advance(3);
+    assert_eq!(x(&harness), 30.0);
+}
+
+#[test]
+fn keyframes_ease_into_each_keyframe_with_its_curve() {
+    let log = Log::default();
+    let probe = ProbeSpec::new("box", 0.0, 0.0, 10.0, 10.0).x_keyframes(vec![
+        keyframe(0, 0.0, EasingCurve::Linear),
+        keyframe(10, 100.0, EasingCurve::InQuad),
+        keyframe(20, 0.0, EasingCurve::Linear),
+    ]);
+    let mut harness = harness(&log, probe, timeline(20, true, false));
+    harness.advance(5);
+    assert_eq!(x(&harness), 25.0);
+    harness.advance(10);
+    assert_eq!(x(&harness), 50.0);
+}
+
+#[test]
+fn finished_timelines_hold_their_last_frame() {
+    let log = Log::default();
+    let mut harness = harness(&log, sliding_box(), timeline(10, true, false));
+    harness.advance(20);
+    assert_eq!(playhead(&harness), 10);
+    assert_eq!(x(&harness), 100.0);
+}
+
+#[test]
+fn looping_timelines_wrap_around() {
+    let log = Log::default();
+    let mut harness = harness(&log, sliding_box(), timeline(10, true, true));
+    harness.advance(12);
+    assert_eq!(playhead(&harness), 3);
+    assert_eq!(x(&harness), 20.0);
+}
+
+#[test]
+fn paused_timelines_hold_until_played_from_a_handler() {
+    let log = Log::default();
+    let probe = sliding_box().on(CLICK_HANDLERS, toggle_timeline);
+    let mut harness = harness(&log, probe, timeline(10, false, false));
+    harness.advance(5);
+    assert_eq!(playhead(&harness), 0);
+    assert_eq!(x(&harness), 0.0);
+
+    harness.click_node("box");
+    harness.advance(5);
+    assert_eq!(playhead(&harness), 5);
+
+    harness.click_node("box");
+    harness.advance(5);
+    assert_eq!(playhead(&harness), 5);
+    assert_eq!(x(&harness), 50.0);
+}
+
+#[test]
+fn seeking_moves_keyframed_properties() {
+    let log = Log::default();
+    let mut harness = harness(&log, sliding_box(), timeline(10, false, false));
+    {
+        let node = harness.get_node_by_id("box");
+        let component = node.containing_component.upgrade().unwrap();
+        let timeline = component.timeline.borrow().clone().unwrap();
+        timeline.borrow_mut().seek(7);
+    }
+    harness.tick();
+    assert_eq!(x(&harness), 70.0);
+}
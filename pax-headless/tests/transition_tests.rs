@@ -0,0 +1,191 @@
+mod common;
+
+use std::time::Duration;
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use common::{engine, entries, record, Log, ProbeSpec};
+use pax_headless::TestHarness;
+use pax_runtime::api::{EasingCurve, Event, NodeContext, PropertyInstance, Size, TransitionEnd};
+use pax_runtime::constants::TRANSITION_END_HANDLERS;
+
+fn on_transition_end(
+    properties: Rc<RefCell<dyn Any>>,
+    _ctx: &NodeContext,
+    args: Option<Box<dyn Any>>,
+) {
+    let event = args.unwrap().downcast::<Event<TransitionEnd>>().unwrap();
+    record(properties, |name| {
+        format!("transition_end {} {}", name, event.property)
+    });
+}
+
+fn harness(log: &Log) -> TestHarness {
+    let probe =
+        ProbeSpec::new("box", 0.0, 0.0, 10.0, 10.0).on(TRANSITION_END_HANDLERS, on_transition_end);
+    TestHarness::new(engine(vec![probe], log))
+}
+
+fn ease_x(harness: &TestHarness, ease: impl FnOnce(&mut dyn PropertyInstance<Size>)) {
+    let node = harness.get_node_by_id("box");
+    let common_properties = node.get_common_properties();
+    let mut common_properties = common_properties.borrow_mut();
+    ease(common_properties.x.as_mut().unwrap().as_mut());
+}
+
+fn x(harness: &TestHarness) -> f64 {
+    let node = harness.get_node_by_id("box");
+    let common_properties = node.get_common_properties();
+    let x = common_properties.borrow().x.as_ref().unwrap().get().clone();
+    match x {
+        Size::Pixels(x) => x.to_float(),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn time_based_transitions_follow_the_clock_not_the_tick_rate() {
+    let log = Log::default();
+    let mut harness = harness(&log);
+    ease_x(&harness, |x| {
+        x.ease_to_ms(Size::Pixels(100.into()), 400, EasingCurve::Linear)
+    });
+    harness.tick_after(Duration::ZERO);
+    assert_eq!(x(&harness), 0.0);
+
+    harness.set_frame_interval(Duration::from_millis(10));
+    harness.advance(10);
+    assert_eq!(x(&harness), 25.0);
+
+    // a long stall finishes the transition without overshooting
+    harness.tick_after(Duration::from_secs(2));
+    assert_eq!(x(&harness), 100.0);
+}
+
+#[test]
+fn queued_time_based_transitions_start_where_the_previous_ended() {
+    let log = Log::default();
+    let mut harness = harness(&log);
+    ease_x(&harness, |x| {
+        x.ease_to_ms(Size::Pixels(100.into()), 100, EasingCurve::Linear);
+        x.ease_to_later_ms(Size::Pixels(200.into()), 100, EasingCurve::Linear);
+    });
+    harness.tick_after(Duration::ZERO);
+    harness.tick_after(Duration::from_millis(150));
+    assert_eq!(x(&harness), 150.0);
+}
+
+#[test]
+fn frame_based_transitions_ignore_the_clock() {
+    let log = Log::default();
+    let mut harness = harness(&log);
+    ease_x(&harness, |x| {
+        x.ease_to(Size::Pixels(100.into()), 4, EasingCurve::Linear)
+    });
+    harness.tick_after(Duration::from_secs(1));
+    assert_eq!(x(&harness), 25.0);
+}
+
+#[test]
+fn springs_run_until_settled() {
+    let log = Log::default();
+    let mut harness = harness(&log);
+    ease_x(&harness, |x| {
+        x.spring_to(Size::Pixels(100.into()), 100.0, 5.0, 1.0)
+    });
+    harness.tick_after(Duration::ZERO);
+    harness.set_frame_interval(Duration::from_millis(10));
+    let mut overshoot = 0.0_f64;
+    for _ in 0..100 {
+        harness.tick();
+        overshoot = overshoot.max(x(&harness));
+    }
+    assert!(overshoot > 100.0);
+
+    harness.tick_after(Duration::from_secs(10));
+    assert_eq!(x(&harness), 100.0);
+}
+
+#[test]
+fn retargeted_springs_keep_their_velocity() {
+    let log = Log::default();
+    let mut harness = harness(&log);
+    ease_x(&harness, |x| {
+        x.spring_to(Size::Pixels(100.into()), 170.0, 26.0, 1.0)
+    });
+    harness.tick_after(Duration::ZERO);
+    harness.set_frame_interval(Duration::from_millis(10));
+    harness.advance(10);
+    let before = x(&harness);
+    harness.tick();
+    let velocity_before = (x(&harness) - before) / 0.01;
+
+    ease_x(&harness, |x| {
+        x.spring_to(Size::Pixels(200.into()), 170.0, 26.0, 1.0)
+    });
+    let before = x(&harness);
+    harness.tick();
+    let velocity_after = (x(&harness) - before) / 0.01;
+    // the new, farther target pulls a little harder, but a spring starting from rest would
+    // only reach a fraction of the previous velocity within a frame
+    assert!(velocity_before > 100.0);
+    assert!(
+        (velocity_after - velocity_before).abs() < velocity_before * 0.2,
+        "{velocity_before} px/s before retargeting, {velocity_after} px/s after"
+    );
+}
+
+fn is_animating(harness: &TestHarness) -> bool {
+    let node = harness.get_node_by_id("box");
+    let common_properties = node.get_common_properties();
+    let is_animating = common_properties
+        .borrow()
+        .x
+        .as_ref()
+        .unwrap()
+        .is_animating();
+    is_animating
+}
+
+#[test]
+fn transition_end_fires_once_the_queue_drains() {
+    let log = Log::default();
+    let mut harness = harness(&log);
+    assert!(!is_animating(&harness));
+    ease_x(&harness, |x| {
+        x.ease_to(Size::Pixels(100.into()), 3, EasingCurve::Linear);
+        x.ease_to_later(Size::Pixels(200.into()), 2, EasingCurve::Linear);
+    });
+    assert!(is_animating(&harness));
+
+    // the first transition finishes on the third tick, and the second halfway through it
+    harness.advance(3);
+    assert!(is_animating(&harness));
+    assert!(entries(&log, "transition_end").is_empty());
+
+    harness.tick();
+    assert_eq!(x(&harness), 200.0);
+    assert!(!is_animating(&harness));
+    assert_eq!(entries(&log, "transition_end"), ["transition_end box x"]);
+
+    harness.advance(5);
+    assert_eq!(entries(&log, "transition_end").len(), 1);
+}
+
+#[test]
+fn interrupted_transitions_end_once() {
+    let log = Log::default();
+    let mut harness = harness(&log);
+    ease_x(&harness, |x| {
+        x.ease_to(Size::Pixels(100.into()), 4, EasingCurve::Linear)
+    });
+    harness.advance(2);
+    ease_x(&harness, |x| {
+        x.ease_to(Size::Pixels(0.into()), 2, EasingCurve::Linear)
+    });
+    harness.advance(2);
+    assert_eq!(x(&harness), 0.0);
+    assert_eq!(entries(&log, "transition_end"), ["transition_end box x"]);
+}
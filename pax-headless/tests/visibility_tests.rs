@@ -0,0 +1,85 @@
+mod common;
+
+use common::{engine, entries, on_click, Log, ProbeSpec};
+use pax_headless::{RasterRenderer, TestHarness};
+use pax_runtime::api::PropertyLiteral;
+use pax_runtime::constants::CLICK_HANDLERS;
+
+trait Visibility {
+    fn visible(self, visible: bool) -> Self;
+    fn pointer_events(self, pointer_events: bool) -> Self;
+}
+
+impl Visibility for ProbeSpec {
+    fn visible(self, visible: bool) -> Self {
+        self.common_properties(move |common| {
+            common.visible = Some(Box::new(PropertyLiteral::new(visible)))
+        })
+    }
+
+    fn pointer_events(self, pointer_events: bool) -> Self {
+        self.common_properties(move |common| {
+            common.pointer_events = Some(Box::new(PropertyLiteral::new(pointer_events)))
+        })
+    }
+}
+
+/// A probe that records its clicks
+fn probe(name: &'static str, x: f64, y: f64, width: f64, height: f64) -> ProbeSpec {
+    ProbeSpec::new(name, x, y, width, height).on(CLICK_HANDLERS, on_click)
+}
+
+#[test]
+fn hidden_subtrees_keep_layout_but_are_not_drawn_or_hit() {
+    let log = Log::default();
+    let mut harness = TestHarness::new(engine(
+        vec![
+            probe("panel", 20.0, 20.0, 30.0, 30.0)
+                .visible(false)
+                .children(vec![probe("content", 0.0, 0.0, 10.0, 10.0)]),
+            probe("backdrop", 0.0, 0.0, 40.0, 40.0),
+        ],
+        &log,
+    ));
+    assert_eq!(harness.computed_tab("content").bounds, (10.0, 10.0));
+    assert!(!harness.get_node_by_id("content").get_computed_visibility());
+
+    harness.click(25.0, 25.0);
+    assert_eq!(entries(&log, "click"), ["click backdrop (25, 25)"]);
+
+    // the panel would overhang the backdrop if it were drawn
+    let mut renderer = RasterRenderer::new(60, 60);
+    harness.render(&mut renderer);
+    let composite = renderer.composite();
+    let alpha = |x, y| composite.pixel(x, y).unwrap().alpha();
+    assert_eq!(alpha(25, 25), 255);
+    assert_eq!(alpha(45, 45), 0);
+}
+
+#[test]
+fn pointer_events_pass_through_to_nodes_beneath() {
+    let log = Log::default();
+    let mut harness = TestHarness::new(engine(
+        vec![
+            probe("overlay", 0.0, 0.0, 100.0, 100.0)
+                .pointer_events(false)
+                .children(vec![
+                    probe("decoration", 0.0, 0.0, 20.0, 20.0),
+                    probe("button", 50.0, 50.0, 20.0, 20.0).pointer_events(true),
+                ]),
+            probe("backdrop", 0.0, 0.0, 100.0, 100.0),
+        ],
+        &log,
+    ));
+
+    harness.click(10.0, 10.0);
+    assert_eq!(entries(&log, "click"), ["click backdrop (10, 10)"]);
+
+    // descendants inherit `pointer_events` unless they opt back in
+    log.borrow_mut().clear();
+    harness.click(60.0, 60.0);
+    assert_eq!(
+        entries(&log, "click"),
+        ["click button (60, 60)", "click overlay (60, 60)"]
+    );
+}
@@ -0,0 +1,122 @@
+mod common;
+
+use common::{engine, engine_with_template, entries, on_click, Log, ProbeSpec};
+use pax_headless::TestHarness;
+use pax_runtime::api::{CommonProperties, Numeric, PropertyLiteral};
+use pax_runtime::constants::CLICK_HANDLERS;
+use pax_runtime::{InstanceNode, InstanceNodePtr, InstantiationArgs};
+use pax_std::primitives::Group;
+use pax_std_primitives::group::GroupInstance;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+trait Stacked {
+    fn z_index(self, z_index: isize) -> Self;
+}
+
+impl Stacked for ProbeSpec {
+    fn z_index(self, z_index: isize) -> Self {
+        self.common_properties(move |common| {
+            common.z_index = Some(Box::new(PropertyLiteral::new(Numeric::from(z_index))))
+        })
+    }
+}
+
+/// A probe that records its clicks
+fn probe(name: &'static str, x: f64, y: f64, width: f64, height: f64) -> ProbeSpec {
+    ProbeSpec::new(name, x, y, width, height).on(CLICK_HANDLERS, on_click)
+}
+
+/// A `Group` filling the viewport, without a `z_index` of its own
+fn group(children: Vec<InstanceNodePtr>) -> InstanceNodePtr {
+    GroupInstance::instantiate(InstantiationArgs {
+        prototypical_common_properties_factory: Box::new(|| {
+            Rc::new(RefCell::new(CommonProperties::default()))
+        }),
+        prototypical_properties_factory: Box::new(|| Rc::new(RefCell::new(Group::default()))),
+        handler_registry: None,
+        children: Some(RefCell::new(children)),
+        component_template: None,
+        compute_properties_fn: None,
+        timeline: None,
+        template_node_identifier: None,
+    })
+}
+
+fn topmost(harness: &mut TestHarness, log: &Log, x: f64, y: f64) -> Vec<String> {
+    log.borrow_mut().clear();
+    harness.click(x, y);
+    entries(log, "click")
+}
+
+#[test]
+fn z_index_reorders_siblings() {
+    let log = Log::default();
+    let mut harness = TestHarness::new(engine(
+        vec![
+            probe("sunk", 0.0, 0.0, 20.0, 20.0).z_index(-1),
+            probe("content", 0.0, 0.0, 40.0, 40.0),
+            probe("popover", 10.0, 10.0, 40.0, 40.0).z_index(1),
+        ],
+        &log,
+    ));
+    // earlier siblings are otherwise on top of later ones
+    assert_eq!(
+        topmost(&mut harness, &log, 5.0, 5.0),
+        ["click content (5, 5)"]
+    );
+    assert_eq!(
+        topmost(&mut harness, &log, 15.0, 15.0),
+        ["click popover (15, 15)"]
+    );
+
+    // native elements and canvas layers are stacked in the same order
+    let stacking_order = &harness.engine().runtime_context.z_index_node_cache;
+    let position = |id| {
+        let node = harness.get_node_by_id(id);
+        stacking_order
+            .iter()
+            .position(|n| Rc::ptr_eq(n, &node))
+            .unwrap()
+    };
+    assert!(position("sunk") < position("content"));
+    assert!(position("content") < position("popover"));
+}
+
+#[test]
+fn z_index_does_not_escape_the_parent() {
+    let log = Log::default();
+    let mut harness = TestHarness::new(engine(
+        vec![
+            probe("sidebar", 0.0, 0.0, 100.0, 100.0)
+                .z_index(-1)
+                .children(vec![probe("tooltip", 0.0, 0.0, 50.0, 50.0).z_index(100)]),
+            probe("page", 0.0, 0.0, 100.0, 100.0),
+        ],
+        &log,
+    ));
+    assert_eq!(topmost(&mut harness, &log, 5.0, 5.0), ["click page (5, 5)"]);
+}
+
+#[test]
+fn z_index_escapes_groups_within_the_component() {
+    let log = Log::default();
+    let popover = probe("popover", 0.0, 0.0, 50.0, 50.0).z_index(10);
+    let mut harness = TestHarness::new(engine_with_template(
+        vec![
+            probe("page", 0.0, 0.0, 100.0, 100.0).instantiate(&log),
+            group(vec![group(vec![popover.instantiate(&log)])]),
+        ],
+        None,
+    ));
+    // `Group`s don't start stacking contexts, so the popover is raised above the page, which
+    // is otherwise on top of the later `Group`
+    assert_eq!(
+        topmost(&mut harness, &log, 5.0, 5.0),
+        ["click popover (5, 5)"]
+    );
+    assert_eq!(
+        topmost(&mut harness, &log, 75.0, 75.0),
+        ["click page (75, 75)"]
+    );
+}
@@ -288,6 +288,8 @@ lazy_static! {
             ("key_down", "Set Key Down event handler"),
             ("key_up", "Set Key Up event handler"),
             ("key_press", "Set Key Press event handler"),
+            ("focus", "Set Focus event handler"),
+            ("blur", "Set Blur event handler"),
             ("click", "Set Click event handler"),
             ("mouse_down", "Set Mouse Down event handler"),
             ("mouse_up", "Set Mouse Up event handler"),
@@ -297,6 +299,15 @@ lazy_static! {
             ("double_click", "Set Double Click event handler"),
             ("context_menu", "Set Context Menu event handler"),
             ("wheel", "Set Wheel event handler"),
+            ("drag_start", "Set Drag Start event handler"),
+            ("drag", "Set Drag event handler"),
+            ("drag_end", "Set Drag End event handler"),
+            ("pinch", "Set Pinch event handler"),
+            ("rotate_gesture", "Set Rotate Gesture event handler"),
+            ("pan", "Set Pan event handler"),
+            ("long_press", "Set Long Press event handler"),
+            ("swipe", "Set Swipe event handler"),
+            ("transition_end", "Set Transition End event handler"),
             ("tick", "Set Tick event handler"),
             ("pre_render", "Set Will Render event handler"),
             ("mount", "Set Did Mount event handler"),
@@ -361,6 +372,37 @@ pub fn get_block_declaration_completions() -> Vec<CompletionItem> {
     completions
 }
 
+pub fn get_control_flow_completions() -> Vec<CompletionItem> {
+    let statements = [
+        (
+            "if",
+            "Render nodes while a condition holds",
+            "if $1 {\n\t$0\n}",
+        ),
+        (
+            "else if",
+            "Render nodes when the conditions before are false and this one holds",
+            "else if $1 {\n\t$0\n}",
+        ),
+        (
+            "else",
+            "Render nodes when the conditions before are false",
+            "else {\n\t$0\n}",
+        ),
+    ];
+
+    statements
+        .iter()
+        .map(|(label, detail, snippet)| {
+            let mut completion = CompletionItem::new_simple(label.to_string(), detail.to_string());
+            completion.kind = Some(CompletionItemKind::KEYWORD);
+            completion.insert_text = Some(snippet.to_string());
+            completion.insert_text_format = Some(InsertTextFormat::SNIPPET);
+            completion
+        })
+        .collect()
+}
+
 pub fn get_root_component_methods(component: &PaxComponent) -> Vec<CompletionItem> {
     let mut completions = Vec::new();
     if let Some(c) = component.identifier_map.get(&component.component_name) {
@@ -1,9 +1,9 @@
 use completion::{
     get_all_root_component_member_completions, get_block_declaration_completions,
     get_class_completions, get_common_properties_setting_completions,
-    get_common_property_type_completion, get_id_completions, get_root_component_methods,
-    get_struct_property_setting_completions, get_struct_property_type_completion,
-    get_struct_static_member_completions,
+    get_common_property_type_completion, get_control_flow_completions, get_id_completions,
+    get_root_component_methods, get_struct_property_setting_completions,
+    get_struct_property_type_completion, get_struct_static_member_completions,
 };
 use completion::{get_event_completions, get_struct_completion};
 use core::panic;
@@ -526,6 +526,17 @@ impl Backend {
                                         "transform",
                                         "width",
                                         "height",
+                                        "tab_index",
+                                        "opacity",
+                                        "visible",
+                                        "pointer_events",
+                                        "z_index",
+                                        "cursor",
+                                        "min_width",
+                                        "max_width",
+                                        "min_height",
+                                        "max_height",
+                                        "padding",
                                     ];
 
                                     if let Some(struct_ident) = struct_name {
@@ -945,6 +956,10 @@ impl LanguageServer for Backend {
                             return Ok(Some(CompletionResponse::Array(completions)));
                         }
                     }
+                    if tag_node.is_none() {
+                        completions.extend(get_control_flow_completions());
+                        return Ok(Some(CompletionResponse::Array(completions)));
+                    }
                 }
             }
         }
@@ -178,8 +178,28 @@ fn recurse_visit_tag_pairs_for_pascal_identifiers(
         Rule::statement_control_flow => {
             let matched_tag = any_tag_pair.into_inner().next().unwrap();
 
+            if matched_tag.as_rule() == Rule::statement_if {
+                // Visit the nodes of every branch, including those of `else if`s and `else`
+                matched_tag
+                    .into_inner()
+                    .flat_map(|pair| match pair.as_rule() {
+                        Rule::statement_else_if | Rule::statement_else => {
+                            pair.into_inner().collect::<Vec<_>>()
+                        }
+                        _ => vec![pair],
+                    })
+                    .filter(|pair| pair.as_rule() == Rule::inner_nodes)
+                    .flat_map(|inner_nodes| inner_nodes.into_inner())
+                    .for_each(|sub_tag_pair| {
+                        recurse_visit_tag_pairs_for_pascal_identifiers(
+                            sub_tag_pair,
+                            Rc::clone(&pascal_identifiers),
+                        );
+                    });
+                return;
+            }
+
             let n = match matched_tag.as_rule() {
-                Rule::statement_if => 1,
                 Rule::statement_for => 2,
                 Rule::statement_slot => 0,
                 _ => {
@@ -187,7 +207,11 @@ fn recurse_visit_tag_pairs_for_pascal_identifiers(
                 }
             };
 
-            let prospective_inner_nodes = matched_tag.into_inner().nth(n).expect("WRONG nth");
+            let prospective_inner_nodes = matched_tag
+                .into_inner()
+                .skip(n)
+                .find(|pair| pair.as_rule() != Rule::statement_for_key)
+                .expect("WRONG nth");
             match prospective_inner_nodes.as_rule() {
                 Rule::inner_nodes => {
                     let inner_nodes = prospective_inner_nodes;
@@ -4,7 +4,7 @@ use std::collections::HashMap;
 use crate::{
     constants::{COMMON_PROPERTIES, COMMON_PROPERTIES_TYPE},
     PaxManifest, PropertyDefinition, SettingElement, SettingsBlockElement, TemplateNodeDefinition,
-    Token, TypeId, ValueDefinition,
+    TimelineDefinition, Token, TypeId, ValueDefinition,
 };
 
 #[derive(Serialize, Debug)]
@@ -50,32 +50,51 @@ impl PaxManifest {
     }
 
     pub fn event_to_args_map(&self) -> HashMap<String, Option<String>> {
+        // Events that propagate through a node's ancestors, and so can also be handled
+        // during the capture phase, e.g. `@click_capture`
+        let propagating = [
+            ("scroll", "Scroll"),
+            ("clap", "Clap"),
+            ("touch_start", "TouchStart"),
+            ("touch_move", "TouchMove"),
+            ("touch_end", "TouchEnd"),
+            ("key_down", "KeyDown"),
+            ("key_up", "KeyUp"),
+            ("key_press", "KeyPress"),
+            ("checkbox_change", "CheckboxChange"),
+            ("button_click", "ButtonClick"),
+            ("textbox_change", "TextboxChange"),
+            ("text_input", "TextInput"),
+            ("textbox_input", "TextboxInput"),
+            ("click", "Click"),
+            ("mouse_down", "MouseDown"),
+            ("mouse_up", "MouseUp"),
+            ("mouse_move", "MouseMove"),
+            ("double_click", "DoubleClick"),
+            ("context_menu", "ContextMenu"),
+            ("wheel", "Wheel"),
+            ("drag_start", "DragStart"),
+            ("drag", "Drag"),
+            ("drag_end", "DragEnd"),
+            ("pinch", "Pinch"),
+            ("rotate_gesture", "RotateGesture"),
+            ("pan", "Pan"),
+            ("long_press", "LongPress"),
+            ("swipe", "Swipe"),
+        ];
         let mut map = HashMap::new();
-        let mut add = |from: &str, to: &str| {
+        for (from, to) in propagating {
             map.insert(from.to_owned(), Some(to.to_owned()));
-        };
-        add("scroll", "Scroll");
-        add("clap", "Clap");
-        add("touch_start", "TouchStart");
-        add("touch_move", "TouchMove");
-        add("touch_end", "TouchEnd");
-        add("key_down", "KeyDown");
-        add("key_up", "KeyUp");
-        add("key_press", "KeyPress");
-        add("checkbox_change", "CheckboxChange");
-        add("button_click", "ButtonClick");
-        add("textbox_change", "TextboxChange");
-        add("text_input", "TextInput");
-        add("textbox_input", "TextboxInput");
-        add("click", "Click");
-        add("mouse_down", "MouseDown");
-        add("mouse_up", "MouseUp");
-        add("mouse_move", "MouseMove");
-        add("mouse_over", "MouseOver");
-        add("mouse_out", "MouseOut");
-        add("double_click", "DoubleClick");
-        add("context_menu", "ContextMenu");
-        add("wheel", "Wheel");
+            map.insert(format!("{}_capture", from), Some(to.to_owned()));
+        }
+        map.insert("focus".to_string(), Some("Focus".to_string()));
+        map.insert("blur".to_string(), Some("Blur".to_string()));
+        map.insert("mouse_over".to_string(), Some("MouseOver".to_string()));
+        map.insert("mouse_out".to_string(), Some("MouseOut".to_string()));
+        map.insert(
+            "transition_end".to_string(),
+            Some("TransitionEnd".to_string()),
+        );
         map.insert("pre_render".to_string(), None);
         map.insert("mount".to_string(), None);
         map.insert("tick".to_string(), None);
@@ -168,15 +187,18 @@ impl PaxManifest {
         let component = self.components.get(&containing_component_type_id).unwrap();
         let settings =
             Self::merge_inline_settings_with_settings_block(&tnd.settings, &component.settings);
+        let keyframes = Self::pull_timeline_keyframes(&tnd.settings, &component.timeline);
         let mut map = HashMap::new();
-        if let Some(settings) = &settings {
+        // Keyframed properties take their values from the timeline instead of their settings
+        for settings in [settings, keyframes].iter().flatten() {
             for setting in settings {
                 if let SettingElement::Setting(key, value) = setting {
                     match value {
                         ValueDefinition::LiteralValue(_)
                         | ValueDefinition::Block(_)
                         | ValueDefinition::Expression(_, _)
-                        | ValueDefinition::Identifier(_, _) => {
+                        | ValueDefinition::Identifier(_, _)
+                        | ValueDefinition::Keyframes(_) => {
                             map.insert(key.token_value.clone(), value.clone());
                         }
                         _ => {}
@@ -195,15 +217,17 @@ impl PaxManifest {
         let component = self.components.get(containing_component_type_id).unwrap();
         let settings =
             Self::merge_inline_settings_with_settings_block(&tnd.settings, &component.settings);
+        let keyframes = Self::pull_timeline_keyframes(&tnd.settings, &component.timeline);
         let mut map = HashMap::new();
-        if let Some(settings) = &settings {
+        for settings in [settings, keyframes].iter().flatten() {
             for setting in settings {
                 if let SettingElement::Setting(key, value) = setting {
                     match value {
                         ValueDefinition::LiteralValue(_)
                         | ValueDefinition::Block(_)
                         | ValueDefinition::Expression(_, _)
-                        | ValueDefinition::Identifier(_, _) => {
+                        | ValueDefinition::Identifier(_, _)
+                        | ValueDefinition::Keyframes(_) => {
                             if CommonProperty::get_common_properties().contains(&key.token_value) {
                                 map.insert(key.token_value.clone(), value.clone());
                             }
@@ -279,6 +303,37 @@ impl PaxManifest {
         })
     }
 
+    /// Keyframed properties from `timeline` whose selectors match the node with `inline_settings`,
+    /// by the same precedence as `merge_inline_settings_with_settings_block`
+    fn pull_timeline_keyframes(
+        inline_settings: &Option<Vec<SettingElement>>,
+        timeline: &Option<TimelineDefinition>,
+    ) -> Option<Vec<SettingElement>> {
+        let timeline = timeline.as_ref()?;
+        // Only the node's `id` and `class` settings matter here, for matching selectors
+        let selectors = inline_settings.as_ref().map(|settings| {
+            settings
+                .iter()
+                .filter(|e| match e {
+                    SettingElement::Setting(token, _) => {
+                        token.token_value == "id" || token.token_value == "class"
+                    }
+                    _ => false,
+                })
+                .cloned()
+                .collect()
+        });
+        let merged = Self::merge_inline_settings_with_settings_block(
+            &selectors,
+            &Some(timeline.selector_blocks.clone()),
+        )?;
+        let keyframes: Vec<SettingElement> = merged
+            .into_iter()
+            .filter(|e| matches!(e, SettingElement::Setting(_, ValueDefinition::Keyframes(_))))
+            .collect();
+        (!keyframes.is_empty()).then_some(keyframes)
+    }
+
     pub fn merge_inline_settings_with_settings_block(
         inline_settings: &Option<Vec<SettingElement>>,
         settings_block: &Option<Vec<SettingsBlockElement>>,
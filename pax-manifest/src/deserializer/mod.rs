@@ -22,6 +22,28 @@ use crate::constants::{
 use crate::deserializer::helpers::{ColorFuncArg, PaxSeqArg};
 use pax_runtime_api::{Color, IntoableLiteral};
 
+/// Deserializes a bare number literal as a primitive number where one is expected, e.g. the
+/// arguments of `EasingCurve::CubicBezier(0.25, 0.1, 0.25, 1.0)`, rather than as a `Numeric`
+macro_rules! deserialize_number {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value>
+            where
+                V: Visitor<'de>,
+            {
+                let input = self.input.trim();
+                if let Ok(integer) = input.parse::<i64>() {
+                    visitor.visit_i64(integer)
+                } else if let Ok(float) = input.parse::<f64>() {
+                    visitor.visit_f64(float)
+                } else {
+                    self.deserialize_any(visitor)
+                }
+            }
+        )*
+    };
+}
+
 pub struct Deserializer {
     input: String,
 }
@@ -251,11 +273,17 @@ impl<'de> de::Deserializer<'de> for Deserializer {
     }
 
     forward_to_deserialize_any! {
-        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bool i128 u128 char str string
         bytes byte_buf option unit unit_struct newtype_struct seq tuple
         tuple_struct map struct enum ignored_any
     }
 
+    deserialize_number! {
+        deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+        deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64
+        deserialize_f32 deserialize_f64
+    }
+
     fn deserialize_identifier<V>(
         self,
         visitor: V,
@@ -192,3 +192,28 @@ fn test_object() {
     let v = from_pax(&object_pax).unwrap();
     assert_eq!(expected, v);
 }
+
+#[test]
+fn test_enum_with_primitive_number_args() {
+    #[derive(Deserialize, PartialEq, Debug)]
+    pub enum EasingCurve {
+        Linear,
+        CubicBezier(f64, f64, f64, f64),
+        Steps(usize),
+    }
+
+    let bezier_pax = "EasingCurve::CubicBezier(0.25, 0.1, 0.25, 1)".to_string();
+    let expected = EasingCurve::CubicBezier(0.25, 0.1, 0.25, 1.0);
+    let v = from_pax(&bezier_pax).unwrap();
+    assert_eq!(expected, v);
+
+    let steps_pax = "EasingCurve::Steps(4)".to_string();
+    let expected = EasingCurve::Steps(4);
+    let v = from_pax(&steps_pax).unwrap();
+    assert_eq!(expected, v);
+
+    let linear_pax = "EasingCurve::Linear".to_string();
+    let expected = EasingCurve::Linear;
+    let v = from_pax(&linear_pax).unwrap();
+    assert_eq!(expected, v);
+}
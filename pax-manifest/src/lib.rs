@@ -253,6 +253,9 @@ pub struct ComponentDefinition {
     pub primitive_instance_import_path: Option<String>,
     pub template: Option<ComponentTemplate>,
     pub settings: Option<Vec<SettingsBlockElement>>,
+    /// Keyframes declared in this component's `@timeline` block, if any
+    #[serde(default)]
+    pub timeline: Option<TimelineDefinition>,
 }
 
 impl ComponentDefinition {
@@ -269,6 +272,31 @@ pub enum SettingsBlockElement {
     Comment(String),
 }
 
+/// Parsed `@timeline` block: playback settings for a component's timeline, and keyframes for
+/// the properties of its template nodes, grouped by selector like `@settings`.  Each keyframed
+/// property is a `SettingElement::Setting` with a `ValueDefinition::Keyframes` value.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(crate = "pax_message::serde")]
+pub struct TimelineDefinition {
+    /// Length of the timeline, defaulting to the frame of its last keyframe
+    pub frame_count: usize,
+    /// Whether the timeline starts playing when the component mounts
+    pub autoplay: bool,
+    /// Whether the timeline restarts upon finishing
+    pub looping: bool,
+    pub selector_blocks: Vec<SettingsBlockElement>,
+}
+
+/// A single `frame: value curve` entry of a keyframed property, e.g. `30: 100px OutQuad`
+#[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq)]
+#[serde(crate = "pax_message::serde")]
+pub struct KeyframeDefinition {
+    pub frame: usize,
+    pub value: Token,
+    /// Easing into this keyframe from the previous one; linear if unspecified
+    pub curve: Option<Token>,
+}
+
 #[derive(Serialize, Default, Deserialize, Debug, Clone, Hash, PartialEq, Eq)]
 #[serde(crate = "pax_message::serde")]
 pub struct TemplateNodeId(usize);
@@ -1458,6 +1486,8 @@ pub enum ValueDefinition {
     /// (Expression contents, vtable id binding)
     Identifier(Token, Option<usize>),
     EventBindingTarget(Token),
+    /// Keyframes declared for this property in an `@timeline` block
+    Keyframes(Vec<KeyframeDefinition>),
 }
 
 impl Hash for ValueDefinition {
@@ -1481,6 +1511,9 @@ impl Hash for ValueDefinition {
             ValueDefinition::EventBindingTarget(t) => {
                 t.hash(state);
             }
+            ValueDefinition::Keyframes(k) => {
+                k.hash(state);
+            }
         }
     }
 }
@@ -1530,6 +1563,13 @@ impl PartialEq for ValueDefinition {
                     false
                 }
             }
+            ValueDefinition::Keyframes(k) => {
+                if let ValueDefinition::Keyframes(ok) = other {
+                    k == ok
+                } else {
+                    false
+                }
+            }
         }
     }
 }
@@ -1565,6 +1605,13 @@ pub struct ControlFlowSettingsDefinition {
     pub slot_index_expression_vtable_id: Option<usize>,
     pub repeat_predicate_definition: Option<ControlFlowRepeatPredicateDefinition>,
     pub repeat_source_definition: Option<ControlFlowRepeatSourceDefinition>,
+    /// The `item.id` in `for item in self.items key item.id`
+    pub repeat_key_expression_paxel: Option<Token>,
+    pub repeat_key_expression_vtable_id: Option<usize>,
+    /// Whether this `if` is the `else if` (or, without a condition, the `else`) of the `if`
+    /// it is the last child of
+    #[serde(default)]
+    pub is_else_branch: bool,
 }
 
 impl PartialEq for ControlFlowRepeatSourceDefinition {
@@ -1587,6 +1634,8 @@ impl PartialEq for ControlFlowSettingsDefinition {
             && self.slot_index_expression_paxel == other.slot_index_expression_paxel
             && self.repeat_predicate_definition == other.repeat_predicate_definition
             && self.repeat_source_definition == other.repeat_source_definition
+            && self.repeat_key_expression_paxel == other.repeat_key_expression_paxel
+            && self.is_else_branch == other.is_else_branch
     }
 }
 
@@ -1598,6 +1647,8 @@ impl Hash for ControlFlowSettingsDefinition {
         self.slot_index_expression_paxel.hash(state);
         self.repeat_predicate_definition.hash(state);
         self.repeat_source_definition.hash(state);
+        self.repeat_key_expression_paxel.hash(state);
+        self.is_else_branch.hash(state);
     }
 }
 
@@ -1692,6 +1743,7 @@ pub enum TokenType {
     IfExpression,
     ForPredicate,
     ForSource,
+    ForKey,
     SlotExpression,
     EventId,
     Handler,
@@ -1845,7 +1897,7 @@ pub struct HostCrateInfo {
 }
 
 //Effectively our `Prelude` types
-pub const IMPORTS_BUILTINS: [&str; 29] = [
+pub const IMPORTS_BUILTINS: [&str; 30] = [
     "std::any::Any",
     "std::cell::RefCell",
     "std::collections::HashMap",
@@ -1853,6 +1905,7 @@ pub const IMPORTS_BUILTINS: [&str; 29] = [
     "std::ops::Deref",
     "std::rc::Rc",
     "pax_runtime::RepeatItem",
+    "pax_runtime::RepeatKey",
     "pax_runtime::RepeatProperties",
     "pax_runtime::ConditionalProperties",
     "pax_runtime::SlotProperties",
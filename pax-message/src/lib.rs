@@ -31,6 +31,7 @@ pub enum NativeMessage {
     ImageLoad(ImagePatch),
     LayerAdd(LayerAddPatch), //FUTURE: native form controls
     OcclusionUpdate(OcclusionPatch),
+    CursorUpdate(CursorMessage),
 }
 
 #[derive(Deserialize)]
@@ -60,6 +61,7 @@ pub enum NativeInterrupt {
     FormTextboxChange(FormTextboxChangeArgs),
     FormTextboxInput(FormTextboxInputArgs),
     FormButtonClick(FormButtonClickArgs),
+    Measure(MeasureArgs),
 }
 
 #[derive(Deserialize)]
@@ -96,6 +98,16 @@ pub struct FormButtonClickArgs {
     pub id_chain: Vec<u32>,
 }
 
+/// The natural size of a natively rendered element's content, e.g. a `Text`'s, as laid out by
+/// the chassis: unwrapped `width`, and `height` at the element's current width
+#[derive(Deserialize)]
+#[repr(C)]
+pub struct MeasureArgs {
+    pub id_chain: Vec<u32>,
+    pub width: f64,
+    pub height: f64,
+}
+
 #[derive(Deserialize)]
 #[repr(C)]
 pub struct ClapInterruptArgs {
@@ -147,7 +159,7 @@ pub enum MouseButtonMessage {
     Unknown,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Clone)]
 #[repr(C)]
 pub enum ModifierKeyMessage {
     Shift,
@@ -330,6 +342,12 @@ pub struct CheckboxPatch {
     pub size_x: Option<f64>,
     pub size_y: Option<f64>,
     pub checked: Option<bool>,
+    pub opacity: Option<f64>,
+    pub visible: Option<bool>,
+    pub pointer_events: Option<bool>,
+    /// SVG path data of the clip of the nearest clipping ancestor, in the element's own
+    /// coordinate space; empty when unclipped
+    pub clip_path: Option<String>,
 }
 
 #[cfg_attr(debug_assertions, derive(Debug))]
@@ -347,6 +365,12 @@ pub struct TextboxPatch {
     pub border_radius: Option<f64>,
     pub style: Option<TextStyleMessage>,
     pub focus_on_mount: Option<bool>,
+    pub opacity: Option<f64>,
+    pub visible: Option<bool>,
+    pub pointer_events: Option<bool>,
+    /// SVG path data of the clip of the nearest clipping ancestor, in the element's own
+    /// coordinate space; empty when unclipped
+    pub clip_path: Option<String>,
 }
 
 #[cfg_attr(debug_assertions, derive(Debug))]
@@ -359,6 +383,12 @@ pub struct ButtonPatch {
     pub size_y: Option<f64>,
     pub content: Option<String>,
     pub style: Option<TextStyleMessage>,
+    pub opacity: Option<f64>,
+    pub visible: Option<bool>,
+    pub pointer_events: Option<bool>,
+    /// SVG path data of the clip of the nearest clipping ancestor, in the element's own
+    /// coordinate space; empty when unclipped
+    pub clip_path: Option<String>,
 }
 
 #[derive(Default, Serialize)]
@@ -387,6 +417,12 @@ pub struct TextPatch {
     pub size_y: Option<f64>,
     pub style: Option<TextStyleMessage>,
     pub style_link: Option<TextStyleMessage>,
+    pub opacity: Option<f64>,
+    pub visible: Option<bool>,
+    pub pointer_events: Option<bool>,
+    /// SVG path data of the clip of the nearest clipping ancestor, in the element's own
+    /// coordinate space; empty when unclipped
+    pub clip_path: Option<String>,
 }
 
 #[cfg_attr(debug_assertions, derive(Debug))]
@@ -424,6 +460,27 @@ impl Default for ColorMessage {
     }
 }
 
+#[cfg_attr(debug_assertions, derive(Debug))]
+#[derive(Default, Serialize, Clone, PartialEq)]
+#[repr(C)]
+pub enum CursorMessage {
+    #[default]
+    Default,
+    Pointer,
+    Text,
+    Move,
+    Grab,
+    Grabbing,
+    NotAllowed,
+    EwResize,
+    NsResize,
+    NeswResize,
+    NwseResize,
+    ColResize,
+    RowResize,
+    None,
+}
+
 #[cfg_attr(debug_assertions, derive(Debug))]
 #[derive(Default, Serialize, Clone, PartialEq)]
 #[repr(C)]
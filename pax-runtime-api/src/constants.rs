@@ -18,7 +18,7 @@ pub const TYPE_ID_REPEAT: &str = "REPEAT";
 pub const TYPE_ID_SLOT: &str = "SLOT";
 pub const TYPE_ID_COMMENT: &str = "COMMENT";
 
-pub const COMMON_PROPERTIES: [&str; 13] = [
+pub const COMMON_PROPERTIES: [&str; 24] = [
     "id",
     "x",
     "y",
@@ -32,6 +32,17 @@ pub const COMMON_PROPERTIES: [&str; 13] = [
     "transform",
     "width",
     "height",
+    "tab_index",
+    "opacity",
+    "visible",
+    "pointer_events",
+    "z_index",
+    "cursor",
+    "min_width",
+    "max_width",
+    "min_height",
+    "max_height",
+    "padding",
 ];
 
 pub fn is_intoable_downstream_type(type_to_check: &str) -> bool {
@@ -50,7 +61,7 @@ const blessed_intoable_downstream_types: [&'static str; 5] = [
     "pax_std::types::Fill",
 ];
 
-pub const COMMON_PROPERTIES_TYPE: [(&str, &str); 13] = [
+pub const COMMON_PROPERTIES_TYPE: [(&str, &str); 24] = [
     ("id", "String"),
     ("x", "pax_engine::api::Size"),
     ("y", "pax_engine::api::Size"),
@@ -64,4 +75,15 @@ pub const COMMON_PROPERTIES_TYPE: [(&str, &str); 13] = [
     ("transform", "pax_engine::api::Transform2D"),
     ("width", "pax_engine::api::Size"),
     ("height", "pax_engine::api::Size"),
+    ("tab_index", "pax_engine::api::Numeric"),
+    ("opacity", "pax_engine::api::Numeric"),
+    ("visible", "bool"),
+    ("pointer_events", "bool"),
+    ("z_index", "pax_engine::api::Numeric"),
+    ("cursor", "pax_engine::api::Cursor"),
+    ("min_width", "pax_engine::api::Size"),
+    ("max_width", "pax_engine::api::Size"),
+    ("min_height", "pax_engine::api::Size"),
+    ("max_height", "pax_engine::api::Size"),
+    ("padding", "pax_engine::api::Size"),
 ];
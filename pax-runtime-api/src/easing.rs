@@ -0,0 +1,539 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use std::rc::Rc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Interpolatable;
+
+/// The shape of a transition over time.  Apart from `Custom`, every curve can be written as a
+/// literal in templates, e.g. `EasingCurve::OutBounce` or
+/// `EasingCurve::CubicBezier(0.25, 0.1, 0.25, 1.0)`.
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(crate = "crate::serde")]
+pub enum EasingCurve {
+    #[default]
+    Linear,
+    InQuad,
+    OutQuad,
+    InOutQuad,
+    InCubic,
+    OutCubic,
+    InOutCubic,
+    InQuart,
+    OutQuart,
+    InOutQuart,
+    InExpo,
+    OutExpo,
+    InOutExpo,
+    InSine,
+    OutSine,
+    InOutSine,
+    InBack,
+    OutBack,
+    InOutBack,
+    InElastic,
+    OutElastic,
+    InOutElastic,
+    InBounce,
+    OutBounce,
+    InOutBounce,
+    /// A cubic Bézier from `(0, 0)` to `(1, 1)` with control points `(x1, y1)` and `(x2, y2)`,
+    /// as CSS's `cubic-bezier(x1, y1, x2, y2)`.  `x1` and `x2` are clamped to `[0, 1]`.
+    CubicBezier(f64, f64, f64, f64),
+    /// Jumps between `n` evenly spaced levels, as CSS's `steps(n, jump-end)`
+    Steps(usize),
+    /// A damped spring with the given stiffness, damping and mass.  When used for a transition,
+    /// the transition lasts until the spring settles (whatever duration was requested), and
+    /// retargeting it mid-flight with `transition_to` carries over its velocity; see
+    /// [`crate::PropertyInstance::spring_to`].
+    Spring(f64, f64, f64),
+    #[serde(skip)]
+    Custom(Rc<dyn Fn(f64) -> f64>),
+}
+
+#[cfg(debug_assertions)]
+impl std::fmt::Debug for EasingCurve {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EasingCurve::CubicBezier(x1, y1, x2, y2) => f
+                .debug_tuple("CubicBezier")
+                .field(x1)
+                .field(y1)
+                .field(x2)
+                .field(y2)
+                .finish(),
+            EasingCurve::Steps(n) => f.debug_tuple("Steps").field(n).finish(),
+            EasingCurve::Spring(stiffness, damping, mass) => f
+                .debug_tuple("Spring")
+                .field(stiffness)
+                .field(damping)
+                .field(mass)
+                .finish(),
+            EasingCurve::Custom(_) => f.write_str("Custom"),
+            EasingCurve::Linear => f.write_str("Linear"),
+            EasingCurve::InQuad => f.write_str("InQuad"),
+            EasingCurve::OutQuad => f.write_str("OutQuad"),
+            EasingCurve::InOutQuad => f.write_str("InOutQuad"),
+            EasingCurve::InCubic => f.write_str("InCubic"),
+            EasingCurve::OutCubic => f.write_str("OutCubic"),
+            EasingCurve::InOutCubic => f.write_str("InOutCubic"),
+            EasingCurve::InQuart => f.write_str("InQuart"),
+            EasingCurve::OutQuart => f.write_str("OutQuart"),
+            EasingCurve::InOutQuart => f.write_str("InOutQuart"),
+            EasingCurve::InExpo => f.write_str("InExpo"),
+            EasingCurve::OutExpo => f.write_str("OutExpo"),
+            EasingCurve::InOutExpo => f.write_str("InOutExpo"),
+            EasingCurve::InSine => f.write_str("InSine"),
+            EasingCurve::OutSine => f.write_str("OutSine"),
+            EasingCurve::InOutSine => f.write_str("InOutSine"),
+            EasingCurve::InBack => f.write_str("InBack"),
+            EasingCurve::OutBack => f.write_str("OutBack"),
+            EasingCurve::InOutBack => f.write_str("InOutBack"),
+            EasingCurve::InElastic => f.write_str("InElastic"),
+            EasingCurve::OutElastic => f.write_str("OutElastic"),
+            EasingCurve::InOutElastic => f.write_str("InOutElastic"),
+            EasingCurve::InBounce => f.write_str("InBounce"),
+            EasingCurve::OutBounce => f.write_str("OutBounce"),
+            EasingCurve::InOutBounce => f.write_str("InOutBounce"),
+        }
+    }
+}
+
+impl Interpolatable for EasingCurve {}
+
+struct EasingEvaluators {}
+impl EasingEvaluators {
+    fn linear(t: f64) -> f64 {
+        t
+    }
+    #[allow(dead_code)]
+    fn none(t: f64) -> f64 {
+        if t == 1.0 {
+            1.0
+        } else {
+            0.0
+        }
+    }
+    fn in_quad(t: f64) -> f64 {
+        t * t
+    }
+    fn out_quad(t: f64) -> f64 {
+        1.0 - (1.0 - t) * (1.0 - t)
+    }
+    fn in_out_quad(t: f64) -> f64 {
+        if t < 0.5 {
+            2.0 * t * t
+        } else {
+            1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+        }
+    }
+    fn in_cubic(t: f64) -> f64 {
+        t.powi(3)
+    }
+    fn out_cubic(t: f64) -> f64 {
+        1.0 - (1.0 - t).powi(3)
+    }
+    fn in_out_cubic(t: f64) -> f64 {
+        if t < 0.5 {
+            4.0 * t.powi(3)
+        } else {
+            1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+        }
+    }
+    fn in_quart(t: f64) -> f64 {
+        t.powi(4)
+    }
+    fn out_quart(t: f64) -> f64 {
+        1.0 - (1.0 - t).powi(4)
+    }
+    fn in_out_quart(t: f64) -> f64 {
+        if t < 0.5 {
+            8.0 * t.powi(4)
+        } else {
+            1.0 - (-2.0 * t + 2.0).powi(4) / 2.0
+        }
+    }
+    fn in_expo(t: f64) -> f64 {
+        if t <= 0.0 {
+            0.0
+        } else {
+            2f64.powf(10.0 * t - 10.0)
+        }
+    }
+    fn out_expo(t: f64) -> f64 {
+        if t >= 1.0 {
+            1.0
+        } else {
+            1.0 - 2f64.powf(-10.0 * t)
+        }
+    }
+    fn in_out_expo(t: f64) -> f64 {
+        if t <= 0.0 {
+            0.0
+        } else if t >= 1.0 {
+            1.0
+        } else if t < 0.5 {
+            2f64.powf(20.0 * t - 10.0) / 2.0
+        } else {
+            (2.0 - 2f64.powf(-20.0 * t + 10.0)) / 2.0
+        }
+    }
+    fn in_sine(t: f64) -> f64 {
+        1.0 - (t * PI / 2.0).cos()
+    }
+    fn out_sine(t: f64) -> f64 {
+        (t * PI / 2.0).sin()
+    }
+    fn in_out_sine(t: f64) -> f64 {
+        -((PI * t).cos() - 1.0) / 2.0
+    }
+    fn in_back(t: f64) -> f64 {
+        const C1: f64 = 1.70158;
+        const C3: f64 = C1 + 1.00;
+        C3 * t * t * t - C1 * t * t
+    }
+    fn out_back(t: f64) -> f64 {
+        const C1: f64 = 1.70158;
+        const C3: f64 = C1 + 1.00;
+        1.0 + C3 * (t - 1.0).powi(3) + C1 * (t - 1.0).powi(2)
+    }
+
+    fn in_out_back(t: f64) -> f64 {
+        const C1: f64 = 1.70158;
+        const C2: f64 = C1 * 1.525;
+        if t < 0.5 {
+            ((2.0 * t).powi(2) * ((C2 + 1.0) * 2.0 * t - C2)) / 2.0
+        } else {
+            ((2.0 * t - 2.0).powi(2) * ((C2 + 1.0) * (t * 2.0 - 2.0) + C2) + 2.0) / 2.0
+        }
+    }
+    fn in_elastic(t: f64) -> f64 {
+        const C4: f64 = 2.0 * PI / 3.0;
+        if t <= 0.0 {
+            0.0
+        } else if t >= 1.0 {
+            1.0
+        } else {
+            -(2f64.powf(10.0 * t - 10.0)) * ((t * 10.0 - 10.75) * C4).sin()
+        }
+    }
+    fn out_elastic(t: f64) -> f64 {
+        const C4: f64 = 2.0 * PI / 3.0;
+        if t <= 0.0 {
+            0.0
+        } else if t >= 1.0 {
+            1.0
+        } else {
+            2f64.powf(-10.0 * t) * ((t * 10.0 - 0.75) * C4).sin() + 1.0
+        }
+    }
+    fn in_out_elastic(t: f64) -> f64 {
+        const C5: f64 = 2.0 * PI / 4.5;
+        if t <= 0.0 {
+            0.0
+        } else if t >= 1.0 {
+            1.0
+        } else if t < 0.5 {
+            -(2f64.powf(20.0 * t - 10.0) * ((20.0 * t - 11.125) * C5).sin()) / 2.0
+        } else {
+            (2f64.powf(-20.0 * t + 10.0) * ((20.0 * t - 11.125) * C5).sin()) / 2.0 + 1.0
+        }
+    }
+    fn in_bounce(t: f64) -> f64 {
+        1.0 - Self::out_bounce(1.0 - t)
+    }
+    fn out_bounce(t: f64) -> f64 {
+        const N1: f64 = 7.5625;
+        const D1: f64 = 2.75;
+        if t < 1.0 / D1 {
+            N1 * t * t
+        } else if t < 2.0 / D1 {
+            let t = t - 1.5 / D1;
+            N1 * t * t + 0.75
+        } else if t < 2.5 / D1 {
+            let t = t - 2.25 / D1;
+            N1 * t * t + 0.9375
+        } else {
+            let t = t - 2.625 / D1;
+            N1 * t * t + 0.984375
+        }
+    }
+    fn in_out_bounce(t: f64) -> f64 {
+        if t < 0.5 {
+            (1.0 - Self::out_bounce(1.0 - 2.0 * t)) / 2.0
+        } else {
+            (1.0 + Self::out_bounce(2.0 * t - 1.0)) / 2.0
+        }
+    }
+    fn cubic_bezier(x1: f64, y1: f64, x2: f64, y2: f64, t: f64) -> f64 {
+        let (x1, x2) = (x1.clamp(0.0, 1.0), x2.clamp(0.0, 1.0));
+        // one coordinate of the curve at parameter `s`, with endpoints 0 and 1
+        let bezier = |p1: f64, p2: f64, s: f64| {
+            3.0 * (1.0 - s).powi(2) * s * p1 + 3.0 * (1.0 - s) * s * s * p2 + s.powi(3)
+        };
+        let bezier_slope = |p1: f64, p2: f64, s: f64| {
+            3.0 * (1.0 - s).powi(2) * p1
+                + 6.0 * (1.0 - s) * s * (p2 - p1)
+                + 3.0 * s * s * (1.0 - p2)
+        };
+        if t <= 0.0 || t >= 1.0 {
+            return bezier(y1, y2, t);
+        }
+
+        // Find the parameter `s` at which the curve reaches `x = t`.  x(s) is monotonic on [0, 1]
+        // since x1 and x2 are, so Newton's method usually converges in a few steps; fall back
+        // to bisection where the slope is too flat for it.
+        const EPSILON: f64 = 1e-7;
+        let mut s = t;
+        for _ in 0..8 {
+            let error = bezier(x1, x2, s) - t;
+            if error.abs() < EPSILON {
+                return bezier(y1, y2, s);
+            }
+            let slope = bezier_slope(x1, x2, s);
+            if slope.abs() < 1e-6 {
+                break;
+            }
+            s -= error / slope;
+        }
+        let (mut low, mut high) = (0.0, 1.0);
+        s = t;
+        while high - low > EPSILON {
+            if bezier(x1, x2, s) < t {
+                low = s;
+            } else {
+                high = s;
+            }
+            s = (low + high) / 2.0;
+        }
+        bezier(y1, y2, s)
+    }
+    fn steps(n: usize, t: f64) -> f64 {
+        if t >= 1.0 {
+            return 1.0;
+        }
+        let n = n.max(1) as f64;
+        (t * n).floor().max(0.0) / n
+    }
+}
+
+impl EasingCurve {
+    /// For a time on the unit interval `t ∈ [0,1]`, the eased progress from start (0.0) to end
+    /// (1.0), which may overshoot in either direction for curves like `OutBack`.  A `Spring` is
+    /// evaluated starting from rest, stretched so that it settles at `t = 1.0`.
+    pub fn evaluate(&self, t: f64) -> f64 {
+        match self {
+            EasingCurve::Linear => EasingEvaluators::linear(t),
+            EasingCurve::InQuad => EasingEvaluators::in_quad(t),
+            EasingCurve::OutQuad => EasingEvaluators::out_quad(t),
+            EasingCurve::InOutQuad => EasingEvaluators::in_out_quad(t),
+            EasingCurve::InCubic => EasingEvaluators::in_cubic(t),
+            EasingCurve::OutCubic => EasingEvaluators::out_cubic(t),
+            EasingCurve::InOutCubic => EasingEvaluators::in_out_cubic(t),
+            EasingCurve::InQuart => EasingEvaluators::in_quart(t),
+            EasingCurve::OutQuart => EasingEvaluators::out_quart(t),
+            EasingCurve::InOutQuart => EasingEvaluators::in_out_quart(t),
+            EasingCurve::InExpo => EasingEvaluators::in_expo(t),
+            EasingCurve::OutExpo => EasingEvaluators::out_expo(t),
+            EasingCurve::InOutExpo => EasingEvaluators::in_out_expo(t),
+            EasingCurve::InSine => EasingEvaluators::in_sine(t),
+            EasingCurve::OutSine => EasingEvaluators::out_sine(t),
+            EasingCurve::InOutSine => EasingEvaluators::in_out_sine(t),
+            EasingCurve::InBack => EasingEvaluators::in_back(t),
+            EasingCurve::OutBack => EasingEvaluators::out_back(t),
+            EasingCurve::InOutBack => EasingEvaluators::in_out_back(t),
+            EasingCurve::InElastic => EasingEvaluators::in_elastic(t),
+            EasingCurve::OutElastic => EasingEvaluators::out_elastic(t),
+            EasingCurve::InOutElastic => EasingEvaluators::in_out_elastic(t),
+            EasingCurve::InBounce => EasingEvaluators::in_bounce(t),
+            EasingCurve::OutBounce => EasingEvaluators::out_bounce(t),
+            EasingCurve::InOutBounce => EasingEvaluators::in_out_bounce(t),
+            EasingCurve::CubicBezier(x1, y1, x2, y2) => {
+                EasingEvaluators::cubic_bezier(*x1, *y1, *x2, *y2, t)
+            }
+            EasingCurve::Steps(n) => EasingEvaluators::steps(*n, t),
+            EasingCurve::Spring(stiffness, damping, mass) => {
+                let spring = SpringMotion::new(*stiffness, *damping, *mass, 0.0);
+                let key = [stiffness.to_bits(), damping.to_bits(), mass.to_bits()];
+                let settling_time = SPRING_SETTLING_TIMES.with(|times| {
+                    *times
+                        .borrow_mut()
+                        .entry(key)
+                        .or_insert_with(|| spring.settling_time().as_secs_f64())
+                });
+                spring.position(t * settling_time)
+            }
+            EasingCurve::Custom(evaluator) => (*evaluator)(t),
+        }
+    }
+
+    //for a time on the unit interval `t ∈ [0,1]`, given a value `t`,
+    // find the interpolated value `vt` between `v0` and `v1` given the self-contained easing curve
+    pub fn interpolate<T: Interpolatable>(&self, v0: &T, v1: &T, t: f64) -> T /*vt*/ {
+        v0.interpolate(v1, self.evaluate(t))
+    }
+}
+
+/// Largest distance from the target, and speed (in distance per natural period), at which a
+/// spring counts as settled, as fractions of the distance it travels
+const SPRING_REST_THRESHOLD: f64 = 0.001;
+/// Springs that never settle (e.g. with no damping) are cut off after this long
+const SPRING_MAX_DURATION: Duration = Duration::from_secs(60);
+
+thread_local! {
+    /// Settling times of the `EasingCurve::Spring`s evaluated so far, by the bits of their
+    /// stiffness, damping and mass, so each curve's is simulated once rather than every frame
+    static SPRING_SETTLING_TIMES: RefCell<HashMap<[u64; 3], f64>> = RefCell::new(HashMap::new());
+}
+
+/// The motion of a damped spring pulling a mass from 0.0 toward rest at 1.0, as a function of
+/// time in seconds.  `initial_velocity` is in the same units, i.e. fractions of the distance
+/// travelled per second.
+#[cfg_attr(debug_assertions, derive(Debug))]
+#[derive(Clone, Copy, PartialEq)]
+pub struct SpringMotion {
+    /// Natural angular frequency, `√(stiffness / mass)`
+    omega: f64,
+    /// Damping ratio: below 1.0 the spring oscillates about its rest position
+    zeta: f64,
+    initial_velocity: f64,
+}
+
+impl SpringMotion {
+    pub fn new(stiffness: f64, damping: f64, mass: f64, initial_velocity: f64) -> Self {
+        let (stiffness, mass) = (stiffness.max(f64::EPSILON), mass.max(f64::EPSILON));
+        Self {
+            omega: (stiffness / mass).sqrt(),
+            zeta: damping.max(0.0) / (2.0 * (stiffness * mass).sqrt()),
+            initial_velocity,
+        }
+    }
+
+    /// Displacement from rest and its rate of change, `t` seconds in: the solution of
+    /// `y'' + 2ζωy' + ω²y = 0` with `y(0) = -1` and `y'(0) = initial_velocity`
+    fn displacement(&self, t: f64) -> (f64, f64) {
+        let (omega, zeta, v0) = (self.omega, self.zeta, self.initial_velocity);
+        if zeta < 1.0 {
+            let omega_d = omega * (1.0 - zeta * zeta).sqrt();
+            let decay = (-zeta * omega * t).exp();
+            let (a, b) = (-1.0, (v0 - zeta * omega) / omega_d);
+            let (sin, cos) = (omega_d * t).sin_cos();
+            let y = decay * (a * cos + b * sin);
+            let dy = decay
+                * ((b * omega_d - zeta * omega * a) * cos - (a * omega_d + zeta * omega * b) * sin);
+            (y, dy)
+        } else if zeta == 1.0 {
+            let decay = (-omega * t).exp();
+            let (a, b) = (-1.0, v0 - omega);
+            let y = (a + b * t) * decay;
+            let dy = (b - omega * (a + b * t)) * decay;
+            (y, dy)
+        } else {
+            let root = (zeta * zeta - 1.0).sqrt();
+            let (r1, r2) = (-omega * (zeta - root), -omega * (zeta + root));
+            let c2 = (v0 + r1) / (r2 - r1);
+            let c1 = -1.0 - c2;
+            let (e1, e2) = ((r1 * t).exp(), (r2 * t).exp());
+            (c1 * e1 + c2 * e2, r1 * c1 * e1 + r2 * c2 * e2)
+        }
+    }
+
+    /// Progress from 0.0 toward 1.0, `t` seconds in
+    pub fn position(&self, t: f64) -> f64 {
+        1.0 + self.displacement(t).0
+    }
+
+    /// Rate of progress, in fractions of the distance per second, `t` seconds in
+    pub fn velocity(&self, t: f64) -> f64 {
+        self.displacement(t).1
+    }
+
+    /// How long until the spring has come to rest, to within a fraction of a percent
+    pub fn settling_time(&self) -> Duration {
+        const STEP: f64 = 1.0 / 60.0;
+        let max = SPRING_MAX_DURATION.as_secs_f64();
+        let mut t = 0.0;
+        while t < max {
+            let (y, dy) = self.displacement(t);
+            if y.abs() < SPRING_REST_THRESHOLD && (dy / self.omega).abs() < SPRING_REST_THRESHOLD {
+                break;
+            }
+            t += STEP;
+        }
+        Duration::from_secs_f64(t.min(max))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::easing::{EasingCurve, SpringMotion};
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-3,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn test_endpoints() {
+        let curves = [
+            EasingCurve::InOutQuad,
+            EasingCurve::InOutCubic,
+            EasingCurve::InOutQuart,
+            EasingCurve::InOutExpo,
+            EasingCurve::InOutSine,
+            EasingCurve::InOutElastic,
+            EasingCurve::InOutBounce,
+            EasingCurve::CubicBezier(0.42, 0.0, 0.58, 1.0),
+            EasingCurve::Steps(3),
+            EasingCurve::Spring(170.0, 26.0, 1.0),
+        ];
+        for curve in curves {
+            assert_close(curve.evaluate(0.0), 0.0);
+            assert_close(curve.evaluate(1.0), 1.0);
+        }
+    }
+
+    #[test]
+    fn test_cubic_bezier() {
+        // CSS's `ease`
+        let ease = EasingCurve::CubicBezier(0.25, 0.1, 0.25, 1.0);
+        assert_close(ease.evaluate(0.5), 0.8024);
+        let linear = EasingCurve::CubicBezier(0.0, 0.0, 1.0, 1.0);
+        assert_close(linear.evaluate(0.3), 0.3);
+    }
+
+    #[test]
+    fn test_steps() {
+        let steps = EasingCurve::Steps(4);
+        assert_eq!(steps.evaluate(0.2), 0.0);
+        assert_eq!(steps.evaluate(0.25), 0.25);
+        assert_eq!(steps.evaluate(0.99), 0.75);
+    }
+
+    #[test]
+    fn test_spring() {
+        let underdamped = SpringMotion::new(100.0, 5.0, 1.0, 0.0);
+        let overshoot = (0..100)
+            .map(|i| underdamped.position(i as f64 / 100.0))
+            .fold(f64::MIN, f64::max);
+        assert!(overshoot > 1.0);
+
+        for spring in [
+            underdamped,
+            SpringMotion::new(100.0, 20.0, 1.0, 0.0),
+            SpringMotion::new(100.0, 40.0, 1.0, 3.0),
+        ] {
+            assert_close(spring.position(0.0), 0.0);
+            let settled = spring.settling_time().as_secs_f64();
+            assert_close(spring.position(settled), 1.0);
+        }
+
+        let moving = SpringMotion::new(100.0, 20.0, 1.0, 3.0);
+        assert_close(moving.velocity(0.0), 3.0);
+    }
+}
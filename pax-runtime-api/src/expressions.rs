@@ -1,14 +1,22 @@
-use crate::{EasingCurve, PropertyInstance, PropertyType, TransitionManager, TransitionQueueEntry};
+use crate::reactivity::{self, Dependency, PropertyVersion};
+use crate::{
+    EasingCurve, PropertyInstance, PropertyType, TransitionDuration, TransitionManager,
+    TransitionQueueEntry,
+};
 
 // The `Expression` form of a property — stores a function
 // that evaluates the value itself, as well as a "register" of
 // the memoized value (`cached_value`) that can be referred to
-// via calls to `read()`
+// via calls to `read()`.  Also tracks the properties read during
+// its last evaluation, so that the engine only re-evaluates it when
+// one of those dependencies has changed.
 pub struct PropertyExpression<T: Default> {
     pub id: usize,
     pub has_been_evaluted: bool,
     pub cached_value: T,
     pub transition_manager: TransitionManager<T>,
+    pub version: PropertyVersion,
+    pub dependencies: Vec<Dependency>,
 }
 
 impl<T: Default> PropertyExpression<T> {
@@ -18,6 +26,8 @@ impl<T: Default> PropertyExpression<T> {
             cached_value: Default::default(),
             transition_manager: TransitionManager::new(),
             has_been_evaluted: false,
+            version: PropertyVersion::new(),
+            dependencies: Vec::new(),
         }
     }
 }
@@ -27,10 +37,12 @@ impl<T: Default + Clone> PropertyInstance<T> for PropertyExpression<T> {
         // if !self.has_been_evaluted {
         //     panic!("expression with id {:?} hasn't been evaluated", self.id)
         // }
+        self.version.track_read();
         &self.cached_value
     }
 
     fn get_mut(&mut self) -> &mut T {
+        self.version.bump();
         &mut self.cached_value
     }
 
@@ -41,24 +53,33 @@ impl<T: Default + Clone> PropertyInstance<T> for PropertyExpression<T> {
     fn set(&mut self, value: T) {
         self.cached_value = value;
         self.has_been_evaluted = true;
+        self.version.bump();
     }
 
     //FUTURE: when trait fields land, DRY this implementation vs. other <T: PropertyInstance> implementations
-    fn ease_to(&mut self, new_value: T, duration_frames: u64, curve: EasingCurve) {
+    fn transition_to(&mut self, new_value: T, duration: TransitionDuration, curve: EasingCurve) {
+        self.version.bump();
         self.transition_manager.value = Some(self.get().clone());
         self.transition_manager.queue.clear();
         self.transition_manager
             .queue
             .push_back(TransitionQueueEntry {
                 global_frame_started: None,
-                duration_frames,
+                global_time_started: None,
+                duration,
                 curve,
                 starting_value: self.cached_value.clone(),
                 ending_value: new_value,
+                initial_velocity: self.transition_manager.velocity,
             });
     }
 
-    fn ease_to_later(&mut self, new_value: T, duration_frames: u64, curve: EasingCurve) {
+    fn transition_to_later(
+        &mut self,
+        new_value: T,
+        duration: TransitionDuration,
+        curve: EasingCurve,
+    ) {
         if let None = self.transition_manager.value {
             //handle case where transition queue is empty -- a None value gets skipped, so populate it with Some
             self.transition_manager.value = Some(self.get().clone());
@@ -67,10 +88,12 @@ impl<T: Default + Clone> PropertyInstance<T> for PropertyExpression<T> {
             .queue
             .push_back(TransitionQueueEntry {
                 global_frame_started: None,
-                duration_frames,
+                global_time_started: None,
+                duration,
                 curve,
                 starting_value: self.cached_value.clone(),
                 ending_value: new_value,
+                initial_velocity: 0.0,
             });
     }
 
@@ -82,7 +105,19 @@ impl<T: Default + Clone> PropertyInstance<T> for PropertyExpression<T> {
         }
     }
 
+    fn is_animating(&self) -> bool {
+        !self.transition_manager.queue.is_empty()
+    }
+
     fn property_type(&self) -> PropertyType {
         PropertyType::Expression
     }
+
+    fn _is_dirty(&self) -> bool {
+        !self.has_been_evaluted || reactivity::any_changed(&self.dependencies)
+    }
+
+    fn _set_dependencies(&mut self, dependencies: Vec<Dependency>) {
+        self.dependencies = dependencies;
+    }
 }
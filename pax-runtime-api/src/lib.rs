@@ -1,6 +1,7 @@
 use std::any::Any;
 use std::collections::VecDeque;
 use std::ops::{Add, Deref, Mul, Neg, Sub};
+use std::time::Duration;
 
 use crate::math::Space;
 use kurbo::BezPath;
@@ -16,30 +17,94 @@ use std::cell::Cell;
 use std::rc::Rc;
 
 pub mod constants;
+pub mod easing;
 pub mod expressions;
 pub mod math;
 pub mod numeric;
+pub mod reactivity;
+pub mod timelines;
 
+pub use crate::easing::{EasingCurve, SpringMotion};
 pub use crate::numeric::Numeric;
+pub use crate::timelines::{Keyframe, PropertyTimeline};
 
 use crate::constants::COMMON_PROPERTIES_TYPE;
 use crate::expressions::PropertyExpression;
+use crate::reactivity::{Dependency, PropertyVersion};
 pub use pax_message::serde;
-use pax_message::{ColorMessage, ModifierKeyMessage, MouseButtonMessage, TouchMessage};
+use pax_message::{
+    ColorMessage, CursorMessage, ModifierKeyMessage, MouseButtonMessage, TouchMessage,
+};
 use serde::{Deserialize, Serialize};
 
+/// How long a transition runs: a number of engine ticks, or an amount of time on the engine's
+/// monotonic clock.  Time-based transitions run at the same speed regardless of frame rate.
+#[cfg_attr(debug_assertions, derive(Debug))]
+#[derive(Clone, Copy, PartialEq)]
+pub enum TransitionDuration {
+    Frames(u64),
+    Time(Duration),
+}
+
+impl From<Duration> for TransitionDuration {
+    fn from(duration: Duration) -> Self {
+        TransitionDuration::Time(duration)
+    }
+}
+
 pub struct TransitionQueueEntry<T> {
     pub global_frame_started: Option<usize>,
-    pub duration_frames: u64,
+    pub global_time_started: Option<Duration>,
+    pub duration: TransitionDuration,
     pub curve: EasingCurve,
     pub starting_value: T,
     pub ending_value: T,
+    /// Rate of change (in units of `T` per second) carried over from the transition this one
+    /// interrupted, which a spring starts out with rather than starting from rest
+    pub initial_velocity: f64,
+}
+
+impl<T: Interpolatable> TransitionQueueEntry<T> {
+    /// For a transition along a spring curve, the spring's motion from `starting_value` to
+    /// `ending_value`, starting out at `initial_velocity`
+    pub fn spring_motion(&self) -> Option<SpringMotion> {
+        let EasingCurve::Spring(stiffness, damping, mass) = self.curve else {
+            return None;
+        };
+        let initial_velocity = self
+            .starting_value
+            .displacement_to(&self.ending_value)
+            .filter(|distance| *distance != 0.0)
+            .map_or(0.0, |distance| self.initial_velocity / distance);
+        Some(SpringMotion::new(
+            stiffness,
+            damping,
+            mass,
+            initial_velocity,
+        ))
+    }
 }
 
 pub trait RenderContext {
     fn fill(&mut self, layer: &str, path: BezPath, brush: &PaintBrush);
     fn stroke(&mut self, layer: &str, path: BezPath, brush: &PaintBrush, width: f64);
+    /// Like `fill`, with the result blurred by `blur_radius`, the standard deviation in pixels of
+    /// the blur (as for CSS's `blur()`), e.g. for drop shadows
+    fn fill_blurred(&mut self, layer: &str, path: BezPath, brush: &PaintBrush, blur_radius: f64);
+    /// Like `stroke`, with the result blurred by `blur_radius` as for `fill_blurred`
+    fn stroke_blurred(
+        &mut self,
+        layer: &str,
+        path: BezPath,
+        brush: &PaintBrush,
+        width: f64,
+        blur_radius: f64,
+    );
     fn save(&mut self, layer: &str);
+    /// Like `save`, additionally compositing everything drawn to `layer` until the matching
+    /// `restore` at `opacity` (0.0 to 1.0), as a whole.  Renderers without layer compositing
+    /// fade each draw in the meantime instead, leaving images opaque.
+    fn save_layer(&mut self, layer: &str, opacity: f64);
     fn restore(&mut self, layer: &str);
     fn clip(&mut self, layer: &str, path: BezPath);
     fn load_image(&mut self, path: &str, image: &[u8], width: usize, height: usize);
@@ -53,9 +118,11 @@ impl<T: std::fmt::Debug> std::fmt::Debug for TransitionQueueEntry<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("TransitionQueueEntry")
             .field("global_frame_started", &self.global_frame_started)
-            .field("duration_frames", &self.duration_frames)
+            .field("global_time_started", &self.global_time_started)
+            .field("duration", &self.duration)
             .field("starting_value", &self.starting_value)
             .field("ending_value", &self.ending_value)
+            .field("initial_velocity", &self.initial_velocity)
             .finish()
     }
 }
@@ -63,6 +130,7 @@ impl<T: std::fmt::Debug> std::fmt::Debug for TransitionQueueEntry<T> {
 pub enum PropertyType {
     Literal,
     Expression,
+    Timeline,
 }
 
 /// An abstract Property that may be either: Literal,
@@ -77,17 +145,78 @@ pub trait PropertyInstance<T: Default + Clone> {
     /// Used by engine to gain access to this property's transition queue
     fn _get_transition_manager(&mut self) -> Option<&mut TransitionManager<T>>;
 
+    /// Whether this property has transitions queued or underway.  Becomes false on the tick
+    /// the last queued transition reaches its final value, when `transition_end` fires.
+    fn is_animating(&self) -> bool;
+
     /// Immediately start transitioning from current value to the provided `new_value`,
     /// clearing the transition queue before doing so
-    fn ease_to(&mut self, new_value: T, duration_frames: u64, curve: EasingCurve);
+    fn transition_to(&mut self, new_value: T, duration: TransitionDuration, curve: EasingCurve);
 
     /// Add a transition to the transition queue, which will execute
     /// after the current queue is complete.  The starting value for this new
     /// transition will be the final value upon completion of the current transition queue.
-    fn ease_to_later(&mut self, new_value: T, duration_frames: u64, curve: EasingCurve);
+    fn transition_to_later(
+        &mut self,
+        new_value: T,
+        duration: TransitionDuration,
+        curve: EasingCurve,
+    );
+
+    /// Immediately start springing toward `new_value`, keeping the current velocity if this
+    /// property is already transitioning.  Lasts until the spring settles.
+    fn spring_to(&mut self, new_value: T, stiffness: f64, damping: f64, mass: f64) {
+        let curve = EasingCurve::Spring(stiffness, damping, mass);
+        self.transition_to(new_value, Duration::ZERO.into(), curve);
+    }
+
+    /// Like [`PropertyInstance::transition_to`], over `duration_frames` engine ticks
+    fn ease_to(&mut self, new_value: T, duration_frames: u64, curve: EasingCurve) {
+        self.transition_to(
+            new_value,
+            TransitionDuration::Frames(duration_frames),
+            curve,
+        );
+    }
+
+    /// Like [`PropertyInstance::transition_to_later`], over `duration_frames` engine ticks
+    fn ease_to_later(&mut self, new_value: T, duration_frames: u64, curve: EasingCurve) {
+        self.transition_to_later(
+            new_value,
+            TransitionDuration::Frames(duration_frames),
+            curve,
+        );
+    }
+
+    /// Like [`PropertyInstance::transition_to`], over `duration_ms` milliseconds
+    fn ease_to_ms(&mut self, new_value: T, duration_ms: u64, curve: EasingCurve) {
+        let duration = Duration::from_millis(duration_ms).into();
+        self.transition_to(new_value, duration, curve);
+    }
+
+    /// Like [`PropertyInstance::transition_to_later`], over `duration_ms` milliseconds
+    fn ease_to_later_ms(&mut self, new_value: T, duration_ms: u64, curve: EasingCurve) {
+        let duration = Duration::from_millis(duration_ms).into();
+        self.transition_to_later(new_value, duration, curve);
+    }
 
     fn property_type(&self) -> PropertyType;
 
+    /// Used by engine to determine whether this property's vtable expression must be re-evaluated,
+    /// i.e. it has never been evaluated or one of the properties it read last time has since changed.
+    /// Always false for literals.
+    fn _is_dirty(&self) -> bool {
+        false
+    }
+
+    /// Used by engine to record the properties read during the most recent evaluation of this property
+    fn _set_dependencies(&mut self, _dependencies: Vec<Dependency>) {}
+
+    /// Used by engine to evaluate keyframed properties at their component's timeline playhead
+    fn _get_timeline(&self) -> Option<&PropertyTimeline<T>> {
+        None
+    }
+
     //Wishlist:
     // to_default: set back to default value
     // ease_to_default: set back to default value via interpolation
@@ -143,6 +272,13 @@ impl<T: Default + Clone + 'static> Clone for Box<dyn PropertyInstance<T>> {
                     ._get_vtable_id()
                     .expect("Cloned expression must have a v-table id"),
             )),
+            PropertyType::Timeline => Box::new(PropertyTimeline::new(
+                self.deref()
+                    ._get_timeline()
+                    .expect("Cloned timeline property must have keyframes")
+                    .keyframes()
+                    .to_vec(),
+            )),
         }
     }
 }
@@ -159,6 +295,7 @@ impl Space for Window {}
 pub struct Event<T> {
     pub args: T,
     cancelled: Rc<Cell<bool>>,
+    propagation_stopped: Rc<Cell<bool>>,
 }
 
 impl<T> Event<T> {
@@ -166,9 +303,12 @@ impl<T> Event<T> {
         Self {
             args,
             cancelled: Default::default(),
+            propagation_stopped: Default::default(),
         }
     }
 
+    /// Signals the chassis to skip its default behavior for this event (e.g. scrolling on wheel.)
+    /// Does not affect which handlers run; see `stop_propagation` for that.
     pub fn prevent_default(&self) {
         self.cancelled.set(true);
     }
@@ -176,6 +316,16 @@ impl<T> Event<T> {
     pub fn cancelled(&self) -> bool {
         self.cancelled.get()
     }
+
+    /// Prevents this event from reaching any further nodes, in either the capture or bubble phase.
+    /// Other handlers bound to the current node still run.
+    pub fn stop_propagation(&self) {
+        self.propagation_stopped.set(true);
+    }
+
+    pub fn propagation_stopped(&self) -> bool {
+        self.propagation_stopped.get()
+    }
 }
 
 impl<T> Deref for Event<T> {
@@ -279,6 +429,17 @@ pub struct KeyPress {
     pub keyboard: KeyboardEventArgs,
 }
 
+// Focus Events
+
+/// An element has received keyboard focus, e.g. by being clicked or tabbed to.
+/// Only elements with a `tab_index` can receive focus.
+#[derive(Clone)]
+pub struct Focus {}
+
+/// An element has lost keyboard focus.
+#[derive(Clone)]
+pub struct Blur {}
+
 // Mouse Events
 
 /// Common properties in mouse events.
@@ -328,6 +489,59 @@ impl From<&ModifierKeyMessage> for ModifierKey {
     }
 }
 
+/// The mouse cursor shown over an element, set with its `cursor` common property, e.g.
+/// `cursor: Cursor::Grab`.  Elements that don't set one show that of their closest ancestor.
+#[cfg_attr(debug_assertions, derive(Debug))]
+#[derive(Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(crate = "crate::serde")]
+pub enum Cursor {
+    #[default]
+    Default,
+    Pointer,
+    Text,
+    Move,
+    Grab,
+    Grabbing,
+    NotAllowed,
+    /// Resizing horizontally
+    EwResize,
+    /// Resizing vertically
+    NsResize,
+    /// Resizing along the diagonal from the bottom left to the top right
+    NeswResize,
+    /// Resizing along the diagonal from the top left to the bottom right
+    NwseResize,
+    /// Resizing a column, e.g. dragging the divider between two panes
+    ColResize,
+    /// Resizing a row
+    RowResize,
+    /// Hides the cursor
+    None,
+}
+
+impl Interpolatable for Cursor {}
+
+impl From<Cursor> for CursorMessage {
+    fn from(value: Cursor) -> Self {
+        match value {
+            Cursor::Default => CursorMessage::Default,
+            Cursor::Pointer => CursorMessage::Pointer,
+            Cursor::Text => CursorMessage::Text,
+            Cursor::Move => CursorMessage::Move,
+            Cursor::Grab => CursorMessage::Grab,
+            Cursor::Grabbing => CursorMessage::Grabbing,
+            Cursor::NotAllowed => CursorMessage::NotAllowed,
+            Cursor::EwResize => CursorMessage::EwResize,
+            Cursor::NsResize => CursorMessage::NsResize,
+            Cursor::NeswResize => CursorMessage::NeswResize,
+            Cursor::NwseResize => CursorMessage::NwseResize,
+            Cursor::ColResize => CursorMessage::ColResize,
+            Cursor::RowResize => CursorMessage::RowResize,
+            Cursor::None => CursorMessage::None,
+        }
+    }
+}
+
 /// User clicks a mouse button over an element.
 #[derive(Clone)]
 pub struct Click {
@@ -391,13 +605,14 @@ pub struct MouseUp {
     pub mouse: MouseEventArgs,
 }
 
-/// User moves the mouse onto an element.
+/// User moves the mouse onto an element, or onto one of its descendants from outside it.
+/// Doesn't bubble: each element whose hover state changed receives its own event.
 #[derive(Clone)]
 pub struct MouseOver {
     pub mouse: MouseEventArgs,
 }
 
-/// User moves the mouse away from an element.
+/// User moves the mouse away from an element and all of its descendants.  Doesn't bubble.
 #[derive(Clone)]
 pub struct MouseOut {
     pub mouse: MouseEventArgs,
@@ -409,8 +624,126 @@ pub struct ContextMenu {
     pub mouse: MouseEventArgs,
 }
 
+// Drag Events
+
+/// Common properties in drag events.  Local coordinates are in the space of the node the drag
+/// started on (the one that captured the pointer), even when handled by one of its ancestors.
+#[derive(Clone)]
+pub struct DragEventArgs {
+    /// Pointer position in window coordinates
+    pub x: f64,
+    pub y: f64,
+    /// Pointer position in the dragged node's local coordinates
+    pub local_x: f64,
+    pub local_y: f64,
+    /// Movement since the previous drag event, in window coordinates
+    pub delta_x: f64,
+    pub delta_y: f64,
+    /// Movement since the previous drag event, in the dragged node's local coordinates
+    pub local_delta_x: f64,
+    pub local_delta_y: f64,
+    /// Movement since the pointer was pressed, in window coordinates
+    pub total_delta_x: f64,
+    pub total_delta_y: f64,
+}
+
+/// User has pressed on an element and moved the pointer far enough to begin dragging it.
+#[derive(Clone)]
+pub struct DragStart {
+    pub drag: DragEventArgs,
+}
+
+/// User moves the pointer while dragging an element.  Keeps firing on the dragged element
+/// even when the pointer leaves its bounds.
+#[derive(Clone)]
+pub struct Drag {
+    pub drag: DragEventArgs,
+}
+
+/// User has released the pointer after dragging an element.
+#[derive(Clone)]
+pub struct DragEnd {
+    pub drag: DragEventArgs,
+}
+
+// Gesture Events
+
+/// User moves two fingers closer together or further apart.  Fires for every move while two
+/// or more fingers are down.
+#[derive(Clone)]
+pub struct Pinch {
+    /// Midpoint between the two fingers, in window coordinates
+    pub x: f64,
+    pub y: f64,
+    /// Distance between the fingers relative to when the second finger touched down, e.g. 2.0
+    /// once they are twice as far apart
+    pub scale: f64,
+}
+
+/// User twists two fingers around each other.  Fires for every move while two or more fingers
+/// are down.
+#[derive(Clone)]
+pub struct RotateGesture {
+    /// Midpoint between the two fingers, in window coordinates
+    pub x: f64,
+    pub y: f64,
+    /// Clockwise rotation of the line between the fingers since the second finger touched down,
+    /// in degrees
+    pub angle: f64,
+}
+
+/// User moves one or more fingers across an element after moving far enough to tell a pan
+/// from a tap.  With several fingers down, the position is their centroid.
+#[derive(Clone)]
+pub struct Pan {
+    pub x: f64,
+    pub y: f64,
+    /// Movement since the previous pan event
+    pub delta_x: f64,
+    pub delta_y: f64,
+    /// Movement since the gesture began
+    pub total_delta_x: f64,
+    pub total_delta_y: f64,
+    pub touch_count: usize,
+}
+
+/// User holds a single finger still on an element.
+#[derive(Clone)]
+pub struct LongPress {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// User quickly flicks a single finger across an element.
+#[derive(Clone)]
+pub struct Swipe {
+    pub direction: SwipeDirection,
+    /// Movement from touch down to release
+    pub delta_x: f64,
+    pub delta_y: f64,
+}
+
+#[cfg_attr(debug_assertions, derive(Debug))]
+#[derive(Clone, Copy, PartialEq)]
+pub enum SwipeDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+// Animation Events
+
+/// The transition queue of one of an element's properties has drained, leaving the property
+/// at its final value.  Doesn't bubble.
+#[derive(Clone)]
+pub struct TransitionEnd {
+    /// Name of the property, e.g. `"x"` or `"width"`
+    pub property: String,
+}
+
 /// A Size value that can be either a concrete pixel value
-/// or a percent of parent bounds.
+/// or a percent of parent bounds, or, for `width` and `height`, sized to fit the element's content.
 
 #[cfg_attr(debug_assertions, derive(Debug))]
 #[derive(Copy, Clone, Serialize, Deserialize)]
@@ -420,6 +753,9 @@ pub enum Size {
     Percent(Numeric),
     ///Pixel component, Percent component
     Combined(Numeric, Numeric),
+    /// The size of the element's content along this axis, as measured by the element (e.g. a
+    /// `Text`'s text.)  Anywhere content can't be measured, behaves like `100%`.
+    Auto,
 }
 
 impl Neg for Size {
@@ -429,6 +765,7 @@ impl Neg for Size {
             Size::Pixels(pix) => Size::Pixels(-pix),
             Size::Percent(per) => Size::Percent(-per),
             Size::Combined(pix, per) => Size::Combined(-pix, -per),
+            Size::Auto => -Size::Percent(100.into()),
         }
     }
 }
@@ -446,6 +783,7 @@ impl Add for Size {
                 pixel_component = pixel_component + *s0;
                 percent_component = percent_component + *s1;
             }
+            Size::Auto => percent_component = percent_component + Numeric::from(100),
         });
 
         Size::Combined(pixel_component, percent_component)
@@ -499,6 +837,9 @@ impl Sub for Size {
                     pixel_component = pixel_component + *s0 * Numeric::from(*multiplier);
                     percent_component = percent_component + *s1 * Numeric::from(*multiplier);
                 }
+                Size::Auto => {
+                    percent_component = percent_component + Numeric::from(100 * *multiplier)
+                }
             }
         }
 
@@ -535,6 +876,7 @@ impl Size {
     }
 }
 
+#[derive(Clone, Copy)]
 pub enum Axis {
     X,
     Y,
@@ -555,6 +897,7 @@ impl Size {
                 //first calc percent, then add pixel
                 (target_bound * (percent_component.to_float() / 100.0)) + pixel_component.to_float()
             }
+            Size::Auto => target_bound,
         }
     }
 }
@@ -587,6 +930,19 @@ pub struct CommonProperties {
     pub transform: Box<dyn PropertyInstance<Transform2D>>,
     pub width: Box<dyn PropertyInstance<Size>>,
     pub height: Box<dyn PropertyInstance<Size>>,
+    pub tab_index: Option<Box<dyn PropertyInstance<Numeric>>>,
+    /// 0.0 to 1.0.  A translucent node's content, including its descendants, is composited
+    /// offscreen and faded as a whole, so overlapping content within it doesn't show through
+    pub opacity: Option<Box<dyn PropertyInstance<Numeric>>>,
+    pub visible: Option<Box<dyn PropertyInstance<bool>>>,
+    pub pointer_events: Option<Box<dyn PropertyInstance<bool>>>,
+    pub z_index: Option<Box<dyn PropertyInstance<Numeric>>>,
+    pub cursor: Option<Box<dyn PropertyInstance<Cursor>>>,
+    pub min_width: Option<Box<dyn PropertyInstance<Size>>>,
+    pub max_width: Option<Box<dyn PropertyInstance<Size>>>,
+    pub min_height: Option<Box<dyn PropertyInstance<Size>>>,
+    pub max_height: Option<Box<dyn PropertyInstance<Size>>>,
+    pub padding: Option<Box<dyn PropertyInstance<Size>>>,
 }
 
 impl CommonProperties {
@@ -640,6 +996,10 @@ impl<T: Interpolatable> Interpolatable for Option<T> {
 pub struct TransitionManager<T> {
     pub queue: VecDeque<TransitionQueueEntry<T>>,
     pub value: Option<T>,
+    /// Rate of change of `value`, in units of `T` per second, as of the last tick
+    pub velocity: f64,
+    /// Engine time at which `value` was last computed, while transitioning
+    pub last_updated: Option<Duration>,
 }
 
 #[cfg(debug_assertions)]
@@ -648,6 +1008,8 @@ impl<T: std::fmt::Debug> std::fmt::Debug for TransitionManager<T> {
         f.debug_struct("TransitionManager")
             .field("queue", &self.queue)
             .field("value", &self.value)
+            .field("velocity", &self.velocity)
+            .field("last_updated", &self.last_updated)
             .finish()
     }
 }
@@ -657,6 +1019,8 @@ impl<T> TransitionManager<T> {
         Self {
             queue: VecDeque::new(),
             value: None,
+            velocity: 0.0,
+            last_updated: None,
         }
     }
 }
@@ -665,6 +1029,7 @@ impl<T> TransitionManager<T> {
 pub struct PropertyLiteral<T> {
     value: T,
     transition_manager: TransitionManager<T>,
+    version: PropertyVersion,
 }
 
 #[cfg(debug_assertions)]
@@ -691,15 +1056,18 @@ impl<T: Clone> PropertyLiteral<T> {
         PropertyLiteral {
             value,
             transition_manager: TransitionManager::new(),
+            version: PropertyVersion::new(),
         }
     }
 }
 impl<T: Default + Clone> PropertyInstance<T> for PropertyLiteral<T> {
     fn get(&self) -> &T {
+        self.version.track_read();
         &self.value
     }
 
     fn get_mut(&mut self) -> &mut T {
+        self.version.bump();
         &mut self.value
     }
 
@@ -709,10 +1077,12 @@ impl<T: Default + Clone> PropertyInstance<T> for PropertyLiteral<T> {
 
     fn set(&mut self, value: T) {
         self.value = value;
+        self.version.bump();
     }
 
     //FUTURE: when trait fields land in Rust, DRY this implementation vs. other <T: PropertyInstance> implementations
-    fn ease_to(&mut self, new_value: T, duration_frames: u64, curve: EasingCurve) {
+    fn transition_to(&mut self, new_value: T, duration: TransitionDuration, curve: EasingCurve) {
+        self.version.bump();
         self.transition_manager.value = Some(self.get().clone());
         let _ = &self.transition_manager.queue.clear();
         let _ = &self
@@ -720,14 +1090,21 @@ impl<T: Default + Clone> PropertyInstance<T> for PropertyLiteral<T> {
             .queue
             .push_back(TransitionQueueEntry {
                 global_frame_started: None,
-                duration_frames,
+                global_time_started: None,
+                duration,
                 curve,
                 starting_value: self.value.clone(),
                 ending_value: new_value,
+                initial_velocity: self.transition_manager.velocity,
             });
     }
 
-    fn ease_to_later(&mut self, new_value: T, duration_frames: u64, curve: EasingCurve) {
+    fn transition_to_later(
+        &mut self,
+        new_value: T,
+        duration: TransitionDuration,
+        curve: EasingCurve,
+    ) {
         if let None = self.transition_manager.value {
             //handle case where transition queue is empty -- a None value gets skipped, so populate it with Some
             self.transition_manager.value = Some(self.get().clone());
@@ -748,10 +1125,12 @@ impl<T: Default + Clone> PropertyInstance<T> for PropertyLiteral<T> {
             .queue
             .push_back(TransitionQueueEntry {
                 global_frame_started: None,
-                duration_frames,
+                global_time_started: None,
+                duration,
                 curve,
                 starting_value,
                 ending_value: new_value,
+                initial_velocity: 0.0,
             });
     }
 
@@ -763,6 +1142,10 @@ impl<T: Default + Clone> PropertyInstance<T> for PropertyLiteral<T> {
         }
     }
 
+    fn is_animating(&self) -> bool {
+        !self.transition_manager.queue.is_empty()
+    }
+
     fn property_type(&self) -> PropertyType {
         PropertyType::Literal
     }
@@ -779,75 +1162,6 @@ impl<T: std::fmt::Debug> PropertyLiteral<T> {
     }
 }
 
-pub enum EasingCurve {
-    Linear,
-    InQuad,
-    OutQuad,
-    InBack,
-    OutBack,
-    InOutBack,
-    Custom(Box<dyn Fn(f64) -> f64>),
-}
-
-struct EasingEvaluators {}
-impl EasingEvaluators {
-    fn linear(t: f64) -> f64 {
-        t
-    }
-    #[allow(dead_code)]
-    fn none(t: f64) -> f64 {
-        if t == 1.0 {
-            1.0
-        } else {
-            0.0
-        }
-    }
-    fn in_quad(t: f64) -> f64 {
-        t * t
-    }
-    fn out_quad(t: f64) -> f64 {
-        1.0 - (1.0 - t) * (1.0 - t)
-    }
-    fn in_back(t: f64) -> f64 {
-        const C1: f64 = 1.70158;
-        const C3: f64 = C1 + 1.00;
-        C3 * t * t * t - C1 * t * t
-    }
-    fn out_back(t: f64) -> f64 {
-        const C1: f64 = 1.70158;
-        const C3: f64 = C1 + 1.00;
-        1.0 + C3 * (t - 1.0).powi(3) + C1 * (t - 1.0).powi(2)
-    }
-
-    fn in_out_back(t: f64) -> f64 {
-        const C1: f64 = 1.70158;
-        const C2: f64 = C1 * 1.525;
-        if t < 0.5 {
-            ((2.0 * t).powi(2) * ((C2 + 1.0) * 2.0 * t - C2)) / 2.0
-        } else {
-            ((2.0 * t - 2.0).powi(2) * ((C2 + 1.0) * (t * 2.0 - 2.0) + C2) + 2.0) / 2.0
-        }
-    }
-}
-
-impl EasingCurve {
-    //for a time on the unit interval `t ∈ [0,1]`, given a value `t`,
-    // find the interpolated value `vt` between `v0` and `v1` given the self-contained easing curve
-    pub fn interpolate<T: Interpolatable>(&self, v0: &T, v1: &T, t: f64) -> T /*vt*/ {
-        let multiplier = match self {
-            EasingCurve::Linear => EasingEvaluators::linear(t),
-            EasingCurve::InQuad => EasingEvaluators::in_quad(t),
-            EasingCurve::OutQuad => EasingEvaluators::out_quad(t),
-            EasingCurve::InBack => EasingEvaluators::in_back(t),
-            EasingCurve::OutBack => EasingEvaluators::out_back(t),
-            EasingCurve::InOutBack => EasingEvaluators::in_out_back(t),
-            EasingCurve::Custom(evaluator) => (*evaluator)(t),
-        };
-
-        v0.interpolate(v1, multiplier)
-    }
-}
-
 pub trait Interpolatable
 where
     Self: Sized + Clone,
@@ -857,6 +1171,13 @@ where
     fn interpolate(&self, _other: &Self, _t: f64) -> Self {
         self.clone()
     }
+
+    /// The signed distance from `self` to `other`, for types that vary along a single axis,
+    /// e.g. `Some(30.0)` from `10px` to `40px`.  Lets the engine track how fast a transition is
+    /// moving, so that a spring retargeted mid-flight keeps its velocity.
+    fn displacement_to(&self, _other: &Self) -> Option<f64> {
+        None
+    }
 }
 
 impl<I: Interpolatable> Interpolatable for std::ops::Range<I> {
@@ -886,6 +1207,10 @@ impl Interpolatable for f64 {
     fn interpolate(&self, other: &f64, t: f64) -> f64 {
         self + (*other - self) * t
     }
+
+    fn displacement_to(&self, other: &f64) -> Option<f64> {
+        Some(other - self)
+    }
 }
 
 impl Interpolatable for bool {
@@ -956,10 +1281,53 @@ impl Interpolatable for i32 {
 
 impl Interpolatable for String {}
 
+/// Playback state for a component's keyframe timeline.  Each engine tick evaluates properties
+/// declared with keyframes at `playhead_position`, then advances it a frame if playing.
+#[cfg_attr(debug_assertions, derive(Debug))]
+#[derive(Clone)]
 pub struct Timeline {
     pub playhead_position: usize,
     pub frame_count: usize,
     pub is_playing: bool,
+    pub is_looping: bool,
+}
+
+impl Timeline {
+    /// Moves the playhead forward a frame if playing.  Upon reaching the last frame, wraps
+    /// back to the first if looping and otherwise stops playing.
+    pub fn advance(&mut self) {
+        if !self.is_playing {
+            return;
+        }
+        if self.playhead_position < self.frame_count {
+            self.playhead_position += 1;
+        }
+        if self.playhead_position >= self.frame_count {
+            if self.is_looping {
+                self.playhead_position = 0;
+            } else {
+                self.is_playing = false;
+            }
+        }
+    }
+
+    /// Starts playing from the playhead, rewinding first if the timeline already finished
+    pub fn play(&mut self) {
+        if self.playhead_position >= self.frame_count {
+            self.playhead_position = 0;
+        }
+        self.is_playing = true;
+    }
+
+    pub fn pause(&mut self) {
+        self.is_playing = false;
+    }
+
+    /// Moves the playhead to `frame`, clamped to the timeline's length, without changing
+    /// whether it is playing
+    pub fn seek(&mut self, frame: usize) {
+        self.playhead_position = frame.min(self.frame_count);
+    }
 }
 
 #[cfg_attr(debug_assertions, derive(Debug))]
@@ -1079,6 +1447,10 @@ impl Interpolatable for Percent {
     fn interpolate(&self, other: &Self, t: f64) -> Self {
         Self(self.0.interpolate(&other.0, t))
     }
+
+    fn displacement_to(&self, other: &Self) -> Option<f64> {
+        self.0.displacement_to(&other.0)
+    }
 }
 
 impl From<IntoableLiteral> for Rotation {
@@ -1551,6 +1923,10 @@ impl Interpolatable for Rotation {
     fn interpolate(&self, other: &Self, t: f64) -> Self {
         Self::Percent((other.to_float_0_1() - self.to_float_0_1() * t / 100.0).into())
     }
+
+    fn displacement_to(&self, other: &Self) -> Option<f64> {
+        Some(other.to_float_0_1() - self.to_float_0_1())
+    }
 }
 
 impl Rotation {
@@ -1630,12 +2006,17 @@ impl Size {
             Self::Pixels(p) => p.to_float(),
             Self::Percent(p) => parent * (p.to_float() / 100.0),
             Self::Combined(pix, per) => (parent * (per.to_float() / 100.0)) + pix.to_float(),
+            Self::Auto => parent,
         }
     }
 }
 
 impl Interpolatable for Size {
     fn interpolate(&self, other: &Self, t: f64) -> Self {
+        // `Auto` has no numeric value to blend through, so it snaps to the target
+        if matches!(self, Self::Auto) || matches!(other, Self::Auto) {
+            return *other;
+        }
         match &self {
             Self::Pixels(sp) => match other {
                 Self::Pixels(op) => Self::Pixels(*sp + ((*op - *sp) * Numeric::from(t))),
@@ -1645,6 +2026,7 @@ impl Interpolatable for Size {
                     let per = *per;
                     Self::Combined(pix, per)
                 }
+                Self::Auto => unreachable!(),
             },
             Self::Percent(sp) => match other {
                 Self::Pixels(op) => Self::Pixels(*op),
@@ -1654,6 +2036,7 @@ impl Interpolatable for Size {
                     let per = *sp + ((*per - *sp) * Numeric::from(t));
                     Self::Combined(pix, per)
                 }
+                Self::Auto => unreachable!(),
             },
             Self::Combined(pix, per) => match other {
                 Self::Pixels(op) => {
@@ -1669,7 +2052,18 @@ impl Interpolatable for Size {
                     let per = *per + ((*per0 - *per) * Numeric::from(t));
                     Self::Combined(pix, per)
                 }
+                Self::Auto => unreachable!(),
             },
+            Self::Auto => unreachable!(),
+        }
+    }
+
+    fn displacement_to(&self, other: &Self) -> Option<f64> {
+        match (self, other) {
+            (Self::Pixels(sp), Self::Pixels(op)) | (Self::Percent(sp), Self::Percent(op)) => {
+                sp.displacement_to(op)
+            }
+            _ => None,
         }
     }
 }
@@ -1694,18 +2088,22 @@ impl Mul for Size {
                     Size::Pixels(pix1) => Size::Pixels(pix0 + pix1),
                     Size::Percent(per1) => Size::Pixels(pix0 * per1),
                     Size::Combined(pix1, per1) => Size::Pixels((pix0 * per1) + pix0 + pix1),
+                    Size::Auto => Size::Pixels(pix0 * Numeric::from(100)),
                 }
             }
             Size::Percent(per0) => match rhs {
                 Size::Pixels(pix1) => Size::Pixels(per0 * pix1),
                 Size::Percent(per1) => Size::Percent(per0 * per1),
                 Size::Combined(pix1, per1) => Size::Pixels((per0 * pix1) + (per0 * per1)),
+                Size::Auto => Size::Percent(per0 * Numeric::from(100)),
             },
             Size::Combined(pix0, per0) => match rhs {
                 Size::Pixels(pix1) => Size::Pixels((pix0 * per0) + pix1),
                 Size::Percent(per1) => Size::Percent(pix0 * per0 * per1),
                 Size::Combined(pix1, per1) => Size::Pixels((pix0 * per0) + (pix1 * per1)),
+                Size::Auto => Size::Percent(pix0 * per0 * Numeric::from(100)),
             },
+            Size::Auto => Size::Percent(100.into()) * rhs,
         }
     }
 }
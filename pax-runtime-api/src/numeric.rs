@@ -42,6 +42,10 @@ impl Interpolatable for Numeric {
     fn interpolate(&self, other: &Self, t: f64) -> Self {
         Self::Float(self.to_float() + ((other.to_float() - self.to_float()) * t))
     }
+
+    fn displacement_to(&self, other: &Self) -> Option<f64> {
+        Some(other.to_float() - self.to_float())
+    }
 }
 
 impl PartialEq<f64> for Numeric {
@@ -0,0 +1,104 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+// Dependency tracking for the dirty-DAG of properties.
+//
+// Every property carries a `PropertyVersion`, bumped whenever the property is
+// written (`set`, `get_mut`, `ease_to`.)  While the engine evaluates an expression
+// (or computes layout for a node), every property read through `get()` is recorded
+// along with the version observed at read-time.  On subsequent ticks, the engine
+// compares those recorded versions against the live ones — if none have changed,
+// the cached value is still valid and re-evaluation is skipped.
+
+/// Monotonic write-counter shared between a property and anything that depends on it.
+#[derive(Default, Clone)]
+pub struct PropertyVersion(Rc<Cell<u64>>);
+
+impl PropertyVersion {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Marks this property (and, transitively, all of its dependents) as dirty
+    pub fn bump(&self) {
+        self.0.set(self.0.get().wrapping_add(1));
+    }
+
+    /// Records a read of this property into the innermost active tracking scope, if any
+    pub fn track_read(&self) {
+        TRACKING_STACK.with(|stack| {
+            if let Some(scope) = stack.borrow_mut().last_mut() {
+                if !scope.iter().any(|dep| Rc::ptr_eq(&dep.version.0, &self.0)) {
+                    scope.push(Dependency {
+                        version: self.clone(),
+                        observed: self.0.get(),
+                    });
+                }
+            }
+        });
+    }
+}
+
+#[cfg(debug_assertions)]
+impl std::fmt::Debug for PropertyVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("PropertyVersion")
+            .field(&self.0.get())
+            .finish()
+    }
+}
+
+/// A single edge in the dirty-DAG: the version of an upstream property as it was
+/// observed when a downstream value was last computed.
+#[cfg_attr(debug_assertions, derive(Debug))]
+#[derive(Clone)]
+pub struct Dependency {
+    version: PropertyVersion,
+    observed: u64,
+}
+
+impl Dependency {
+    pub fn has_changed(&self) -> bool {
+        self.version.0.get() != self.observed
+    }
+}
+
+/// Returns true if any of the given dependencies has been written since it was recorded
+pub fn any_changed(dependencies: &[Dependency]) -> bool {
+    dependencies.iter().any(Dependency::has_changed)
+}
+
+thread_local! {
+    static TRACKING_STACK: RefCell<Vec<Vec<Dependency>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Runs `f`, returning its result along with the set of properties read during its execution.
+/// Scopes nest: reads are attributed only to the innermost active scope.
+pub fn track_dependencies<R>(f: impl FnOnce() -> R) -> (R, Vec<Dependency>) {
+    TRACKING_STACK.with(|stack| stack.borrow_mut().push(Vec::new()));
+    let ret = f();
+    let dependencies = TRACKING_STACK.with(|stack| stack.borrow_mut().pop().unwrap_or_default());
+    (ret, dependencies)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expressions::PropertyExpression;
+    use crate::{PropertyInstance, PropertyLiteral};
+
+    #[test]
+    fn expression_is_dirty_only_after_dependency_changes() {
+        let mut source = PropertyLiteral::new(1);
+        let mut expr: PropertyExpression<i32> = PropertyExpression::new(0);
+        assert!(expr._is_dirty());
+
+        let (value, dependencies) = track_dependencies(|| *source.get() + 1);
+        expr.set(value);
+        expr._set_dependencies(dependencies);
+        assert!(!expr._is_dirty());
+
+        source.set(5);
+        assert!(expr._is_dirty());
+    }
+}
@@ -0,0 +1,230 @@
+use std::cell::Cell;
+
+use crate::reactivity::PropertyVersion;
+use crate::{
+    EasingCurve, Interpolatable, PropertyInstance, PropertyType, TransitionDuration,
+    TransitionManager, TransitionQueueEntry,
+};
+
+/// A value that a keyframed property reaches at `frame` of its component's timeline.  `curve`
+/// eases the approach from the previous keyframe into this one.
+#[cfg_attr(debug_assertions, derive(Debug))]
+#[derive(Clone)]
+pub struct Keyframe<T> {
+    pub frame: usize,
+    pub value: T,
+    pub curve: EasingCurve,
+}
+
+impl<T> Keyframe<T> {
+    pub fn new(frame: usize, value: T, curve: EasingCurve) -> Self {
+        Self {
+            frame,
+            value,
+            curve,
+        }
+    }
+}
+
+// The `Timeline` form of a property — holds a set of keyframes, and a
+// "register" of the value most recently interpolated between them at the
+// containing component's playhead.  The engine only re-evaluates (and thus
+// only marks the property as changed) when the playhead has moved.
+pub struct PropertyTimeline<T> {
+    keyframes: Vec<Keyframe<T>>,
+    value: T,
+    evaluated_at: Cell<Option<usize>>,
+    transition_manager: TransitionManager<T>,
+    version: PropertyVersion,
+}
+
+#[cfg(debug_assertions)]
+impl<T: std::fmt::Debug> std::fmt::Debug for PropertyTimeline<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PropertyTimeline")
+            .field("keyframes", &self.keyframes)
+            .field("value", &self.value)
+            .field("transition_manager", &self.transition_manager)
+            .finish()
+    }
+}
+
+impl<T> From<PropertyTimeline<T>> for Box<dyn PropertyInstance<T>>
+where
+    T: Default + Clone + 'static,
+{
+    fn from(property: PropertyTimeline<T>) -> Self {
+        Box::new(property)
+    }
+}
+
+impl<T: Default + Clone> PropertyTimeline<T> {
+    /// Keyframes may be passed in any order.  Until first evaluated, the property holds the
+    /// value of its earliest keyframe.
+    pub fn new(mut keyframes: Vec<Keyframe<T>>) -> Self {
+        keyframes.sort_by_key(|keyframe| keyframe.frame);
+        let value = keyframes
+            .first()
+            .map(|keyframe| keyframe.value.clone())
+            .unwrap_or_default();
+        PropertyTimeline {
+            keyframes,
+            value,
+            evaluated_at: Cell::new(None),
+            transition_manager: TransitionManager::new(),
+            version: PropertyVersion::new(),
+        }
+    }
+
+    pub fn keyframes(&self) -> &[Keyframe<T>] {
+        &self.keyframes
+    }
+}
+
+impl<T: Default + Clone + Interpolatable> PropertyTimeline<T> {
+    /// The value at frame `playhead`, interpolated between the keyframes on either side of it.
+    /// Before the first keyframe and after the last, their values are held.
+    pub fn value_at(&self, playhead: usize) -> T {
+        let next = self.keyframes.iter().position(|k| k.frame > playhead);
+        match next {
+            Some(0) => self.keyframes[0].value.clone(),
+            Some(i) => {
+                let (prev, next) = (&self.keyframes[i - 1], &self.keyframes[i]);
+                let progress = (playhead - prev.frame) as f64 / (next.frame - prev.frame) as f64;
+                prev.value
+                    .interpolate(&next.value, next.curve.evaluate(progress))
+            }
+            None => self
+                .keyframes
+                .last()
+                .map(|keyframe| keyframe.value.clone())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Used by engine to evaluate this property at `playhead`.  Returns `None` if it was
+    /// already evaluated there, so that unmoved timelines don't dirty their dependents.
+    pub fn evaluate(&self, playhead: usize) -> Option<T> {
+        if self.evaluated_at.replace(Some(playhead)) == Some(playhead) {
+            return None;
+        }
+        Some(self.value_at(playhead))
+    }
+}
+
+impl<T: Default + Clone> PropertyInstance<T> for PropertyTimeline<T> {
+    fn get(&self) -> &T {
+        self.version.track_read();
+        &self.value
+    }
+
+    fn get_mut(&mut self) -> &mut T {
+        self.version.bump();
+        &mut self.value
+    }
+
+    fn _get_vtable_id(&self) -> Option<usize> {
+        None
+    }
+
+    fn set(&mut self, value: T) {
+        self.value = value;
+        self.version.bump();
+    }
+
+    //FUTURE: when trait fields land, DRY this implementation vs. other <T: PropertyInstance> implementations
+    fn transition_to(&mut self, new_value: T, duration: TransitionDuration, curve: EasingCurve) {
+        self.version.bump();
+        self.transition_manager.value = Some(self.get().clone());
+        self.transition_manager.queue.clear();
+        self.transition_manager
+            .queue
+            .push_back(TransitionQueueEntry {
+                global_frame_started: None,
+                global_time_started: None,
+                duration,
+                curve,
+                starting_value: self.value.clone(),
+                ending_value: new_value,
+                initial_velocity: self.transition_manager.velocity,
+            });
+    }
+
+    fn transition_to_later(
+        &mut self,
+        new_value: T,
+        duration: TransitionDuration,
+        curve: EasingCurve,
+    ) {
+        if self.transition_manager.value.is_none() {
+            //handle case where transition queue is empty -- a None value gets skipped, so populate it with Some
+            self.transition_manager.value = Some(self.get().clone());
+        }
+        let starting_value = match self.transition_manager.queue.back() {
+            Some(last) => last.ending_value.clone(),
+            None => self.value.clone(),
+        };
+        self.transition_manager
+            .queue
+            .push_back(TransitionQueueEntry {
+                global_frame_started: None,
+                global_time_started: None,
+                duration,
+                curve,
+                starting_value,
+                ending_value: new_value,
+                initial_velocity: 0.0,
+            });
+    }
+
+    fn _get_transition_manager(&mut self) -> Option<&mut TransitionManager<T>> {
+        if self.transition_manager.value.is_none() {
+            None
+        } else {
+            Some(&mut self.transition_manager)
+        }
+    }
+
+    fn is_animating(&self) -> bool {
+        !self.transition_manager.queue.is_empty()
+    }
+
+    fn property_type(&self) -> PropertyType {
+        PropertyType::Timeline
+    }
+
+    fn _get_timeline(&self) -> Option<&PropertyTimeline<T>> {
+        Some(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_at() {
+        let timeline = PropertyTimeline::new(vec![
+            Keyframe::new(20, 30.0, EasingCurve::Linear),
+            Keyframe::new(10, 10.0, EasingCurve::Linear),
+            Keyframe::new(40, 0.0, EasingCurve::OutQuad),
+        ]);
+        assert_eq!(*timeline.get(), 10.0);
+        assert_eq!(timeline.value_at(0), 10.0);
+        assert_eq!(timeline.value_at(15), 20.0);
+        assert_eq!(timeline.value_at(20), 30.0);
+        assert_eq!(timeline.value_at(30), 30.0 * 0.25);
+        assert_eq!(timeline.value_at(100), 0.0);
+    }
+
+    #[test]
+    fn test_evaluate_only_when_moved() {
+        let timeline = PropertyTimeline::new(vec![
+            Keyframe::new(0, 0.0, EasingCurve::Linear),
+            Keyframe::new(10, 1.0, EasingCurve::Linear),
+        ]);
+        assert_eq!(timeline.evaluate(5), Some(0.5));
+        assert_eq!(timeline.evaluate(5), None);
+        assert_eq!(timeline.evaluate(10), Some(1.0));
+    }
+}
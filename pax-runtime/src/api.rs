@@ -1,10 +1,13 @@
+use crate::node_interface::NodeInterface;
 use crate::RuntimeContext;
 pub use pax_runtime_api::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
 #[cfg(feature = "designtime")]
 use {
-    crate::api::math::Point2, crate::node_interface::NodeInterface,
-    pax_designtime::DesigntimeManager, pax_manifest::UniqueTemplateNodeIdentifier,
-    std::cell::RefCell, std::rc::Rc,
+    crate::api::math::Point2, pax_designtime::DesigntimeManager,
+    pax_manifest::UniqueTemplateNodeIdentifier,
 };
 
 #[derive(Clone)]
@@ -12,17 +15,119 @@ use {
 pub struct NodeContext<'a> {
     /// The current global engine tick count
     pub frames_elapsed: usize,
+    /// Monotonic time since the engine's first tick; unlike `frames_elapsed`, independent of
+    /// frame rate
+    pub time_elapsed: Duration,
     /// The bounds of this element's immediate container (parent) in px
     pub bounds_parent: (f64, f64),
     /// The bounds of this element in px
     pub bounds_self: (f64, f64),
+    /// The bounds this element's children are laid out in, i.e. `bounds_self` less its
+    /// `padding`, in px
+    pub bounds_content: (f64, f64),
     /// Borrow of the RuntimeContext, used at least for exposing raycasting to userland
     pub(crate) runtime_context: &'a RuntimeContext,
+    /// The node whose handler is running
+    pub(crate) current_target_id: u32,
+    /// The node an event was originally dispatched to; differs from `current_target_id` while
+    /// an event is being captured or bubbled through ancestors
+    pub(crate) target_id: u32,
+    /// The timeline of the component this handler belongs to, if it declares keyframes
+    pub(crate) timeline: Option<Rc<RefCell<Timeline>>>,
 
     #[cfg(feature = "designtime")]
     pub designtime: Rc<RefCell<DesigntimeManager>>,
 }
 
+impl NodeContext<'_> {
+    /// The node whose handler is currently running, i.e. the one this handler was bound to
+    pub fn current_target(&self) -> Option<NodeInterface> {
+        let node = self
+            .runtime_context
+            .node_cache
+            .get(&self.current_target_id)?;
+        Some(Rc::clone(node).into())
+    }
+
+    /// The node an event was originally dispatched to, e.g. the topmost node beneath a click.
+    /// For handlers run outside of event dispatch (e.g. `@tick`), this is the current node.
+    pub fn target(&self) -> Option<NodeInterface> {
+        let node = self.runtime_context.node_cache.get(&self.target_id)?;
+        Some(Rc::clone(node).into())
+    }
+
+    /// Whether the running handler was bound to the node the event was dispatched to,
+    /// rather than to one of its ancestors
+    pub fn is_target(&self) -> bool {
+        self.current_target_id == self.target_id
+    }
+
+    /// Plays the keyframes of this handler's component from the current frame, or from the
+    /// first if the timeline already finished.  No-op for components without keyframes.
+    pub fn play_timeline(&self) {
+        if let Some(timeline) = &self.timeline {
+            timeline.borrow_mut().play();
+        }
+    }
+
+    /// Stops the timeline of this handler's component at the current frame
+    pub fn pause_timeline(&self) {
+        if let Some(timeline) = &self.timeline {
+            timeline.borrow_mut().pause();
+        }
+    }
+
+    /// Moves the timeline of this handler's component to `frame`, clamped to its length
+    pub fn seek_timeline(&self, frame: usize) {
+        if let Some(timeline) = &self.timeline {
+            timeline.borrow_mut().seek(frame);
+        }
+    }
+
+    /// Sets whether the timeline of this handler's component restarts upon finishing
+    pub fn set_timeline_looping(&self, looping: bool) {
+        if let Some(timeline) = &self.timeline {
+            timeline.borrow_mut().is_looping = looping;
+        }
+    }
+
+    /// The current frame of the timeline of this handler's component, if it has one
+    pub fn timeline_playhead(&self) -> Option<usize> {
+        let timeline = self.timeline.as_ref()?;
+        let playhead = timeline.borrow().playhead_position;
+        Some(playhead)
+    }
+
+    pub fn is_timeline_playing(&self) -> bool {
+        self.timeline
+            .as_ref()
+            .is_some_and(|timeline| timeline.borrow().is_playing)
+    }
+
+    /// The sizes of this component's slot children (flattened through `if` and `for`) when laid
+    /// out in `bounds`, with `Auto` sizes measured.  Slot children are updated after `@tick`
+    /// runs, so these are as of the previous tick.
+    pub fn slot_children_sizes(&self, bounds: (f64, f64)) -> Vec<(f64, f64)> {
+        let Some(node) = self.runtime_context.node_cache.get(&self.current_target_id) else {
+            return Vec::new();
+        };
+        let slot_children = node.expanded_and_flattened_slot_children.borrow();
+        slot_children
+            .iter()
+            .flatten()
+            .map(|child| child.get_size_computed(bounds))
+            .collect()
+    }
+
+    /// Reports the size of this component's content, which its `Auto` width and height resolve
+    /// to, e.g. the extent of the slot children it arranges
+    pub fn set_content_size(&self, size: (f64, f64)) {
+        if let Some(node) = self.runtime_context.node_cache.get(&self.current_target_id) {
+            node.set_native_measure(size);
+        }
+    }
+}
+
 #[cfg(feature = "designtime")]
 impl NodeContext<'_> {
     pub fn raycast(&self, point: Point2<Window>) -> Vec<NodeInterface> {
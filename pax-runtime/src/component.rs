@@ -15,7 +15,9 @@ use crate::{
 /// properties attached to each of Repeat's virtual nodes.
 pub struct ComponentInstance {
     pub template: InstanceNodePtrList,
-    pub timeline: Option<Rc<RefCell<Timeline>>>,
+    /// Initial state of the timeline each expanded instance of this component plays its
+    /// keyframed properties on, if it declares any keyframes
+    pub timeline: Option<Timeline>,
     pub compute_properties_fn: Box<dyn Fn(&ExpandedNode, &ExpressionTable, &Globals)>,
     base: BaseInstance,
 }
@@ -31,6 +33,7 @@ impl InstanceNode for ComponentInstance {
         let template = component_template.unwrap_or_default();
 
         let compute_properties_fn = args.compute_properties_fn.take();
+        let timeline = args.timeline.take();
         let base = BaseInstance::new(
             args,
             InstanceFlags {
@@ -45,7 +48,7 @@ impl InstanceNode for ComponentInstance {
             template,
             compute_properties_fn: compute_properties_fn
                 .expect("must pass a compute_properties_fn to a Component instance"),
-            timeline: None,
+            timeline,
         })
     }
 
@@ -77,7 +80,18 @@ impl InstanceNode for ComponentInstance {
                 Some(containing_component.create_children_detached(children_with_env, context));
         }
 
-        let new_env = expanded_node.stack.push(&expanded_node.properties.borrow());
+        let timeline = self.timeline.as_ref().map(|prototype| {
+            let mut timeline = expanded_node.timeline.borrow_mut();
+            let timeline = timeline.get_or_insert_with(|| {
+                let timeline = Rc::new(RefCell::new(prototype.clone()));
+                context.register_timeline(&timeline);
+                timeline
+            });
+            Rc::clone(timeline)
+        });
+        let new_env = expanded_node
+            .stack
+            .push_component(&expanded_node.properties.borrow(), timeline);
         let children = self.template.borrow();
         let children_with_envs = children.iter().cloned().zip(iter::repeat(new_env));
         expanded_node.set_children(children_with_envs, context);
@@ -10,7 +10,9 @@ use crate::{
 /// subtree of a component template to be rendered conditionally,
 /// based on the value of the property `boolean_expression`.
 /// The Pax compiler handles ConditionalInstance specially
-/// with the `if` syntax in templates.
+/// with the `if` syntax in templates.  An `else` (or `else if`) is another
+/// Conditional, kept as the last child and rendered in place of the others
+/// while `boolean_expression` is false.
 pub struct ConditionalInstance {
     base: BaseInstance,
 }
@@ -19,6 +21,8 @@ pub struct ConditionalInstance {
 #[derive(Default)]
 pub struct ConditionalProperties {
     pub boolean_expression: Box<dyn crate::api::PropertyInstance<bool>>,
+    /// Whether the last child is an `else` branch rather than one of the `if`'s children
+    pub has_else: bool,
     last_boolean_expression: Option<bool>,
 }
 
@@ -41,7 +45,7 @@ impl InstanceNode for ConditionalInstance {
     }
 
     fn update(self: Rc<Self>, expanded_node: &Rc<ExpandedNode>, context: &mut RuntimeContext) {
-        let (should_update, active) =
+        let (should_update, active, has_else) =
             expanded_node.with_properties_unwrapped(|properties: &mut ConditionalProperties| {
                 handle_vtable_update(
                     context.expression_table(),
@@ -52,18 +56,24 @@ impl InstanceNode for ConditionalInstance {
                 let val = Some(*properties.boolean_expression.get());
                 let update_children = properties.last_boolean_expression != val;
                 properties.last_boolean_expression = val;
-                (update_children, *properties.boolean_expression.get())
+                (
+                    update_children,
+                    *properties.boolean_expression.get(),
+                    properties.has_else,
+                )
             });
 
         if should_update {
-            if active {
-                let env = Rc::clone(&expanded_node.stack);
-                let children = self.base().get_instance_children().borrow();
-                let children_with_envs = children.iter().cloned().zip(iter::repeat(env));
-                expanded_node.set_children(children_with_envs, context);
+            let env = Rc::clone(&expanded_node.stack);
+            let children = self.base().get_instance_children().borrow();
+            let (branch, else_branch) = if has_else {
+                children.split_at(children.len() - 1)
             } else {
-                expanded_node.set_children(iter::empty(), context);
-            }
+                (&children[..], &[][..])
+            };
+            let shown = if active { branch } else { else_branch };
+            let children_with_envs = shown.iter().cloned().zip(iter::repeat(env));
+            expanded_node.set_children(children_with_envs, context);
         }
     }
 
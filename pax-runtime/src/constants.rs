@@ -6,6 +6,8 @@ pub const TOUCH_END_HANDLERS: &str = "touch_end";
 pub const KEY_DOWN_HANDLERS: &str = "key_down";
 pub const KEY_UP_HANDLERS: &str = "key_up";
 pub const KEY_PRESS_HANDLERS: &str = "key_press";
+pub const FOCUS_HANDLERS: &str = "focus";
+pub const BLUR_HANDLERS: &str = "blur";
 pub const CHECKBOX_CHANGE_HANDLERS: &str = "checkbox_change";
 pub const BUTTON_CLICK_HANDLERS: &str = "button_click";
 pub const TEXTBOX_CHANGE_HANDLERS: &str = "textbox_change";
@@ -19,6 +21,19 @@ pub const MOUSE_OVER_HANDLERS: &str = "mouse_over";
 pub const MOUSE_OUT_HANDLERS: &str = "mouse_out";
 pub const DOUBLE_CLICK_HANDLERS: &str = "double_click";
 pub const CONTEXT_MENU_HANDLERS: &str = "context_menu";
+pub const DRAG_START_HANDLERS: &str = "drag_start";
+pub const DRAG_HANDLERS: &str = "drag";
+pub const DRAG_END_HANDLERS: &str = "drag_end";
+pub const PINCH_HANDLERS: &str = "pinch";
+pub const ROTATE_GESTURE_HANDLERS: &str = "rotate_gesture";
+pub const PAN_HANDLERS: &str = "pan";
+pub const LONG_PRESS_HANDLERS: &str = "long_press";
+pub const SWIPE_HANDLERS: &str = "swipe";
+pub const TRANSITION_END_HANDLERS: &str = "transition_end";
 pub const WHEEL_HANDLERS: &str = "wheel";
 pub const PRE_RENDER_HANDLERS: &str = "pre_render";
 pub const MOUNT_HANDLERS: &str = "mount";
+
+/// Appended to an event's handler key to register a handler for that event's capture phase,
+/// e.g. `@click_capture`
+pub const CAPTURE_SUFFIX: &str = "_capture";
@@ -6,7 +6,11 @@ use crate::api::PropertyInstance;
 use crate::{ExpressionTable, Globals, RuntimePropertiesStackFrame};
 
 /// Manages vtable updates (if necessary) for a given `dyn PropertyInstance`.
-/// Is a no-op for `PropertyLiteral`s, and mutates (by calling `.set`) `PropertyExpression` instances.
+/// Is a no-op for `PropertyLiteral`s, and mutates (by calling `.set`) `PropertyExpression` instances,
+/// as well as `PropertyTimeline` instances whose timeline's playhead has moved.
+/// Expressions are only re-evaluated when dirty, i.e. when one of the properties they read
+/// during their last evaluation has since been written.
+/// Returns whether this update finished the property's last queued transition.
 /// # Examples
 /// ```text
 /// handle_vtable_update!(ptc, self.height, Size);
@@ -16,11 +20,17 @@ pub fn handle_vtable_update<V: Default + Clone + Interpolatable + 'static>(
     stack: &Rc<RuntimePropertiesStackFrame>,
     property: &mut Box<dyn PropertyInstance<V>>,
     globals: &Globals,
-) {
+) -> bool {
+    let was_animating = property.is_animating();
     if let Some(vtable_id) = property._get_vtable_id() {
-        let new_value_wrapped: Box<dyn Any> = table.compute_vtable_value(&stack, vtable_id);
+        if !property._is_dirty() {
+            return false;
+        }
+        let (new_value_wrapped, dependencies): (Box<dyn Any>, _) =
+            table.compute_tracked_vtable_value(&stack, vtable_id);
         if let Ok(downcast_value) = new_value_wrapped.downcast::<V>() {
             property.set(*downcast_value);
+            property._set_dependencies(dependencies);
         } else {
             panic!(
                 "property has an unexpected type for vtable id {}",
@@ -31,8 +41,10 @@ pub fn handle_vtable_update<V: Default + Clone + Interpolatable + 'static>(
         table.compute_eased_value(property._get_transition_manager(), globals)
     {
         property.set(new_value);
-    } else {
+    } else if let Some(new_value) = table.compute_timeline_value(property._get_timeline(), stack) {
+        property.set(new_value);
     }
+    was_animating && !property.is_animating()
 }
 
 /// Does same as [`handle_vtable_update`], but manages case (as a no-op) where the property is wrapped in an outer Option,
@@ -47,8 +59,10 @@ pub fn handle_vtable_update_optional<V: Default + Clone + Interpolatable + 'stat
     stack: &Rc<RuntimePropertiesStackFrame>,
     optional_property: Option<&mut Box<dyn PropertyInstance<V>>>,
     globals: &Globals,
-) {
+) -> bool {
     if let Some(property) = optional_property {
-        handle_vtable_update(table, stack, property, globals);
+        handle_vtable_update(table, stack, property, globals)
+    } else {
+        false
     }
 }
@@ -0,0 +1,133 @@
+use std::rc::{Rc, Weak};
+
+use crate::api::math::{Point2, Transform2};
+use crate::api::{Drag, DragEnd, DragEventArgs, DragStart, Window};
+use crate::{ExpandedNode, PaxEngine};
+
+/// Distance in px the pointer must travel after being pressed before a drag starts, so that
+/// slightly unsteady clicks and taps aren't treated as drags
+const DRAG_THRESHOLD: f64 = 3.0;
+
+/// A source of pointer input that can press, move and release
+#[cfg_attr(debug_assertions, derive(Debug))]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pointer {
+    Mouse,
+    /// A single touch point, by `Touch::identifier`
+    Touch(i64),
+}
+
+/// A pressed pointer and the node it was pressed on.  While captured, the pointer's drag events
+/// are delivered to that node regardless of where the pointer is; its raw moves and release still
+/// go to the node beneath it.
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub(crate) struct PointerCapture {
+    node: Weak<ExpandedNode>,
+    start: Point2<Window>,
+    last: Point2<Window>,
+    dragging: bool,
+}
+
+impl PaxEngine {
+    /// Captures `pointer` for `node`, e.g. on mouse down, so that `node` receives the drag events
+    /// of subsequent moves until the pointer is released
+    pub fn capture_pointer(
+        &mut self,
+        pointer: Pointer,
+        node: &Rc<ExpandedNode>,
+        at: Point2<Window>,
+    ) {
+        self.runtime_context.pointer_captures.insert(
+            pointer,
+            PointerCapture {
+                node: Rc::downgrade(node),
+                start: at,
+                last: at,
+                dragging: false,
+            },
+        );
+    }
+
+    /// The node that has captured `pointer`, if any
+    pub fn captured_node(&self, pointer: Pointer) -> Option<Rc<ExpandedNode>> {
+        self.runtime_context
+            .pointer_captures
+            .get(&pointer)
+            .and_then(|capture| capture.node.upgrade())
+    }
+
+    /// Moves a captured pointer, firing `drag_start` once it has travelled past the drag
+    /// threshold and `drag` for every move thereafter.  Returns whether any handler called
+    /// `prevent_default`.
+    pub(crate) fn move_captured_pointer(&mut self, pointer: Pointer, at: Point2<Window>) -> bool {
+        let Some(node) = self.captured_node(pointer) else {
+            return false;
+        };
+        let capture = self
+            .runtime_context
+            .pointer_captures
+            .get_mut(&pointer)
+            .unwrap();
+        let started = !capture.dragging && (at - capture.start).length() > DRAG_THRESHOLD;
+        if !capture.dragging && !started {
+            return false;
+        }
+        capture.dragging = true;
+        let args = drag_args(&node, capture, at);
+        capture.last = at;
+
+        let ctx = &self.runtime_context;
+        let mut prevent_default = false;
+        if started {
+            prevent_default |= node.dispatch_drag_start(DragStart { drag: args.clone() }, ctx);
+        }
+        prevent_default |= node.dispatch_drag(Drag { drag: args }, ctx);
+        prevent_default
+    }
+
+    /// Releases a captured pointer, firing `drag_end` if it was dragging.  Returns whether any
+    /// handler called `prevent_default`.
+    pub(crate) fn release_captured_pointer(
+        &mut self,
+        pointer: Pointer,
+        at: Point2<Window>,
+    ) -> bool {
+        let Some(capture) = self.runtime_context.pointer_captures.remove(&pointer) else {
+            return false;
+        };
+        if !capture.dragging {
+            return false;
+        }
+        let Some(node) = capture.node.upgrade() else {
+            return false;
+        };
+        let args = drag_args(&node, &capture, at);
+        let ctx = &self.runtime_context;
+        node.dispatch_drag_end(DragEnd { drag: args }, ctx)
+    }
+}
+
+fn drag_args(node: &ExpandedNode, capture: &PointerCapture, at: Point2<Window>) -> DragEventArgs {
+    let to_local = node
+        .layout_properties
+        .borrow()
+        .as_ref()
+        .map(|layout| layout.computed_tab.transform.inverse())
+        .unwrap_or_else(Transform2::identity);
+    let local = to_local * at;
+    let delta = at - capture.last;
+    let local_delta = to_local * delta;
+    let total_delta = at - capture.start;
+    DragEventArgs {
+        x: at.x,
+        y: at.y,
+        local_x: local.x,
+        local_y: local.y,
+        delta_x: delta.x,
+        delta_y: delta.y,
+        local_delta_x: local_delta.x,
+        local_delta_y: local_delta.y,
+        total_delta_x: total_delta.x,
+        total_delta_y: total_delta.y,
+    }
+}
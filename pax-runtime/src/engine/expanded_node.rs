@@ -1,31 +1,38 @@
 use pax_runtime_api::TextInput;
 
-use crate::api::math::Point2;
+use crate::api::math::{Point2, Transform2, Vector2};
 use crate::constants::{
-    BUTTON_CLICK_HANDLERS, CHECKBOX_CHANGE_HANDLERS, CLAP_HANDLERS, CLICK_HANDLERS,
-    CONTEXT_MENU_HANDLERS, DOUBLE_CLICK_HANDLERS, KEY_DOWN_HANDLERS, KEY_PRESS_HANDLERS,
-    KEY_UP_HANDLERS, MOUSE_DOWN_HANDLERS, MOUSE_MOVE_HANDLERS, MOUSE_OUT_HANDLERS,
-    MOUSE_OVER_HANDLERS, MOUSE_UP_HANDLERS, SCROLL_HANDLERS, TEXTBOX_CHANGE_HANDLERS,
-    TEXTBOX_INPUT_HANDLERS, TEXT_INPUT_HANDLERS, TOUCH_END_HANDLERS, TOUCH_MOVE_HANDLERS,
-    TOUCH_START_HANDLERS, WHEEL_HANDLERS,
+    BLUR_HANDLERS, BUTTON_CLICK_HANDLERS, CAPTURE_SUFFIX, CHECKBOX_CHANGE_HANDLERS, CLAP_HANDLERS,
+    CLICK_HANDLERS, CONTEXT_MENU_HANDLERS, DOUBLE_CLICK_HANDLERS, DRAG_END_HANDLERS, DRAG_HANDLERS,
+    DRAG_START_HANDLERS, FOCUS_HANDLERS, KEY_DOWN_HANDLERS, KEY_PRESS_HANDLERS, KEY_UP_HANDLERS,
+    LONG_PRESS_HANDLERS, MOUSE_DOWN_HANDLERS, MOUSE_MOVE_HANDLERS, MOUSE_OUT_HANDLERS,
+    MOUSE_OVER_HANDLERS, MOUSE_UP_HANDLERS, PAN_HANDLERS, PINCH_HANDLERS, ROTATE_GESTURE_HANDLERS,
+    SCROLL_HANDLERS, SWIPE_HANDLERS, TEXTBOX_CHANGE_HANDLERS, TEXTBOX_INPUT_HANDLERS,
+    TEXT_INPUT_HANDLERS, TOUCH_END_HANDLERS, TOUCH_MOVE_HANDLERS, TOUCH_START_HANDLERS,
+    TRANSITION_END_HANDLERS, WHEEL_HANDLERS,
 };
-use crate::{properties, Globals};
+use crate::properties;
 #[cfg(debug_assertions)]
 use core::fmt;
+use kurbo::{Affine, BezPath};
 use std::any::Any;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
 use std::rc::{Rc, Weak};
 
+use crate::api::reactivity::{self, Dependency, PropertyVersion};
 use crate::api::{
-    Axis, ButtonClick, CheckboxChange, Clap, Click, CommonProperties, ContextMenu, DoubleClick,
-    Event, KeyDown, KeyPress, KeyUp, MouseDown, MouseMove, MouseOut, MouseOver, MouseUp,
-    NodeContext, RenderContext, Scroll, Size, TextboxChange, TextboxInput, TouchEnd, TouchMove,
-    TouchStart, Wheel, Window,
+    Axis, Blur, ButtonClick, CheckboxChange, Clap, Click, CommonProperties, ContextMenu, Cursor,
+    DoubleClick, Drag, DragEnd, DragStart, Event, Focus, KeyDown, KeyPress, KeyUp, LongPress,
+    MouseDown, MouseMove, MouseOut, MouseOver, MouseUp, NodeContext, Pan, Pinch, PropertyInstance,
+    RenderContext, RotateGesture, Scroll, Size, Swipe, TextboxChange, TextboxInput, Timeline,
+    TouchEnd, TouchMove, TouchStart, TransitionEnd, Wheel, Window,
 };
 
 use crate::{
-    compute_tab, ComponentInstance, HandlerLocation, InstanceNode, InstanceNodePtr,
-    PropertiesComputable, RuntimeContext, RuntimePropertiesStackFrame, TransformAndBounds,
+    compute_content_tab, compute_tab, ComponentInstance, HandlerLocation, InstanceNode,
+    InstanceNodePtr, PropertiesComputable, RuntimeContext, RuntimePropertiesStackFrame,
+    TransformAndBounds,
 };
 
 pub struct ExpandedNode {
@@ -54,6 +61,9 @@ pub struct ExpandedNode {
 
     /// Pointers to the ExpandedNode beneath this one.  Used for e.g. rendering recursion.
     pub children: RefCell<Vec<Rc<ExpandedNode>>>,
+    /// Bumped whenever `children` changes, so that layout measuring them (e.g. of an `Auto`
+    /// sized `Group` around a `for`) is recomputed
+    children_version: PropertyVersion,
 
     /// Each ExpandedNode has a unique "stamp" of computed properties
     pub properties: RefCell<Rc<RefCell<dyn Any>>>,
@@ -66,6 +76,10 @@ pub struct ExpandedNode {
     /// would be part of render pass?
     pub layout_properties: RefCell<Option<LayoutProperties>>,
 
+    /// For items of a layout container like `Flex`, the cell the container assigned this node,
+    /// which it fills and is laid out against in place of its parent's bounds
+    pub layout_cell: RefCell<Option<TransformAndBounds>>,
+
     /// For component instances only, tracks the expanded slot_children in it's
     /// non-collapsed form (repeat and conditionals still present). This allows
     /// repeat/conditionals to update their children (handled in component.rs
@@ -88,61 +102,48 @@ pub struct ExpandedNode {
     /// Occlusion layer for this node. Used by canvas elements to decide what canvas to draw on, and
     /// by native elements to move to the correct native layer.
     pub occlusion_id: RefCell<u32>,
-}
 
-macro_rules! dispatch_event_handler {
-    ($fn_name:ident, $arg_type:ty, $handler_key:ident, $recurse:expr) => {
-        pub fn $fn_name(&self, args: $arg_type, globals: &Globals, ctx: &RuntimeContext) -> bool {
-            let event = Event::new(args.clone());
-            if let Some(registry) = self.instance_node.borrow().base().get_handler_registry() {
-                let component_properties = if let Some(cc) = self.containing_component.upgrade() {
-                    Rc::clone(&cc.properties.borrow())
-                } else {
-                    Rc::clone(&self.properties.borrow())
-                };
+    /// For component instances declaring keyframes only, the timeline their template's keyframed
+    /// properties are evaluated at.  Controlled from handlers through `NodeContext`.
+    pub timeline: RefCell<Option<Rc<RefCell<Timeline>>>>,
 
-                let comp_props = self.layout_properties.borrow();
-                let bounds_self = comp_props.as_ref().unwrap().computed_tab.bounds;
-                let bounds_parent = self
-                    .parent_expanded_node
-                    .borrow()
-                    .upgrade()
-                    .map(|parent| {
-                        let comp_props = parent.layout_properties.borrow();
-                        let bounds_parent = comp_props.as_ref().unwrap().computed_tab.bounds;
-                        bounds_parent
-                    })
-                    .unwrap_or(globals.viewport.bounds);
-                let context = NodeContext {
-                    bounds_self,
-                    bounds_parent,
-                    frames_elapsed: globals.frames_elapsed,
-                    runtime_context: ctx,
-                    #[cfg(feature = "designtime")]
-                    designtime: globals.designtime.clone(),
-                };
+    /// Names of the properties whose transition queues drained while computing this node's
+    /// properties this tick, for `transition_end` to be dispatched once the update is done
+    pub finished_transitions: RefCell<Vec<String>>,
 
-                let borrowed_registry = &(*registry).borrow();
-                if let Some(handlers) = borrowed_registry.handlers.get($handler_key) {
-                    handlers.iter().for_each(|handler| {
-                        let properties = if let HandlerLocation::Component = &handler.location {
-                            Rc::clone(&self.properties.borrow())
-                        } else {
-                            Rc::clone(&component_properties)
-                        };
-                        (handler.function)(
-                            Rc::clone(&properties),
-                            &context,
-                            Some(Box::new(event.clone()) as Box<dyn Any>),
-                        );
-                    });
-                };
-            }
+    /// The size of this node's content as last measured by the chassis for natively rendered
+    /// nodes, or as reported by a component through `NodeContext::set_content_size`; versioned so that layout depending on it is recomputed when it changes
+    native_measure: Cell<Option<(f64, f64)>>,
+    native_measure_version: PropertyVersion,
+}
 
+/// Generates a `dispatch_*` method that delivers an event DOM-style: if `$recurse`, handlers
+/// registered for the capture phase (`@<event>_capture`) run first, from the root down to
+/// the target, followed by regular handlers from the target back up to the root.  Any handler can
+/// end propagation with `Event::stop_propagation`.  Returns whether any handler called
+/// `Event::prevent_default`.
+macro_rules! dispatch_event_handler {
+    ($fn_name:ident, $arg_type:ty, $handler_key:ident, $recurse:expr) => {
+        pub fn $fn_name(self: &Rc<Self>, args: $arg_type, ctx: &RuntimeContext) -> bool {
+            let event = Event::new(args);
+            let path = if $recurse {
+                self.ancestors_and_self()
+            } else {
+                vec![Rc::clone(self)]
+            };
             if $recurse {
-                if let Some(parent) = &self.parent_expanded_node.borrow().upgrade() {
-                    let parent_prevent_default = parent.$fn_name(args, globals, ctx);
-                    return event.cancelled() || parent_prevent_default;
+                let capture_key = format!("{}{}", $handler_key, CAPTURE_SUFFIX);
+                for node in path.iter().rev() {
+                    node.fire_handlers(&capture_key, &event, self, ctx);
+                    if event.propagation_stopped() {
+                        return event.cancelled();
+                    }
+                }
+            }
+            for node in path.iter() {
+                node.fire_handlers($handler_key, &event, self, ctx);
+                if event.propagation_stopped() {
+                    break;
                 }
             }
             event.cancelled()
@@ -181,10 +182,16 @@ impl ExpandedNode {
             containing_component,
 
             children: RefCell::new(Vec::new()),
+            children_version: PropertyVersion::new(),
             layout_properties: RefCell::new(None),
+            layout_cell: RefCell::new(None),
             expanded_slot_children: Default::default(),
             expanded_and_flattened_slot_children: Default::default(),
             occlusion_id: RefCell::new(0),
+            timeline: Default::default(),
+            finished_transitions: Default::default(),
+            native_measure: Cell::new(None),
+            native_measure_version: PropertyVersion::new(),
         })
     }
 
@@ -197,6 +204,8 @@ impl ExpandedNode {
             .base()
             .instance_prototypical_common_properties_factory)(
         );
+        // Freshly created properties carry no history, so cached layout can't be dirty-checked against them
+        *self.layout_properties.borrow_mut() = None;
     }
 
     /// Returns whether this node is a descendant of the ExpandedNode described by `other_expanded_node_id` (id_chain)
@@ -215,6 +224,62 @@ impl ExpandedNode {
         }
     }
 
+    /// This node followed by each of its ancestors, up to and including the root
+    pub fn ancestors_and_self(self: &Rc<Self>) -> Vec<Rc<ExpandedNode>> {
+        let mut path = vec![Rc::clone(self)];
+        let mut parent = self.parent_expanded_node.borrow().upgrade();
+        while let Some(node) = parent {
+            parent = node.parent_expanded_node.borrow().upgrade();
+            path.push(node);
+        }
+        path
+    }
+
+    /// Runs this node's handlers registered under `handler_key` for `event`, which was
+    /// originally dispatched to `target`
+    fn fire_handlers<T: Clone + 'static>(
+        &self,
+        handler_key: &str,
+        event: &Event<T>,
+        target: &ExpandedNode,
+        ctx: &RuntimeContext,
+    ) {
+        let Some(registry) = self.instance_node.borrow().base().get_handler_registry() else {
+            return;
+        };
+        let borrowed_registry = &(*registry).borrow();
+        let Some(handlers) = borrowed_registry.handlers.get(handler_key) else {
+            return;
+        };
+
+        let component_properties = if let Some(cc) = self.containing_component.upgrade() {
+            Rc::clone(&cc.properties.borrow())
+        } else {
+            Rc::clone(&self.properties.borrow())
+        };
+        let mut context = self.get_node_context(ctx);
+        context.target_id = target.id_chain[0];
+        // Inline handlers belong to the containing component, so they control its timeline
+        let mut inline_context = context.clone();
+        inline_context.timeline = self
+            .containing_component
+            .upgrade()
+            .and_then(|cc| cc.timeline.borrow().clone());
+
+        handlers.iter().for_each(|handler| {
+            let (properties, context) = if let HandlerLocation::Component = &handler.location {
+                (Rc::clone(&self.properties.borrow()), &context)
+            } else {
+                (Rc::clone(&component_properties), &inline_context)
+            };
+            (handler.function)(
+                Rc::clone(&properties),
+                context,
+                Some(Box::new(event.clone()) as Box<dyn Any>),
+            );
+        });
+    }
+
     pub fn create_children_detached(
         self: &Rc<Self>,
         templates: impl IntoIterator<Item = (Rc<dyn InstanceNode>, Rc<RuntimePropertiesStackFrame>)>,
@@ -245,18 +310,34 @@ impl ExpandedNode {
         context: &mut RuntimeContext,
     ) {
         let mut curr_children = self.children.borrow_mut();
-        //TODO here we could probably check intersection between old and new children (to avoid unmount + mount)
+        // Children that stay (e.g. those a keyed `for` keeps) stay mounted
         if *self.attached.borrow() > 0 {
+            let ids = |children: &[Rc<ExpandedNode>]| -> HashSet<u32> {
+                children.iter().map(|child| child.id_chain[0]).collect()
+            };
+            let (curr_ids, new_ids) = (ids(&curr_children), ids(&new_children));
             for child in curr_children.iter() {
-                Rc::clone(child).recurse_unmount(context);
+                if !new_ids.contains(&child.id_chain[0]) {
+                    Rc::clone(child).recurse_unmount(context);
+                }
             }
             for child in new_children.iter() {
-                Rc::clone(child).recurse_mount(context);
+                if !curr_ids.contains(&child.id_chain[0]) {
+                    Rc::clone(child).recurse_mount(context);
+                }
             }
         }
         for child in new_children.iter() {
             *child.parent_expanded_node.borrow_mut() = Rc::downgrade(self);
         }
+        let same_children = curr_children.len() == new_children.len()
+            && curr_children
+                .iter()
+                .zip(new_children.iter())
+                .all(|(curr, new)| Rc::ptr_eq(curr, new));
+        if !same_children {
+            self.children_version.bump();
+        }
         *curr_children = new_children;
     }
 
@@ -269,26 +350,12 @@ impl ExpandedNode {
         self.attach_children(new_children, context);
     }
 
-    /// This method recursively updates all node properties. When dirty-dag exists, this won't
-    /// need to be here since all property dependencies can be set up and removed during mount/unmount
+    /// This method recursively visits all nodes, re-evaluating only the properties whose dependencies
+    /// have changed (see [`crate::api::reactivity`]) and recomputing layout only for nodes whose
+    /// common properties or container have changed. Lifecycle handlers (`tick`, `pre_render`) still fire every frame.
     pub fn recurse_update(self: &Rc<Self>, context: &mut RuntimeContext) {
-        self.get_common_properties()
-            .borrow_mut()
-            .compute_properties(&self.stack, context.expression_table(), context.globals());
-
-        let viewport = self
-            .parent_expanded_node
-            .borrow()
-            .upgrade()
-            .and_then(|p| {
-                let props = p.layout_properties.borrow();
-                props.as_ref().map(|c| c.computed_tab.clone())
-            })
-            .unwrap_or(context.globals().viewport.clone());
-
-        *self.layout_properties.borrow_mut() = Some(LayoutProperties {
-            computed_tab: compute_tab(self, &viewport),
-        });
+        self.update_common_properties(context);
+        self.update_layout(context);
 
         if let Some(ref registry) = self.instance_node.borrow().base().handler_registry {
             for handler in registry
@@ -305,6 +372,9 @@ impl ExpandedNode {
             }
         }
         Rc::clone(&self.instance_node.borrow()).update(&self, context);
+        for property in self.finished_transitions.take() {
+            self.dispatch_transition_end(TransitionEnd { property }, context);
+        }
 
         if *self.attached.borrow() > 0 {
             self.instance_node
@@ -325,9 +395,103 @@ impl ExpandedNode {
                 )
             }
         }
+        if self.instance_node.borrow().lays_out_children() {
+            self.assign_layout_cells(context);
+        }
         for child in self.children.borrow().iter() {
             child.recurse_update(context);
         }
+        // `if` and `for` children only expand during their own update, after this node's layout
+        // was computed above, so a size measured from them is brought up to date here
+        self.update_layout(context);
+    }
+
+    fn update_common_properties(&self, context: &RuntimeContext) {
+        let finished_transitions = self
+            .get_common_properties()
+            .borrow_mut()
+            .compute_properties(&self.stack, context.expression_table(), context.globals());
+        self.finished_transitions
+            .borrow_mut()
+            .extend(finished_transitions.into_iter().map(String::from));
+    }
+
+    fn update_layout(&self, context: &RuntimeContext) {
+        let viewport = self
+            .layout_cell
+            .borrow()
+            .clone()
+            .or_else(|| {
+                let parent = self.parent_expanded_node.borrow().upgrade()?;
+                let props = parent.layout_properties.borrow();
+                props.as_ref().map(|c| c.content_tab.clone())
+            })
+            .unwrap_or(context.globals().viewport.clone());
+
+        let needs_layout = match *self.layout_properties.borrow() {
+            Some(ref layout) => {
+                layout.container_tab != viewport || reactivity::any_changed(&layout.dependencies)
+            }
+            None => true,
+        };
+        if needs_layout {
+            let ((computed_tab, content_tab), dependencies) =
+                reactivity::track_dependencies(|| {
+                    let computed_tab = compute_tab(self, &viewport);
+                    let content_tab = compute_content_tab(self, &computed_tab, viewport.bounds);
+                    (computed_tab, content_tab)
+                });
+            *self.layout_properties.borrow_mut() = Some(LayoutProperties {
+                computed_tab,
+                content_tab,
+                container_tab: viewport,
+                dependencies,
+            });
+        }
+    }
+
+    /// For layout containers: brings their items (this node's children, flattened through `if`
+    /// and `for`) up to date and assigns each the cell it's laid out in.  `if` and `for` are
+    /// updated early for this, so that items they add this tick are laid out straight away.
+    fn assign_layout_cells(self: &Rc<Self>, context: &mut RuntimeContext) {
+        for child in self.children.borrow().clone() {
+            child.update_slot_invisible(context);
+        }
+        let items = flatten_expanded_nodes_for_slot(&self.children.borrow());
+        for item in &items {
+            item.update_common_properties(context);
+        }
+        let Some(tab) = self
+            .layout_properties
+            .borrow()
+            .as_ref()
+            .map(|layout| layout.content_tab.clone())
+        else {
+            return;
+        };
+        let cells = self
+            .instance_node
+            .borrow()
+            .layout_children(self, tab.bounds, &items);
+        for (item, cell) in items.iter().zip(cells) {
+            let offset = Transform2::translate(Vector2::new(cell.x0, cell.y0));
+            *item.layout_cell.borrow_mut() = Some(TransformAndBounds {
+                transform: tab.transform * offset,
+                bounds: (cell.width(), cell.height()),
+            });
+        }
+    }
+
+    fn update_slot_invisible(self: &Rc<Self>, context: &mut RuntimeContext) {
+        if !self.instance_node.borrow().base().flags().invisible_to_slot {
+            return;
+        }
+        self.update_common_properties(context);
+        self.update_layout(context);
+        Rc::clone(&self.instance_node.borrow()).update(self, context);
+        for child in self.children.borrow().clone() {
+            child.update_slot_invisible(context);
+        }
     }
 
     pub fn recurse_mount(self: Rc<Self>, context: &mut RuntimeContext) {
@@ -387,6 +551,12 @@ impl ExpandedNode {
         if *self.attached.borrow() == 1 {
             *self.attached.borrow_mut() -= 1;
             context.node_cache.remove(&self.id_chain[0]);
+            if context
+                .focused_node()
+                .is_some_and(|focused| Rc::ptr_eq(&focused, &self))
+            {
+                context.focused_node = None;
+            }
             let uni = self
                 .instance_node
                 .borrow()
@@ -403,16 +573,66 @@ impl ExpandedNode {
     }
 
     pub fn recurse_render(&self, ctx: &mut RuntimeContext, rcs: &mut dyn RenderContext) {
+        if !self.is_visible() {
+            return;
+        }
+        // A translucent node composites itself and its subtree as a group, on every layer
+        // its descendants may draw to
+        let opacity = self.get_opacity();
+        let layers: Vec<String> = if opacity < 1.0 {
+            rcs.layers().iter().map(|layer| layer.to_string()).collect()
+        } else {
+            Vec::new()
+        };
+        for layer in &layers {
+            rcs.save_layer(layer, opacity);
+        }
         self.instance_node
             .borrow()
             .handle_pre_render(&self, ctx, rcs);
-        for child in self.children.borrow().iter().rev() {
-            child.recurse_render(ctx, rcs);
+        // Nodes stacked in this context are drawn within their ancestors' `handle_pre_render`
+        // and `handle_post_render` (e.g. a `Frame`'s clip), entered and left as needed
+        let mut entered: Vec<Rc<ExpandedNode>> = Vec::new();
+        for stacked in self.stacked_descendants() {
+            let within = stacked.within();
+            if !within.iter().all(|node| node.is_visible()) {
+                continue;
+            }
+            let shared = entered
+                .iter()
+                .zip(within)
+                .take_while(|(entered, node)| Rc::ptr_eq(entered, node))
+                .count();
+            for node in entered.drain(shared..).rev() {
+                node.instance_node
+                    .borrow()
+                    .handle_post_render(&node, ctx, rcs);
+            }
+            for node in &within[shared..] {
+                node.instance_node
+                    .borrow()
+                    .handle_pre_render(node, ctx, rcs);
+                entered.push(Rc::clone(node));
+            }
+            let node = stacked.node();
+            if stacked.is_context {
+                node.recurse_render(ctx, rcs);
+            } else {
+                node.instance_node.borrow().render(node, ctx, rcs);
+            }
+        }
+        for node in entered.into_iter().rev() {
+            node.instance_node
+                .borrow()
+                .handle_post_render(&node, ctx, rcs);
         }
         self.instance_node.borrow().render(&self, ctx, rcs);
         self.instance_node
             .borrow()
             .handle_post_render(&self, ctx, rcs);
+        for layer in &layers {
+            rcs.restore(layer);
+        }
     }
 
     /// Manages unpacking an Rc<RefCell<dyn Any>>, downcasting into
@@ -442,8 +662,13 @@ impl ExpandedNode {
         func: &impl Fn(&Rc<Self>, &mut T),
         val: &mut T,
     ) {
-        for child in self.children.borrow().iter().rev() {
-            child.recurse_visit_postorder(func, val);
+        for stacked in self.stacked_descendants() {
+            let node = stacked.node();
+            if stacked.is_context {
+                node.recurse_visit_postorder(func, val);
+            } else {
+                func(node, val);
+            }
         }
         func(self, val);
     }
@@ -455,19 +680,33 @@ impl ExpandedNode {
             .as_ref()
             .map(|v| v.computed_tab.bounds)
             .unwrap_or(globals.viewport.bounds);
+        let bounds_content = computed_props
+            .as_ref()
+            .map(|v| v.content_tab.bounds)
+            .unwrap_or(globals.viewport.bounds);
         let parent = self.parent_expanded_node.borrow().upgrade();
         let bounds_parent = parent
             .as_ref()
             .and_then(|p| {
                 let props = p.layout_properties.borrow();
-                props.as_ref().map(|v| v.computed_tab.bounds)
+                props.as_ref().map(|v| v.content_tab.bounds)
             })
             .unwrap_or(globals.viewport.bounds);
+        let timeline = self.timeline.borrow().clone().or_else(|| {
+            let containing_component = self.containing_component.upgrade()?;
+            let timeline = containing_component.timeline.borrow().clone();
+            timeline
+        });
         NodeContext {
             frames_elapsed: globals.frames_elapsed,
+            time_elapsed: globals.time_elapsed,
             bounds_self,
+            bounds_content,
             bounds_parent,
             runtime_context: context,
+            current_target_id: self.id_chain[0],
+            target_id: self.id_chain[0],
+            timeline,
             #[cfg(feature = "designtime")]
             designtime: globals.designtime.clone(),
         }
@@ -477,6 +716,143 @@ impl ExpandedNode {
         Rc::clone(&self.common_properties.borrow())
     }
 
+    /// This node's `tab_index`, if set.  Nodes with a `tab_index` can receive keyboard focus;
+    /// those with a non-negative one are also reachable with Tab/Shift-Tab.
+    pub fn get_tab_index(&self) -> Option<isize> {
+        let common_properties = self.get_common_properties();
+        let common_properties = common_properties.borrow();
+        common_properties
+            .tab_index
+            .as_ref()
+            .map(|tab_index| tab_index.get().to_int())
+    }
+
+    /// This node's own `cursor`, if set
+    pub fn get_cursor(&self) -> Option<Cursor> {
+        let common_properties = self.get_common_properties();
+        let common_properties = common_properties.borrow();
+        common_properties
+            .cursor
+            .as_ref()
+            .map(|cursor| *cursor.get())
+    }
+
+    /// This node's `z_index`, defaulting to 0
+    pub fn get_z_index(&self) -> isize {
+        let common_properties = self.get_common_properties();
+        let common_properties = common_properties.borrow();
+        common_properties
+            .z_index
+            .as_ref()
+            .map_or(0, |z_index| z_index.get().to_int())
+    }
+
+    /// Whether this node starts a stacking context: whether it's stacked, along with its
+    /// subtree, as a unit among the nodes of its closest ancestor context.  Components do, as do
+    /// nodes given a `z_index` and translucent nodes (which are composited as a group).  Other
+    /// nodes, e.g. `Group`s, `Frame`s, `if` and `for`, are stacked individually in their
+    /// ancestor's context, so that e.g. a popover within a `Group` can be raised above the
+    /// `Group`'s later siblings.
+    pub fn is_stacking_context(&self) -> bool {
+        self.instance_node.borrow().base().flags().is_component
+            || self.get_common_properties().borrow().z_index.is_some()
+            || self.get_opacity() < 1.0
+    }
+
+    /// The nodes stacked in this node's stacking context, bottom-most first: its descendants
+    /// down to (and including) the roots of any nested contexts, by ascending `z_index`, then
+    /// with later siblings beneath earlier ones and nodes beneath their parents.
+    pub(crate) fn stacked_descendants(&self) -> Vec<Stacked> {
+        let mut stacked = Vec::new();
+        self.collect_stacked(&mut Vec::new(), &mut stacked);
+        stacked.sort_by_key(|stacked| stacked.z_index);
+        stacked
+    }
+
+    fn collect_stacked(&self, path: &mut Vec<Rc<ExpandedNode>>, stacked: &mut Vec<Stacked>) {
+        for child in self.children.borrow().iter().rev() {
+            path.push(Rc::clone(child));
+            let is_context = child.is_stacking_context();
+            if !is_context {
+                child.collect_stacked(path, stacked);
+            }
+            stacked.push(Stacked {
+                path: path.clone(),
+                is_context,
+                z_index: child.get_z_index(),
+            });
+            path.pop();
+        }
+    }
+
+    /// This node's `opacity`, clamped to 0.0 to 1.0 and defaulting to fully opaque
+    pub fn get_opacity(&self) -> f64 {
+        let common_properties = self.get_common_properties();
+        let common_properties = common_properties.borrow();
+        common_properties
+            .opacity
+            .as_ref()
+            .map_or(1.0, |opacity| opacity.get().to_float().clamp(0.0, 1.0))
+    }
+
+    /// The opacity this node is composited at on screen, i.e. its own multiplied by those of
+    /// its ancestors.
+    ///
+    /// Canvas content is drawn within its ancestors, so their opacity, visibility and clipping
+    /// apply to it as a matter of course.  Native elements (text, form controls) are laid over
+    /// the canvas by the chassis instead, so those effects are resolved per node by this and
+    /// the other `get_computed_*` methods, and [`Self::get_ancestral_clip_path`], and
+    /// forwarded in each element's patch.
+    pub fn get_computed_opacity(&self) -> f64 {
+        let parent_opacity = self
+            .parent_expanded_node
+            .borrow()
+            .upgrade()
+            .map_or(1.0, |parent| parent.get_computed_opacity());
+        self.get_opacity() * parent_opacity
+    }
+
+    /// Whether this node's own `visible` allows it to be drawn, defaulting to `true`.  A hidden
+    /// node keeps its state and layout, but neither it nor its subtree is rendered.
+    pub fn is_visible(&self) -> bool {
+        let common_properties = self.get_common_properties();
+        let common_properties = common_properties.borrow();
+        common_properties
+            .visible
+            .as_ref()
+            .map(|visible| *visible.get())
+            .unwrap_or(true)
+    }
+
+    /// Whether this node and all of its ancestors are visible; see [`Self::get_computed_opacity`]
+    pub fn get_computed_visibility(&self) -> bool {
+        self.is_visible()
+            && self
+                .parent_expanded_node
+                .borrow()
+                .upgrade()
+                .map(|parent| parent.get_computed_visibility())
+                .unwrap_or(true)
+    }
+
+    /// Whether this node can be the target of pointer events.  `pointer_events` is inherited:
+    /// a node that doesn't set it takes its closest ancestor's, and receives events by default.
+    pub fn get_computed_pointer_events(&self) -> bool {
+        let common_properties = self.get_common_properties();
+        let own = common_properties
+            .borrow()
+            .pointer_events
+            .as_ref()
+            .map(|pointer_events| *pointer_events.get());
+        own.unwrap_or_else(|| {
+            self.parent_expanded_node
+                .borrow()
+                .upgrade()
+                .map(|parent| parent.get_computed_pointer_events())
+                .unwrap_or(true)
+        })
+    }
+
     /// Determines whether the provided ray, orthogonal to the view plane,
     /// intersects this `ExpandedNode`.
     pub fn ray_cast_test(&self, ray: Point2<Window>) -> bool {
@@ -491,6 +867,11 @@ impl ExpandedNode {
             return false;
         }
 
+        // Nor for nodes that are hidden or have opted out of pointer events
+        if !self.get_computed_pointer_events() || !self.get_computed_visibility() {
+            return false;
+        }
+
         let props = self.layout_properties.borrow();
         let computed_tab = &props.as_ref().unwrap().computed_tab;
 
@@ -508,6 +889,29 @@ impl ExpandedNode {
         res
     }
 
+    /// The shape this node clips its contents to, in window coordinates, if any
+    pub fn get_clip_path(&self) -> Option<BezPath> {
+        let clip_path = self.instance_node.borrow().get_clip_path(self)?;
+        let layout_properties = self.layout_properties.borrow();
+        let transform = layout_properties.as_ref()?.computed_tab.transform;
+        Some(Affine::from(transform) * clip_path)
+    }
+
+    /// The clip of this node's nearest clipping ancestor, in this node's own coordinate space;
+    /// see [`Self::get_computed_opacity`]
+    pub fn get_ancestral_clip_path(&self) -> Option<BezPath> {
+        let mut ancestor = self.parent_expanded_node.borrow().upgrade();
+        while let Some(node) = ancestor {
+            if let Some(clip_path) = node.get_clip_path() {
+                let layout_properties = self.layout_properties.borrow();
+                let transform = layout_properties.as_ref()?.computed_tab.transform;
+                return Some(Affine::from(transform).inverse() * clip_path);
+            }
+            ancestor = node.parent_expanded_node.borrow().upgrade();
+        }
+        None
+    }
+
     /// Returns the size of this node, or `None` if this node
     /// doesn't have a size (e.g. `Group`)
     pub fn get_size(&self) -> (Size, Size) {
@@ -515,15 +919,118 @@ impl ExpandedNode {
     }
 
     /// Returns the size of this node in pixels, requiring this node's containing bounds
-    /// for calculation of `Percent` values
+    /// for calculation of `Percent` values, and measuring its content for `Auto` ones
     pub fn get_size_computed(&self, bounds: (f64, f64)) -> (f64, f64) {
-        let size = self.get_size();
+        let (width, height) = self.get_size();
+        let measured = if matches!(width, Size::Auto) || matches!(height, Size::Auto) {
+            self.instance_node.borrow().measure(self, bounds)
+        } else {
+            None
+        };
+        // Content is measured inside the padding
+        let measured = measured.map(|(width, height)| {
+            let padding = self.get_padding(bounds);
+            (width + 2.0 * padding.0, height + 2.0 * padding.1)
+        });
+        let resolve = |size: Size, measured: Option<f64>, axis| match (size, measured) {
+            (Size::Auto, Some(measured)) => measured,
+            _ => size.evaluate(bounds, axis),
+        };
+        self.clamp_size(
+            (
+                resolve(width, measured.map(|size| size.0), Axis::X),
+                resolve(height, measured.map(|size| size.1), Axis::Y),
+            ),
+            bounds,
+        )
+    }
+
+    /// The smallest and largest sizes this node may take in `bounds`, from its `min_width`,
+    /// `min_height`, `max_width` and `max_height`.  Unset limits are `0` and infinity.
+    pub fn get_size_limits(&self, bounds: (f64, f64)) -> ((f64, f64), (f64, f64)) {
+        let common_properties = self.get_common_properties();
+        let common_properties = common_properties.borrow();
+        let limit = |limit: &Option<Box<dyn PropertyInstance<Size>>>, axis, unset| {
+            limit
+                .as_ref()
+                .map_or(unset, |limit| limit.get().evaluate(bounds, axis))
+        };
         (
-            size.0.evaluate(bounds, Axis::X),
-            size.1.evaluate(bounds, Axis::Y),
+            (
+                limit(&common_properties.min_width, Axis::X, 0.0),
+                limit(&common_properties.min_height, Axis::Y, 0.0),
+            ),
+            (
+                limit(&common_properties.max_width, Axis::X, f64::INFINITY),
+                limit(&common_properties.max_height, Axis::Y, f64::INFINITY),
+            ),
         )
     }
 
+    /// `size` kept within this node's size limits in `bounds`; where the limits conflict, the
+    /// minimum wins
+    pub fn clamp_size(&self, size: (f64, f64), bounds: (f64, f64)) -> (f64, f64) {
+        let (min, max) = self.get_size_limits(bounds);
+        (size.0.min(max.0).max(min.0), size.1.min(max.1).max(min.1))
+    }
+
+    /// This node's `padding` in `bounds`, evaluated along each axis
+    pub fn get_padding(&self, bounds: (f64, f64)) -> (f64, f64) {
+        let common_properties = self.get_common_properties();
+        let common_properties = common_properties.borrow();
+        common_properties
+            .padding
+            .as_ref()
+            .map_or((0.0, 0.0), |padding| {
+                (
+                    padding.get().evaluate(bounds, Axis::X),
+                    padding.get().evaluate(bounds, Axis::Y),
+                )
+            })
+    }
+
+    /// This node's children, with any `if` and `for` among them replaced by their own
+    pub fn get_flattened_children(&self) -> Vec<Rc<ExpandedNode>> {
+        self.children_version.track_read();
+        flatten_expanded_nodes_for_slot(&self.children.borrow())
+    }
+
+    /// How far right and down this node's children (flattened through `if` and `for`) reach
+    /// from its origin, sized and positioned against `bounds`; a measure for containers whose
+    /// children are positioned freely, like `Group`'s
+    pub fn measure_children(&self, bounds: (f64, f64)) -> (f64, f64) {
+        self.get_flattened_children()
+            .iter()
+            .fold((0.0, 0.0), |(width, height), child| {
+                let size = child.get_size_computed(bounds);
+                let common_properties = child.get_common_properties();
+                let common_properties = common_properties.borrow();
+                let offset = |position: &Option<Box<dyn PropertyInstance<Size>>>, axis| {
+                    position
+                        .as_ref()
+                        .map_or(0.0, |position| position.get().evaluate(bounds, axis))
+                };
+                (
+                    width.max(offset(&common_properties.x, Axis::X) + size.0),
+                    height.max(offset(&common_properties.y, Axis::Y) + size.1),
+                )
+            })
+    }
+
+    /// The size of this node's content last reported by the chassis or by this component, tracked
+    /// as a dependency of the layout being computed, if any
+    pub fn get_native_measure(&self) -> Option<(f64, f64)> {
+        self.native_measure_version.track_read();
+        self.native_measure.get()
+    }
+
+    pub fn set_native_measure(&self, size: (f64, f64)) {
+        if self.native_measure.get() != Some(size) {
+            self.native_measure.set(Some(size));
+            self.native_measure_version.bump();
+        }
+    }
+
     /// Used at least by ray-casting; only nodes that clip content (and thus should
     /// not allow outside content to respond to ray-casting) should return a value
     pub fn get_clipping_size(&self) -> Option<(Size, Size)> {
@@ -562,9 +1069,11 @@ impl ExpandedNode {
 
     dispatch_event_handler!(dispatch_touch_move, TouchMove, TOUCH_MOVE_HANDLERS, true);
     dispatch_event_handler!(dispatch_touch_end, TouchEnd, TOUCH_END_HANDLERS, true);
-    dispatch_event_handler!(dispatch_key_down, KeyDown, KEY_DOWN_HANDLERS, false);
-    dispatch_event_handler!(dispatch_key_up, KeyUp, KEY_UP_HANDLERS, false);
-    dispatch_event_handler!(dispatch_key_press, KeyPress, KEY_PRESS_HANDLERS, false);
+    dispatch_event_handler!(dispatch_key_down, KeyDown, KEY_DOWN_HANDLERS, true);
+    dispatch_event_handler!(dispatch_key_up, KeyUp, KEY_UP_HANDLERS, true);
+    dispatch_event_handler!(dispatch_key_press, KeyPress, KEY_PRESS_HANDLERS, true);
+    dispatch_event_handler!(dispatch_focus, Focus, FOCUS_HANDLERS, false);
+    dispatch_event_handler!(dispatch_blur, Blur, BLUR_HANDLERS, false);
     dispatch_event_handler!(
         dispatch_checkbox_change,
         CheckboxChange,
@@ -593,8 +1102,8 @@ impl ExpandedNode {
     dispatch_event_handler!(dispatch_mouse_down, MouseDown, MOUSE_DOWN_HANDLERS, true);
     dispatch_event_handler!(dispatch_mouse_up, MouseUp, MOUSE_UP_HANDLERS, true);
     dispatch_event_handler!(dispatch_mouse_move, MouseMove, MOUSE_MOVE_HANDLERS, true);
-    dispatch_event_handler!(dispatch_mouse_over, MouseOver, MOUSE_OVER_HANDLERS, true);
-    dispatch_event_handler!(dispatch_mouse_out, MouseOut, MOUSE_OUT_HANDLERS, true);
+    dispatch_event_handler!(dispatch_mouse_over, MouseOver, MOUSE_OVER_HANDLERS, false);
+    dispatch_event_handler!(dispatch_mouse_out, MouseOut, MOUSE_OUT_HANDLERS, false);
     dispatch_event_handler!(
         dispatch_double_click,
         DoubleClick,
@@ -609,13 +1118,40 @@ impl ExpandedNode {
     );
     dispatch_event_handler!(dispatch_click, Click, CLICK_HANDLERS, true);
     dispatch_event_handler!(dispatch_wheel, Wheel, WHEEL_HANDLERS, true);
+    dispatch_event_handler!(dispatch_drag_start, DragStart, DRAG_START_HANDLERS, true);
+    dispatch_event_handler!(dispatch_drag, Drag, DRAG_HANDLERS, true);
+    dispatch_event_handler!(dispatch_drag_end, DragEnd, DRAG_END_HANDLERS, true);
+    dispatch_event_handler!(dispatch_pinch, Pinch, PINCH_HANDLERS, true);
+    dispatch_event_handler!(
+        dispatch_rotate_gesture,
+        RotateGesture,
+        ROTATE_GESTURE_HANDLERS,
+        true
+    );
+    dispatch_event_handler!(dispatch_pan, Pan, PAN_HANDLERS, true);
+    dispatch_event_handler!(dispatch_long_press, LongPress, LONG_PRESS_HANDLERS, true);
+    dispatch_event_handler!(dispatch_swipe, Swipe, SWIPE_HANDLERS, true);
+    dispatch_event_handler!(
+        dispatch_transition_end,
+        TransitionEnd,
+        TRANSITION_END_HANDLERS,
+        false
+    );
 }
 
-/// Properties that are currently re-computed each frame before rendering.
+/// Properties that are re-computed before rendering whenever this node's container or
+/// any of the properties read during layout have changed.
 #[cfg_attr(debug_assertions, derive(Debug))]
 pub struct LayoutProperties {
     /// Computed transform and size of this ExpandedNode
     pub computed_tab: TransformAndBounds,
+    /// Transform and size of the area this node's children are laid out in, i.e.
+    /// `computed_tab` less its padding
+    pub content_tab: TransformAndBounds,
+    /// Transform and size of the container this layout was computed against
+    pub container_tab: TransformAndBounds,
+    /// Properties read while computing this layout
+    pub dependencies: Vec<Dependency>,
 }
 
 /// Given some InstanceNodePtrList, distill away all "slot-invisible" nodes (namely, `if` and `for`)
@@ -624,6 +1160,7 @@ fn flatten_expanded_nodes_for_slot(nodes: &[Rc<ExpandedNode>]) -> Vec<Rc<Expande
     let mut result = vec![];
     for node in nodes {
         if node.instance_node.borrow().base().flags().invisible_to_slot {
+            node.children_version.track_read();
             result.extend(flatten_expanded_nodes_for_slot(
                 node.children
                     .borrow()
@@ -699,3 +1236,28 @@ impl std::fmt::Debug for ExpandedNode {
             .finish()
     }
 }
+
+/// A node stacked in a stacking context; see [`ExpandedNode::stacked_descendants`]
+pub(crate) struct Stacked {
+    /// The node's ancestors below the context's root, outermost first, then the node itself
+    path: Vec<Rc<ExpandedNode>>,
+    /// Whether the node starts a nested context, stacked along with its subtree
+    is_context: bool,
+    z_index: isize,
+}
+
+impl Stacked {
+    fn node(&self) -> &Rc<ExpandedNode> {
+        self.path.last().unwrap()
+    }
+
+    /// The nodes whose `handle_pre_render` applies to drawing this one: its ancestors below the
+    /// context's root, and itself unless it's drawn by its own `recurse_render`
+    fn within(&self) -> &[Rc<ExpandedNode>] {
+        if self.is_context {
+            &self.path[..self.path.len() - 1]
+        } else {
+            &self.path
+        }
+    }
+}
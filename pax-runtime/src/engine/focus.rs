@@ -0,0 +1,122 @@
+use std::rc::Rc;
+
+use crate::api::{Blur, Focus, KeyDown, KeyPress, KeyUp, ModifierKey};
+use crate::{ExpandedNode, PaxEngine};
+
+impl PaxEngine {
+    /// Moves keyboard focus to `node` (or clears it, for `None`), firing `blur` on the
+    /// previously focused node and `focus` on the newly focused one.
+    pub fn focus(&mut self, node: Option<Rc<ExpandedNode>>) {
+        let previous = self.runtime_context.focused_node();
+        let unchanged = match (&previous, &node) {
+            (Some(previous), Some(node)) => Rc::ptr_eq(previous, node),
+            (None, None) => true,
+            _ => false,
+        };
+        if unchanged {
+            return;
+        }
+        self.runtime_context.focused_node = node.as_ref().map(Rc::downgrade);
+
+        let ctx = &self.runtime_context;
+        if let Some(previous) = previous {
+            previous.dispatch_blur(Blur {}, ctx);
+        }
+        if let Some(node) = node {
+            node.dispatch_focus(Focus {}, ctx);
+        }
+    }
+
+    /// Moves focus to the closest ancestor-or-self of `node` that can receive focus,
+    /// or clears focus if there is none, e.g. when clicking an empty area.
+    pub fn focus_closest(&mut self, node: Option<Rc<ExpandedNode>>) {
+        let mut candidate = node;
+        while let Some(node) = candidate {
+            if node.get_tab_index().is_some() {
+                self.focus(Some(node));
+                return;
+            }
+            candidate = node.parent_expanded_node.borrow().upgrade();
+        }
+        self.focus(None);
+    }
+
+    /// Moves focus to the next (or, with `backwards`, previous) node in tab order, wrapping
+    /// around at either end.  Returns false if there are no tabbable nodes.
+    pub fn focus_next(&mut self, backwards: bool) -> bool {
+        let order = self.tab_order();
+        if order.is_empty() {
+            return false;
+        }
+        let current = self
+            .runtime_context
+            .focused_node()
+            .and_then(|focused| order.iter().position(|node| Rc::ptr_eq(node, &focused)));
+        let next = match (current, backwards) {
+            (Some(i), false) => (i + 1) % order.len(),
+            (Some(i), true) => (i + order.len() - 1) % order.len(),
+            (None, false) => 0,
+            (None, true) => order.len() - 1,
+        };
+        self.focus(Some(Rc::clone(&order[next])));
+        true
+    }
+
+    /// Nodes reachable with Tab, in the order they are visited: following HTML, nodes with a
+    /// positive `tab_index` come first in ascending order, then those with a `tab_index` of zero,
+    /// ties broken by template order.  Nodes with a negative `tab_index` are skipped.
+    fn tab_order(&self) -> Vec<Rc<ExpandedNode>> {
+        fn collect(node: &Rc<ExpandedNode>, order: &mut Vec<(isize, Rc<ExpandedNode>)>) {
+            if let Some(tab_index) = node.get_tab_index().filter(|i| *i >= 0) {
+                order.push((tab_index, Rc::clone(node)));
+            }
+            for child in node.children.borrow().iter() {
+                collect(child, order);
+            }
+        }
+        let mut order = Vec::new();
+        collect(&self.root_node, &mut order);
+        order.sort_by_key(|(tab_index, _)| match tab_index {
+            0 => isize::MAX,
+            positive => *positive,
+        });
+        order.into_iter().map(|(_, node)| node).collect()
+    }
+
+    /// Dispatches a `KeyDown` to the focused node (or the root, if no node is focused),
+    /// bubbling up to its ancestors.  Unless a handler called `prevent_default`, Tab and
+    /// Shift-Tab then move focus through the tab order.
+    pub fn dispatch_key_down(&mut self, args: KeyDown) -> bool {
+        let is_tab = args.keyboard.key == "Tab";
+        let backwards = args
+            .keyboard
+            .modifiers
+            .iter()
+            .any(|modifier| matches!(modifier, ModifierKey::Shift));
+        let target = self.keyboard_target();
+        let ctx = &self.runtime_context;
+        let prevent_default = target.dispatch_key_down(args, ctx);
+        if is_tab && !prevent_default {
+            return self.focus_next(backwards);
+        }
+        prevent_default
+    }
+
+    /// Dispatches a `KeyUp` to the focused node (or the root), bubbling up to its ancestors
+    pub fn dispatch_key_up(&self, args: KeyUp) -> bool {
+        let ctx = &self.runtime_context;
+        self.keyboard_target().dispatch_key_up(args, ctx)
+    }
+
+    /// Dispatches a `KeyPress` to the focused node (or the root), bubbling up to its ancestors
+    pub fn dispatch_key_press(&self, args: KeyPress) -> bool {
+        let ctx = &self.runtime_context;
+        self.keyboard_target().dispatch_key_press(args, ctx)
+    }
+
+    fn keyboard_target(&self) -> Rc<ExpandedNode> {
+        self.runtime_context
+            .focused_node()
+            .unwrap_or_else(|| Rc::clone(&self.root_node))
+    }
+}
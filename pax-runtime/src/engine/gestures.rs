@@ -0,0 +1,300 @@
+use std::rc::{Rc, Weak};
+use std::time::Duration;
+
+use crate::api::math::{Point2, Vector2};
+use crate::api::{LongPress, Pan, Pinch, RotateGesture, Swipe, SwipeDirection, Touch, Window};
+use crate::{ExpandedNode, PaxEngine};
+
+/// Distance in px the touches' centroid must travel before a pan begins
+const PAN_THRESHOLD: f64 = 10.0;
+/// How long a single finger must be held down to count as a long press
+const LONG_PRESS_DURATION: Duration = Duration::from_millis(500);
+/// Distance in px a finger may wander while still counting as held for a long press
+const LONG_PRESS_TOLERANCE: f64 = 10.0;
+/// How soon a finger must be lifted to count as a swipe
+const SWIPE_MAX_DURATION: Duration = Duration::from_millis(250);
+/// Distance in px a finger must travel to count as a swipe
+const SWIPE_MIN_DISTANCE: f64 = 50.0;
+/// Distance in px the first two fingers must be apart to measure pinches and rotations against
+const BASELINE_MIN_DISTANCE: f64 = 1.0;
+
+#[cfg_attr(debug_assertions, derive(Debug))]
+struct TrackedTouch {
+    identifier: i64,
+    start: Point2<Window>,
+    current: Point2<Window>,
+}
+
+/// Distance and angle (in degrees) between the first two fingers when the second touched down
+#[cfg_attr(debug_assertions, derive(Debug))]
+struct TwoFingerBaseline {
+    distance: f64,
+    angle: f64,
+}
+
+/// State of the touch gesture in progress: from the first finger touching down until the
+/// last is lifted.  Gesture events are dispatched to the node beneath the first finger.
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub(crate) struct GestureTracker {
+    node: Weak<ExpandedNode>,
+    touches: Vec<TrackedTouch>,
+    started: Duration,
+    baseline: Option<TwoFingerBaseline>,
+    pan_start: Point2<Window>,
+    pan_last: Point2<Window>,
+    panning: bool,
+    /// Set once more than one finger has been down; such gestures are never long presses or swipes
+    multi_touch: bool,
+    long_pressed: bool,
+}
+
+impl GestureTracker {
+    fn centroid(&self) -> Point2<Window> {
+        let sum = self.touches.iter().fold(Vector2::default(), |sum, touch| {
+            sum + touch.current.to_vector()
+        });
+        (sum / self.touches.len() as f64).to_point()
+    }
+
+    fn reset_pan_origin(&mut self) {
+        // Fingers touching down or lifting move the centroid, which shouldn't read as panning
+        let centroid = self.centroid();
+        self.pan_start = self.pan_start + (centroid - self.pan_last);
+        self.pan_last = centroid;
+    }
+
+    fn two_finger_state(&self) -> Option<(Point2<Window>, TwoFingerBaseline)> {
+        let [a, b] = [self.touches.first()?, self.touches.get(1)?].map(|touch| touch.current);
+        let between = b - a;
+        Some((
+            a.midpoint_towards(b),
+            TwoFingerBaseline {
+                distance: between.length(),
+                angle: between.y.atan2(between.x).to_degrees(),
+            },
+        ))
+    }
+}
+
+impl PaxEngine {
+    /// Tracks fingers touching down, starting a gesture on `node`, the node beneath the first
+    /// finger, if none is in progress
+    pub(crate) fn gesture_touch_start(
+        &mut self,
+        touches: &[Touch],
+        node: Option<&Rc<ExpandedNode>>,
+    ) {
+        let now = self.runtime_context.globals().time_elapsed;
+        let tracker = match node {
+            Some(node) => self.runtime_context.gesture.get_or_insert_with(|| {
+                let first = touches.first().map(|touch| Point2::new(touch.x, touch.y));
+                let origin = first.unwrap_or_default();
+                GestureTracker {
+                    node: Rc::downgrade(node),
+                    touches: Vec::new(),
+                    started: now,
+                    baseline: None,
+                    pan_start: origin,
+                    pan_last: origin,
+                    panning: false,
+                    multi_touch: false,
+                    long_pressed: false,
+                }
+            }),
+            // Fingers touching down beside every node still join a gesture in progress
+            None => match self.runtime_context.gesture.as_mut() {
+                Some(tracker) => tracker,
+                None => return,
+            },
+        };
+        for touch in touches {
+            if tracker
+                .touches
+                .iter()
+                .all(|tracked| tracked.identifier != touch.identifier)
+            {
+                let point = Point2::new(touch.x, touch.y);
+                tracker.touches.push(TrackedTouch {
+                    identifier: touch.identifier,
+                    start: point,
+                    current: point,
+                });
+            }
+        }
+        tracker.multi_touch |= tracker.touches.len() > 1;
+        tracker.baseline = tracker.two_finger_state().map(|(_, baseline)| baseline);
+        tracker.reset_pan_origin();
+    }
+
+    /// Updates tracked fingers, firing `pan` once the fingers have moved far enough, and `pinch`
+    /// and `rotate_gesture` while two or more are down.  Returns whether any handler called
+    /// `prevent_default`.
+    pub(crate) fn gesture_touch_move(&mut self, touches: &[Touch]) -> bool {
+        let Some(tracker) = self.runtime_context.gesture.as_mut() else {
+            return false;
+        };
+        let Some(node) = tracker.node.upgrade() else {
+            return false;
+        };
+        for touch in touches {
+            if let Some(tracked) = tracker
+                .touches
+                .iter_mut()
+                .find(|tracked| tracked.identifier == touch.identifier)
+            {
+                tracked.current = Point2::new(touch.x, touch.y);
+            }
+        }
+
+        let centroid = tracker.centroid();
+        tracker.panning |= (centroid - tracker.pan_start).length() > PAN_THRESHOLD;
+        let pan = tracker.panning.then(|| {
+            let delta = centroid - tracker.pan_last;
+            let total = centroid - tracker.pan_start;
+            Pan {
+                x: centroid.x,
+                y: centroid.y,
+                delta_x: delta.x,
+                delta_y: delta.y,
+                total_delta_x: total.x,
+                total_delta_y: total.y,
+                touch_count: tracker.touches.len(),
+            }
+        });
+        if pan.is_some() {
+            tracker.pan_last = centroid;
+        }
+        // Fingers that touched down (nearly) at the same point have no distance or angle to
+        // compare against, so the first move apart sets them instead of pinching or rotating
+        let rebaselined = tracker
+            .baseline
+            .as_ref()
+            .is_some_and(|baseline| baseline.distance < BASELINE_MIN_DISTANCE);
+        if rebaselined {
+            tracker.baseline = tracker.two_finger_state().map(|(_, baseline)| baseline);
+        }
+        let two_finger = tracker
+            .baseline
+            .as_ref()
+            .zip(tracker.two_finger_state())
+            .filter(|_| !rebaselined);
+        let (pinch, rotate) = match two_finger {
+            Some((baseline, (midpoint, current))) => {
+                let pinch = Pinch {
+                    x: midpoint.x,
+                    y: midpoint.y,
+                    scale: current.distance / baseline.distance,
+                };
+                // normalized to (-180, 180] so that crossing the negative x axis doesn't jump
+                let mut angle = current.angle - baseline.angle;
+                if angle > 180.0 {
+                    angle -= 360.0;
+                } else if angle <= -180.0 {
+                    angle += 360.0;
+                }
+                let rotate = RotateGesture {
+                    x: midpoint.x,
+                    y: midpoint.y,
+                    angle,
+                };
+                (Some(pinch), Some(rotate))
+            }
+            None => (None, None),
+        };
+
+        let ctx = &self.runtime_context;
+        let mut prevent_default = false;
+        if let Some(pan) = pan {
+            prevent_default |= node.dispatch_pan(pan, ctx);
+        }
+        if let Some(pinch) = pinch {
+            prevent_default |= node.dispatch_pinch(pinch, ctx);
+        }
+        if let Some(rotate) = rotate {
+            prevent_default |= node.dispatch_rotate_gesture(rotate, ctx);
+        }
+        prevent_default
+    }
+
+    /// Stops tracking lifted fingers, firing `swipe` if a single finger was flicked, and ends the
+    /// gesture once no fingers remain.  Returns whether any handler called `prevent_default`.
+    pub(crate) fn gesture_touch_end(&mut self, touches: &[Touch]) -> bool {
+        let now = self.runtime_context.globals().time_elapsed;
+        let Some(tracker) = self.runtime_context.gesture.as_mut() else {
+            return false;
+        };
+        let node = tracker.node.upgrade();
+        let mut swipe = None;
+        for touch in touches {
+            let Some(i) = tracker
+                .touches
+                .iter()
+                .position(|tracked| tracked.identifier == touch.identifier)
+            else {
+                continue;
+            };
+            let tracked = tracker.touches.remove(i);
+            let delta = Point2::new(touch.x, touch.y) - tracked.start;
+            let quick = now - tracker.started <= SWIPE_MAX_DURATION;
+            if !tracker.multi_touch && !tracker.long_pressed && quick {
+                swipe = swipe_direction(delta).map(|direction| Swipe {
+                    direction,
+                    delta_x: delta.x,
+                    delta_y: delta.y,
+                });
+            }
+        }
+        if tracker.touches.is_empty() {
+            self.runtime_context.gesture = None;
+        } else {
+            tracker.baseline = tracker.two_finger_state().map(|(_, baseline)| baseline);
+            tracker.reset_pan_origin();
+        }
+
+        match (node, swipe) {
+            (Some(node), Some(swipe)) => node.dispatch_swipe(swipe, &self.runtime_context),
+            _ => false,
+        }
+    }
+
+    /// Fires `long_press` once a single finger has been held still long enough.  Called every tick.
+    pub(crate) fn gesture_tick(&mut self) {
+        let now = self.runtime_context.globals().time_elapsed;
+        let Some(tracker) = self.runtime_context.gesture.as_mut() else {
+            return;
+        };
+        let [touch] = tracker.touches.as_slice() else {
+            return;
+        };
+        let held = now - tracker.started >= LONG_PRESS_DURATION;
+        let still = (touch.current - touch.start).length() <= LONG_PRESS_TOLERANCE;
+        if tracker.multi_touch || tracker.long_pressed || tracker.panning || !held || !still {
+            return;
+        }
+        tracker.long_pressed = true;
+        let args = LongPress {
+            x: touch.current.x,
+            y: touch.current.y,
+        };
+        if let Some(node) = tracker.node.upgrade() {
+            node.dispatch_long_press(args, &self.runtime_context);
+        }
+    }
+}
+
+fn swipe_direction(delta: Vector2<Window>) -> Option<SwipeDirection> {
+    if delta.length() < SWIPE_MIN_DISTANCE {
+        return None;
+    }
+    Some(if delta.x.abs() > delta.y.abs() {
+        if delta.x > 0.0 {
+            SwipeDirection::Right
+        } else {
+            SwipeDirection::Left
+        }
+    } else if delta.y > 0.0 {
+        SwipeDirection::Down
+    } else {
+        SwipeDirection::Up
+    })
+}
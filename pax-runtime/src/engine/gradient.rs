@@ -0,0 +1,120 @@
+use kurbo::{Affine, Point, Rect, Shape};
+use piet::{
+    Color, Error, FixedGradient, FixedLinearGradient, GradientStop, ImageFormat, InterpolationMode,
+    IntoBrush, NullImage, NullRenderContext, NullText, NullTextLayout, StrokeStyle,
+};
+
+/// Resolves a gradient given in piet's unit space to the fixed gradient it describes over
+/// `bbox`, so that its stops can be faded like those of any other gradient.  piet keeps the
+/// geometry and stops of unit-space gradients private, and only hands them to a render context
+/// (as a fixed gradient) when they're drawn, so they're "drawn" to a context that keeps them.
+pub(crate) fn resolve_gradient(
+    gradient: &impl IntoBrush<GradientResolver>,
+    bbox: impl FnOnce() -> Rect,
+) -> FixedGradient {
+    let mut resolver = GradientResolver(NullRenderContext::new());
+    gradient.make_brush(&mut resolver, bbox).into_owned()
+}
+
+/// A render context that draws nothing, whose brushes are the fixed gradients they're made from
+pub(crate) struct GradientResolver(NullRenderContext);
+
+impl piet::RenderContext for GradientResolver {
+    type Brush = FixedGradient;
+    type Text = NullText;
+    type TextLayout = NullTextLayout;
+    type Image = NullImage;
+
+    fn status(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn solid_brush(&mut self, color: Color) -> Self::Brush {
+        let stop = |pos| GradientStop { pos, color };
+        FixedGradient::Linear(FixedLinearGradient {
+            start: Point::ZERO,
+            end: Point::ZERO,
+            stops: vec![stop(0.0), stop(1.0)],
+        })
+    }
+
+    fn gradient(&mut self, gradient: impl Into<FixedGradient>) -> Result<Self::Brush, Error> {
+        Ok(gradient.into())
+    }
+
+    fn clear(&mut self, _region: impl Into<Option<Rect>>, _color: Color) {}
+
+    fn stroke(&mut self, _shape: impl Shape, _brush: &impl IntoBrush<Self>, _width: f64) {}
+
+    fn stroke_styled(
+        &mut self,
+        _shape: impl Shape,
+        _brush: &impl IntoBrush<Self>,
+        _width: f64,
+        _style: &StrokeStyle,
+    ) {
+    }
+
+    fn fill(&mut self, _shape: impl Shape, _brush: &impl IntoBrush<Self>) {}
+
+    fn fill_even_odd(&mut self, _shape: impl Shape, _brush: &impl IntoBrush<Self>) {}
+
+    fn clip(&mut self, _shape: impl Shape) {}
+
+    fn text(&mut self) -> &mut Self::Text {
+        self.0.text()
+    }
+
+    fn draw_text(&mut self, _layout: &Self::TextLayout, _pos: impl Into<Point>) {}
+
+    fn save(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn restore(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn transform(&mut self, _transform: Affine) {}
+
+    fn make_image(
+        &mut self,
+        _width: usize,
+        _height: usize,
+        _buf: &[u8],
+        _format: ImageFormat,
+    ) -> Result<Self::Image, Error> {
+        Ok(NullImage)
+    }
+
+    fn draw_image(
+        &mut self,
+        _image: &Self::Image,
+        _dst_rect: impl Into<Rect>,
+        _interp: InterpolationMode,
+    ) {
+    }
+
+    fn draw_image_area(
+        &mut self,
+        _image: &Self::Image,
+        _src_rect: impl Into<Rect>,
+        _dst_rect: impl Into<Rect>,
+        _interp: InterpolationMode,
+    ) {
+    }
+
+    fn capture_image_area(&mut self, _src_rect: impl Into<Rect>) -> Result<Self::Image, Error> {
+        Ok(NullImage)
+    }
+
+    fn blurred_rect(&mut self, _rect: Rect, _blur_radius: f64, _brush: &impl IntoBrush<Self>) {}
+
+    fn current_transform(&self) -> Affine {
+        Affine::default()
+    }
+}
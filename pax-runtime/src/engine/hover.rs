@@ -0,0 +1,84 @@
+use std::rc::{Rc, Weak};
+
+use pax_message::NativeMessage;
+
+use crate::api::{Cursor, MouseEventArgs, MouseOut, MouseOver};
+use crate::{ExpandedNode, PaxEngine};
+
+impl PaxEngine {
+    /// Updates the hovered node chain to `topmost` and its ancestors (or to nothing, for `None`,
+    /// e.g. when the pointer leaves the viewport).  Fires `mouse_out` on every node that is no
+    /// longer hovered, innermost first, then `mouse_over` on every newly hovered node, outermost
+    /// first.  Neither bubbles: each node whose hover state changed receives its own event.
+    /// Returns whether any handler called `prevent_default`.
+    pub(crate) fn update_hover(
+        &mut self,
+        topmost: Option<Rc<ExpandedNode>>,
+        mouse: MouseEventArgs,
+    ) -> bool {
+        let hovered = topmost
+            .map(|node| node.ancestors_and_self())
+            .unwrap_or_default();
+        let previous: Vec<Rc<ExpandedNode>> = std::mem::replace(
+            &mut self.runtime_context.hovered_nodes,
+            hovered.iter().map(Rc::downgrade).collect(),
+        )
+        .iter()
+        .filter_map(Weak::upgrade)
+        .collect();
+
+        let contains = |chain: &[Rc<ExpandedNode>], node: &Rc<ExpandedNode>| {
+            chain.iter().any(|n| Rc::ptr_eq(n, node))
+        };
+        let ctx = &self.runtime_context;
+        let mut prevent_default = false;
+        for node in previous.iter().filter(|node| !contains(&hovered, node)) {
+            let args = MouseOut {
+                mouse: mouse.clone(),
+            };
+            prevent_default |= node.dispatch_mouse_out(args, ctx);
+        }
+        for node in hovered
+            .iter()
+            .rev()
+            .filter(|node| !contains(&previous, node))
+        {
+            let args = MouseOver {
+                mouse: mouse.clone(),
+            };
+            prevent_default |= node.dispatch_mouse_over(args, ctx);
+        }
+        self.update_cursor();
+        prevent_default
+    }
+
+    /// Resolves the cursor from the hovered node chain, i.e. that of the innermost hovered node
+    /// that sets one, and tells the chassis if it changed.  Also run every tick, since `cursor`
+    /// may be bound to an expression, e.g. to show `Cursor::Grabbing` while dragging.
+    pub(crate) fn update_cursor(&mut self) {
+        let cursor = self
+            .hovered_nodes()
+            .iter()
+            .find_map(|node| node.get_cursor())
+            .unwrap_or_default();
+        if cursor != self.runtime_context.cursor {
+            self.runtime_context.cursor = cursor;
+            self.runtime_context
+                .enqueue_native_message(NativeMessage::CursorUpdate(cursor.into()));
+        }
+    }
+
+    /// The cursor shown over the hovered node chain
+    pub fn cursor(&self) -> Cursor {
+        self.runtime_context.cursor
+    }
+
+    /// The node beneath the pointer and its ancestors, innermost first, as of the last pointer move
+    pub fn hovered_nodes(&self) -> Vec<Rc<ExpandedNode>> {
+        self.runtime_context
+            .hovered_nodes
+            .iter()
+            .filter_map(Weak::upgrade)
+            .collect()
+    }
+}
@@ -0,0 +1,282 @@
+use pax_message::{ModifierKeyMessage, MouseButtonMessage, NativeInterrupt};
+
+use crate::api::math::Point2;
+use crate::api::{
+    ButtonClick, CheckboxChange, Clap, Click, ContextMenu, DoubleClick, KeyDown, KeyPress, KeyUp,
+    KeyboardEventArgs, ModifierKey, MouseButton, MouseDown, MouseEventArgs, MouseMove, MouseUp,
+    TextInput, TextboxChange, TextboxInput, Touch, TouchEnd, TouchMove, TouchStart, Wheel,
+};
+use crate::{PaxEngine, Pointer};
+
+fn mouse_event_args(
+    x: f64,
+    y: f64,
+    button: MouseButtonMessage,
+    modifiers: &[ModifierKeyMessage],
+) -> MouseEventArgs {
+    MouseEventArgs {
+        x,
+        y,
+        button: MouseButton::from(button),
+        modifiers: modifiers.iter().map(ModifierKey::from).collect(),
+    }
+}
+
+fn keyboard_event_args(
+    key: String,
+    modifiers: &[ModifierKeyMessage],
+    is_repeat: bool,
+) -> KeyboardEventArgs {
+    KeyboardEventArgs {
+        key,
+        modifiers: modifiers.iter().map(ModifierKey::from).collect(),
+        is_repeat,
+    }
+}
+
+impl PaxEngine {
+    /// Routes a `NativeInterrupt` (e.g. user input reported by a chassis) to the relevant
+    /// `ExpandedNode`s' event handlers: pointer events go to the topmost node beneath the ray,
+    /// form events to the node identified by `id_chain`, and keyboard events to the focused node.
+    /// Returns whether any handler called `prevent_default`.
+    ///
+    /// Interrupts that concern chassis-owned resources (e.g. `Image` data, which must be loaded into
+    /// the chassis' render contexts) are left for the chassis to handle and are a no-op here.
+    pub fn handle_interrupt(&mut self, interrupt: NativeInterrupt) -> bool {
+        let ctx = &self.runtime_context;
+        match interrupt {
+            NativeInterrupt::Image(_) => false,
+            NativeInterrupt::AddedLayer(_args) => false,
+            NativeInterrupt::Scroll(_args) => false,
+            NativeInterrupt::Measure(args) => {
+                // The node may have been removed since the chassis measured it
+                if let Some(node) = self.get_expanded_node(args.id_chain[0]) {
+                    node.set_native_measure((args.width, args.height));
+                }
+                false
+            }
+            NativeInterrupt::FormButtonClick(args) => {
+                let node = self
+                    .get_expanded_node(args.id_chain[0])
+                    .expect("button node exists in engine");
+                node.dispatch_button_click(ButtonClick {}, ctx)
+            }
+            NativeInterrupt::FormTextboxInput(args) => {
+                let node = self
+                    .get_expanded_node(args.id_chain[0])
+                    .expect("textbox node exists in engine");
+                node.dispatch_textbox_input(TextboxInput { text: args.text }, ctx)
+            }
+            NativeInterrupt::TextInput(args) => {
+                let node = self
+                    .get_expanded_node(args.id_chain[0])
+                    .expect("text node exists in engine");
+                node.dispatch_text_input(TextInput { text: args.text }, ctx)
+            }
+            NativeInterrupt::FormTextboxChange(args) => {
+                let node = self
+                    .get_expanded_node(args.id_chain[0])
+                    .expect("textbox node exists in engine");
+                node.dispatch_textbox_change(TextboxChange { text: args.text }, ctx)
+            }
+            NativeInterrupt::FormCheckboxToggle(args) => {
+                let node = self
+                    .get_expanded_node(args.id_chain[0])
+                    .expect("checkbox node exists in engine");
+                node.dispatch_checkbox_change(
+                    CheckboxChange {
+                        checked: args.state,
+                    },
+                    ctx,
+                )
+            }
+            NativeInterrupt::Click(args) => {
+                match ctx.get_topmost_element_beneath_ray(Point2::new(args.x, args.y)) {
+                    Some(topmost_node) => {
+                        let mouse = mouse_event_args(args.x, args.y, args.button, &args.modifiers);
+                        topmost_node.dispatch_click(Click { mouse }, ctx)
+                    }
+                    None => false,
+                }
+            }
+            NativeInterrupt::Clap(args) => {
+                match ctx.get_topmost_element_beneath_ray(Point2::new(args.x, args.y)) {
+                    Some(topmost_node) => topmost_node.dispatch_clap(
+                        Clap {
+                            x: args.x,
+                            y: args.y,
+                        },
+                        ctx,
+                    ),
+                    None => false,
+                }
+            }
+            NativeInterrupt::TouchStart(args) => {
+                let first_touch = args.touches.first().unwrap();
+                let point = Point2::new(first_touch.x, first_touch.y);
+                let touches: Vec<Touch> = args.touches.iter().map(Touch::from).collect();
+                let topmost_node = ctx.get_topmost_element_beneath_ray(point);
+                let prevent_default = match &topmost_node {
+                    Some(topmost_node) => {
+                        let prevent_default = topmost_node.dispatch_touch_start(
+                            TouchStart {
+                                touches: touches.clone(),
+                            },
+                            ctx,
+                        );
+                        // Further fingers touching down don't restart a drag that's in progress
+                        let pointer = Pointer::Touch(first_touch.identifier);
+                        if self.captured_node(pointer).is_none() {
+                            self.capture_pointer(pointer, topmost_node, point);
+                        }
+                        prevent_default
+                    }
+                    None => false,
+                };
+                self.gesture_touch_start(&touches, topmost_node.as_ref());
+                prevent_default
+            }
+            NativeInterrupt::TouchMove(args) => {
+                let first_touch = args.touches.first().unwrap();
+                let point = Point2::new(first_touch.x, first_touch.y);
+                let pointer = Pointer::Touch(first_touch.identifier);
+                let touches: Vec<Touch> = args.touches.iter().map(Touch::from).collect();
+                let mut prevent_default = match ctx.get_topmost_element_beneath_ray(point) {
+                    Some(topmost_node) => topmost_node.dispatch_touch_move(
+                        TouchMove {
+                            touches: touches.clone(),
+                        },
+                        ctx,
+                    ),
+                    None => false,
+                };
+                prevent_default |= self.move_captured_pointer(pointer, point);
+                prevent_default |= self.gesture_touch_move(&touches);
+                prevent_default
+            }
+            NativeInterrupt::TouchEnd(args) => {
+                let first_touch = args.touches.first().unwrap();
+                let point = Point2::new(first_touch.x, first_touch.y);
+                let pointer = Pointer::Touch(first_touch.identifier);
+                let touches: Vec<Touch> = args.touches.iter().map(Touch::from).collect();
+                let mut prevent_default = match ctx.get_topmost_element_beneath_ray(point) {
+                    Some(topmost_node) => topmost_node.dispatch_touch_end(
+                        TouchEnd {
+                            touches: touches.clone(),
+                        },
+                        ctx,
+                    ),
+                    None => false,
+                };
+                prevent_default |= self.release_captured_pointer(pointer, point);
+                prevent_default |= self.gesture_touch_end(&touches);
+                prevent_default
+            }
+            NativeInterrupt::KeyDown(args) => {
+                let keyboard = keyboard_event_args(args.key, &args.modifiers, args.is_repeat);
+                self.dispatch_key_down(KeyDown { keyboard })
+            }
+            NativeInterrupt::KeyUp(args) => {
+                let keyboard = keyboard_event_args(args.key, &args.modifiers, args.is_repeat);
+                self.dispatch_key_up(KeyUp { keyboard })
+            }
+            NativeInterrupt::KeyPress(args) => {
+                let keyboard = keyboard_event_args(args.key, &args.modifiers, args.is_repeat);
+                self.dispatch_key_press(KeyPress { keyboard })
+            }
+            NativeInterrupt::DoubleClick(args) => {
+                match ctx.get_topmost_element_beneath_ray(Point2::new(args.x, args.y)) {
+                    Some(topmost_node) => {
+                        let mouse = mouse_event_args(args.x, args.y, args.button, &args.modifiers);
+                        topmost_node.dispatch_double_click(DoubleClick { mouse }, ctx)
+                    }
+                    None => false,
+                }
+            }
+            NativeInterrupt::MouseMove(args) => {
+                let point = Point2::new(args.x, args.y);
+                let mouse = mouse_event_args(args.x, args.y, args.button, &args.modifiers);
+                let topmost_node = ctx.get_topmost_element_beneath_ray(point);
+                let mut prevent_default = match &topmost_node {
+                    Some(topmost_node) => topmost_node.dispatch_mouse_move(
+                        MouseMove {
+                            mouse: mouse.clone(),
+                        },
+                        ctx,
+                    ),
+                    None => false,
+                };
+                prevent_default |= self.move_captured_pointer(Pointer::Mouse, point);
+                prevent_default |= self.update_hover(topmost_node, mouse);
+                prevent_default
+            }
+            NativeInterrupt::Wheel(args) => {
+                match ctx.get_topmost_element_beneath_ray(Point2::new(args.x, args.y)) {
+                    Some(topmost_node) => {
+                        let args_wheel = Wheel {
+                            x: args.x,
+                            y: args.y,
+                            delta_x: args.delta_x,
+                            delta_y: args.delta_y,
+                            modifiers: args.modifiers.iter().map(ModifierKey::from).collect(),
+                        };
+                        topmost_node.dispatch_wheel(args_wheel, ctx)
+                    }
+                    None => false,
+                }
+            }
+            NativeInterrupt::MouseDown(args) => {
+                let point = Point2::new(args.x, args.y);
+                let topmost_node = ctx.get_topmost_element_beneath_ray(point);
+                let prevent_default = match &topmost_node {
+                    Some(topmost_node) => {
+                        let mouse = mouse_event_args(args.x, args.y, args.button, &args.modifiers);
+                        let prevent_default =
+                            topmost_node.dispatch_mouse_down(MouseDown { mouse }, ctx);
+                        // Until released, drags of the mouse go to the pressed node
+                        self.capture_pointer(Pointer::Mouse, topmost_node, point);
+                        prevent_default
+                    }
+                    None => false,
+                };
+                // As in browsers, pressing on an element focuses it (or its closest focusable
+                // ancestor) unless a handler prevented the default
+                if !prevent_default {
+                    self.focus_closest(topmost_node);
+                }
+                prevent_default
+            }
+            NativeInterrupt::MouseUp(args) => {
+                let point = Point2::new(args.x, args.y);
+                let prevent_default = match ctx.get_topmost_element_beneath_ray(point) {
+                    Some(topmost_node) => {
+                        let mouse = mouse_event_args(args.x, args.y, args.button, &args.modifiers);
+                        topmost_node.dispatch_mouse_up(MouseUp { mouse }, ctx)
+                    }
+                    None => false,
+                };
+                self.release_captured_pointer(Pointer::Mouse, point) || prevent_default
+            }
+            // Hover state is tracked by the engine from pointer moves; chassis report the pointer
+            // entering the viewport (`MouseOver`) and leaving it (`MouseOut`)
+            NativeInterrupt::MouseOver(args) => {
+                let topmost_node = ctx.get_topmost_element_beneath_ray(Point2::new(args.x, args.y));
+                let mouse = mouse_event_args(args.x, args.y, args.button, &args.modifiers);
+                self.update_hover(topmost_node, mouse)
+            }
+            NativeInterrupt::MouseOut(args) => {
+                let mouse = mouse_event_args(args.x, args.y, args.button, &args.modifiers);
+                self.update_hover(None, mouse)
+            }
+            NativeInterrupt::ContextMenu(args) => {
+                match ctx.get_topmost_element_beneath_ray(Point2::new(args.x, args.y)) {
+                    Some(topmost_node) => {
+                        let mouse = mouse_event_args(args.x, args.y, args.button, &args.modifiers);
+                        topmost_node.dispatch_context_menu(ContextMenu { mouse }, ctx)
+                    }
+                    None => false,
+                }
+            }
+        }
+    }
+}
@@ -1,18 +1,20 @@
+use kurbo::{Affine, Shape};
 use std::any::Any;
-use std::borrow::Borrow;
+use std::borrow::Cow;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
 use std::iter;
 use std::rc::Rc;
-use kurbo::Affine;
+use std::time::Duration;
 
 use pax_manifest::UniqueTemplateNodeIdentifier;
 use pax_message::{NativeMessage, OcclusionPatch};
 
+use crate::api::reactivity::{self, Dependency};
 use crate::api::{
-    CommonProperties, Interpolatable, KeyDown, KeyPress, KeyUp, Layer, NodeContext,
-    OcclusionLayerGen, RenderContext, TransitionManager,
+    CommonProperties, Interpolatable, Layer, NodeContext, OcclusionLayerGen, RenderContext,
+    TransitionDuration, TransitionManager,
 };
 use piet::InterpolationMode;
 
@@ -22,6 +24,15 @@ use crate::{
     RuntimePropertiesStackFrame, TransformAndBounds,
 };
 
+mod drag;
+mod focus;
+mod gestures;
+mod gradient;
+mod hover;
+mod interrupts;
+pub use drag::Pointer;
+pub(crate) use drag::PointerCapture;
+pub(crate) use gestures::GestureTracker;
 pub mod node_interface;
 
 /// The atomic unit of rendering; also the container for each unique tuple of computed properties.
@@ -39,6 +50,9 @@ use pax_designtime::DesigntimeManager;
 #[cfg_attr(debug_assertions, derive(Debug))]
 pub struct Globals {
     pub frames_elapsed: usize,
+    /// Monotonic time since the engine's first tick, as reported by the chassis (see
+    /// [`PaxEngine::set_clock`])
+    pub time_elapsed: Duration,
     pub viewport: TransformAndBounds,
     #[cfg(feature = "designtime")]
     pub designtime: Rc<RefCell<DesigntimeManager>>,
@@ -49,18 +63,24 @@ pub struct PaxEngine {
     pub runtime_context: RuntimeContext,
     pub root_node: Rc<ExpandedNode>,
     main_component_instance: Rc<ComponentInstance>,
+    /// The first timestamp passed to `set_clock`, from which `Globals::time_elapsed` is measured
+    clock_origin: Option<Duration>,
 }
 
+/// How far the clock advances per tick for chassis that don't call `PaxEngine::set_clock`
+const NOMINAL_FRAME_DURATION: Duration = Duration::from_nanos(1_000_000_000 / 60);
+
 //This trait is used strictly to side-load the `compute_properties` function onto CommonProperties,
 //so that it can use the type RenderTreeContext (defined in pax_runtime, which depends on crate::api, which
 //defines CommonProperties, and which can thus not depend on pax_runtime due to a would-be circular dependency.)
 pub trait PropertiesComputable {
+    /// Returns the names of the properties whose transition queues drained during this update
     fn compute_properties(
         &mut self,
         stack: &Rc<RuntimePropertiesStackFrame>,
         table: &ExpressionTable,
         globals: &Globals,
-    );
+    ) -> Vec<&'static str>;
 }
 
 impl PropertiesComputable for CommonProperties {
@@ -69,19 +89,105 @@ impl PropertiesComputable for CommonProperties {
         stack: &Rc<RuntimePropertiesStackFrame>,
         table: &ExpressionTable,
         globals: &Globals,
-    ) {
-        handle_vtable_update(table, stack, &mut self.width, globals);
-        handle_vtable_update(table, stack, &mut self.height, globals);
-        handle_vtable_update(table, stack, &mut self.transform, globals);
-        handle_vtable_update_optional(table, stack, self.rotate.as_mut(), globals);
-        handle_vtable_update_optional(table, stack, self.scale_x.as_mut(), globals);
-        handle_vtable_update_optional(table, stack, self.scale_y.as_mut(), globals);
-        handle_vtable_update_optional(table, stack, self.skew_x.as_mut(), globals);
-        handle_vtable_update_optional(table, stack, self.skew_y.as_mut(), globals);
-        handle_vtable_update_optional(table, stack, self.anchor_x.as_mut(), globals);
-        handle_vtable_update_optional(table, stack, self.anchor_y.as_mut(), globals);
-        handle_vtable_update_optional(table, stack, self.x.as_mut(), globals);
-        handle_vtable_update_optional(table, stack, self.y.as_mut(), globals);
+    ) -> Vec<&'static str> {
+        let finished = [
+            (
+                "width",
+                handle_vtable_update(table, stack, &mut self.width, globals),
+            ),
+            (
+                "height",
+                handle_vtable_update(table, stack, &mut self.height, globals),
+            ),
+            (
+                "transform",
+                handle_vtable_update(table, stack, &mut self.transform, globals),
+            ),
+            (
+                "rotate",
+                handle_vtable_update_optional(table, stack, self.rotate.as_mut(), globals),
+            ),
+            (
+                "scale_x",
+                handle_vtable_update_optional(table, stack, self.scale_x.as_mut(), globals),
+            ),
+            (
+                "scale_y",
+                handle_vtable_update_optional(table, stack, self.scale_y.as_mut(), globals),
+            ),
+            (
+                "skew_x",
+                handle_vtable_update_optional(table, stack, self.skew_x.as_mut(), globals),
+            ),
+            (
+                "skew_y",
+                handle_vtable_update_optional(table, stack, self.skew_y.as_mut(), globals),
+            ),
+            (
+                "anchor_x",
+                handle_vtable_update_optional(table, stack, self.anchor_x.as_mut(), globals),
+            ),
+            (
+                "anchor_y",
+                handle_vtable_update_optional(table, stack, self.anchor_y.as_mut(), globals),
+            ),
+            (
+                "x",
+                handle_vtable_update_optional(table, stack, self.x.as_mut(), globals),
+            ),
+            (
+                "y",
+                handle_vtable_update_optional(table, stack, self.y.as_mut(), globals),
+            ),
+            (
+                "tab_index",
+                handle_vtable_update_optional(table, stack, self.tab_index.as_mut(), globals),
+            ),
+            (
+                "opacity",
+                handle_vtable_update_optional(table, stack, self.opacity.as_mut(), globals),
+            ),
+            (
+                "visible",
+                handle_vtable_update_optional(table, stack, self.visible.as_mut(), globals),
+            ),
+            (
+                "pointer_events",
+                handle_vtable_update_optional(table, stack, self.pointer_events.as_mut(), globals),
+            ),
+            (
+                "z_index",
+                handle_vtable_update_optional(table, stack, self.z_index.as_mut(), globals),
+            ),
+            (
+                "cursor",
+                handle_vtable_update_optional(table, stack, self.cursor.as_mut(), globals),
+            ),
+            (
+                "min_width",
+                handle_vtable_update_optional(table, stack, self.min_width.as_mut(), globals),
+            ),
+            (
+                "max_width",
+                handle_vtable_update_optional(table, stack, self.max_width.as_mut(), globals),
+            ),
+            (
+                "min_height",
+                handle_vtable_update_optional(table, stack, self.min_height.as_mut(), globals),
+            ),
+            (
+                "max_height",
+                handle_vtable_update_optional(table, stack, self.max_height.as_mut(), globals),
+            ),
+            (
+                "padding",
+                handle_vtable_update_optional(table, stack, self.padding.as_mut(), globals),
+            ),
+        ];
+        finished
+            .into_iter()
+            .filter_map(|(name, finished)| finished.then_some(name))
+            .collect()
     }
 }
 
@@ -127,19 +233,82 @@ impl Default for HandlerRegistry {
     }
 }
 
+/// piet backends that can draw into an offscreen layer and composite it onto the content
+/// beneath as a whole, so that a translucent group's overlapping content doesn't show through
+pub trait LayerCompositing {
+    /// Directs subsequent draws to a new, transparent layer above the current one, to be
+    /// composited at `opacity`
+    fn push_layer(&mut self, opacity: f64);
+    /// Composites the topmost layer onto the one beneath it, and discards it
+    fn pop_layer(&mut self);
+}
+
+/// piet backends that can blur any shape they draw, rather than only rectangles (as with
+/// `blurred_rect`)
+pub trait NativeBlur {
+    /// Like `fill` with `color`, with the result blurred by `blur_radius`, the standard deviation
+    /// in pixels of the blur
+    fn fill_blurred(&mut self, path: kurbo::BezPath, color: piet::Color, blur_radius: f64);
+    /// Like `stroke` with `color`, with the result blurred as for `fill_blurred`
+    fn stroke_blurred(
+        &mut self,
+        path: kurbo::BezPath,
+        color: piet::Color,
+        width: f64,
+        blur_radius: f64,
+    );
+}
+
+/// `LayerCompositing::push_layer` and `pop_layer`
+type LayerCompositingFns<R> = (fn(&mut R, f64), fn(&mut R));
+
+/// `NativeBlur::fill_blurred` and `stroke_blurred`
+type NativeBlurFns<R> = (
+    fn(&mut R, kurbo::BezPath, piet::Color, f64),
+    fn(&mut R, kurbo::BezPath, piet::Color, f64, f64),
+);
+
+/// What `restore` undoes on a layer, besides restoring its piet state
+struct SavedLayer {
+    /// The opacity draws are faded by until the matching `restore`
+    opacity: f64,
+    /// Whether an offscreen layer was pushed, to be popped by the matching `restore`
+    composited: bool,
+}
+
 pub struct Renderer<R: piet::RenderContext> {
     pub backends: HashMap<String, R>,
     pub image_map: HashMap<String, R::Image>,
+    /// Per layer, the `save`s and `save_layer`s in effect, innermost last
+    saved_layers: HashMap<String, Vec<SavedLayer>>,
+    /// For backends that support them, their `LayerCompositing` and `NativeBlur` methods
+    layer_compositing: Option<LayerCompositingFns<R>>,
+    native_blur: Option<NativeBlurFns<R>>,
 }
 
 impl<R: piet::RenderContext> Renderer<R> {
+    /// A renderer that fades each draw within a translucent group individually; overlapping
+    /// content within such a group shows through, and images within it are drawn opaque.  Shapes
+    /// other than rectangles are blurred approximately.  See [`Self::with_layer_compositing`]
+    /// and [`Self::with_native_blur`] for backends that can do better.
     pub fn new() -> Self {
         Self {
             backends: HashMap::new(),
             image_map: HashMap::new(),
+            saved_layers: HashMap::new(),
+            layer_compositing: None,
+            native_blur: None,
         }
     }
 
+    /// The opacity that draws to `layer` are currently faded by
+    fn opacity(&self, layer: &str) -> f64 {
+        self.saved_layers
+            .get(layer)
+            .and_then(|saved_layers| saved_layers.last())
+            .map_or(1.0, |saved_layer| saved_layer.opacity)
+    }
+
     pub fn add_context(&mut self, id: &str, context: R) {
         self.backends.insert(id.to_owned(), context);
     }
@@ -153,9 +322,31 @@ impl<R: piet::RenderContext> Renderer<R> {
     }
 }
 
+impl<R: piet::RenderContext + LayerCompositing> Renderer<R> {
+    /// Makes this renderer composite each translucent group offscreen, as a whole
+    pub fn with_layer_compositing(self) -> Self {
+        Self {
+            layer_compositing: Some((R::push_layer, R::pop_layer)),
+            ..self
+        }
+    }
+}
+
+impl<R: piet::RenderContext + NativeBlur> Renderer<R> {
+    /// Makes this renderer have the backend blur solid fills and strokes, rather than
+    /// approximating the blur of shapes other than rectangles
+    pub fn with_native_blur(self) -> Self {
+        Self {
+            native_blur: Some((R::fill_blurred, R::stroke_blurred)),
+            ..self
+        }
+    }
+}
+
 impl<R: piet::RenderContext> crate::api::RenderContext for Renderer<R> {
     fn fill(&mut self, layer: &str, path: kurbo::BezPath, brush: &piet_common::PaintBrush) {
-        self.backends.get_mut(layer).unwrap().fill(path, brush);
+        let brush = faded(brush, self.opacity(layer), || path.bounding_box());
+        self.backends.get_mut(layer).unwrap().fill(path, &*brush);
     }
 
     fn stroke(
@@ -165,25 +356,120 @@ impl<R: piet::RenderContext> crate::api::RenderContext for Renderer<R> {
         brush: &piet_common::PaintBrush,
         width: f64,
     ) {
+        let brush = faded(brush, self.opacity(layer), || path.bounding_box());
         self.backends
             .get_mut(layer)
             .unwrap()
-            .stroke(path, brush, width);
+            .stroke(path, &*brush, width);
+    }
+
+    fn fill_blurred(
+        &mut self,
+        layer: &str,
+        path: kurbo::BezPath,
+        brush: &piet_common::PaintBrush,
+        blur_radius: f64,
+    ) {
+        if blur_radius <= 0.0 {
+            return self.fill(layer, path, brush);
+        }
+        let opacity = self.opacity(layer);
+        let backend = self.backends.get_mut(layer).unwrap();
+        if let (Some((fill_blurred, _)), piet_common::PaintBrush::Color(color)) =
+            (self.native_blur, brush)
+        {
+            let color = color.with_alpha(color.as_rgba().3 * opacity);
+            return fill_blurred(backend, path, color, blur_radius);
+        }
+        // Otherwise, axis-aligned rectangles, e.g. most elevation shadows, can still be blurred
+        // by the backend
+        let bbox = path.bounding_box();
+        if bbox.area() <= 0.0 {
+            return;
+        }
+        if (path.area().abs() - bbox.area()).abs() <= 1e-6 * bbox.area().max(1.0) {
+            backend.blurred_rect(bbox, blur_radius, &*faded(brush, opacity, || bbox));
+            return;
+        }
+        // Other shapes are approximated by stacking translucent copies, scaled about their
+        // center, so that the edge fades out over the span a box blur would spread it across
+        let center = bbox.center();
+        for (offset, layer_opacity) in blur_steps(blur_radius) {
+            let scale_x = (bbox.width() + 2.0 * offset) / bbox.width();
+            let scale_y = (bbox.height() + 2.0 * offset) / bbox.height();
+            if !(scale_x > 0.0 && scale_y > 0.0) {
+                continue;
+            }
+            let scale = Affine::translate(center.to_vec2())
+                * Affine::scale_non_uniform(scale_x, scale_y)
+                * Affine::translate(-center.to_vec2());
+            let brush = faded(brush, opacity * layer_opacity, || bbox);
+            backend.fill(scale * path.clone(), &*brush);
+        }
+    }
+
+    fn stroke_blurred(
+        &mut self,
+        layer: &str,
+        path: kurbo::BezPath,
+        brush: &piet_common::PaintBrush,
+        width: f64,
+        blur_radius: f64,
+    ) {
+        if blur_radius <= 0.0 {
+            return self.stroke(layer, path, brush, width);
+        }
+        let opacity = self.opacity(layer);
+        let backend = self.backends.get_mut(layer).unwrap();
+        if let (Some((_, stroke_blurred)), piet_common::PaintBrush::Color(color)) =
+            (self.native_blur, brush)
+        {
+            let color = color.with_alpha(color.as_rgba().3 * opacity);
+            return stroke_blurred(backend, path, color, width, blur_radius);
+        }
+        let bbox = path.bounding_box();
+        // As for fills, with each copy's band around the path widened rather than scaled
+        for (offset, layer_opacity) in blur_steps(blur_radius) {
+            let width = width + 2.0 * offset;
+            if width <= 0.0 {
+                continue;
+            }
+            let brush = faded(brush, opacity * layer_opacity, || bbox);
+            backend.stroke(&path, &*brush, width);
+        }
     }
 
     fn save(&mut self, layer: &str) {
-        self.backends
-            .get_mut(layer)
-            .unwrap()
-            .save()
-            .expect("failed to save piet state");
+        self.save_layer(layer, 1.0);
+    }
+
+    fn save_layer(&mut self, layer: &str, opacity: f64) {
+        let opacity = opacity.clamp(0.0, 1.0);
+        let backend = self.backends.get_mut(layer).unwrap();
+        backend.save().expect("failed to save piet state");
+        let saved_layer = match self.layer_compositing {
+            Some((push_layer, _)) if opacity < 1.0 => {
+                push_layer(backend, opacity);
+                SavedLayer {
+                    opacity: 1.0,
+                    composited: true,
+                }
+            }
+            // Opaque groups need no layer of their own; without layer compositing, each draw
+            // within a translucent group is faded instead
+            _ => SavedLayer {
+                opacity: self.opacity(layer) * opacity,
+                composited: false,
+            },
+        };
+        self.saved_layers
+            .entry(layer.to_owned())
+            .or_default()
+            .push(saved_layer);
     }
 
     fn transform(&mut self, layer: &str, affine: Affine) {
-        self.backends
-            .get_mut(layer)
-            .unwrap()
-            .transform(affine);
+        self.backends.get_mut(layer).unwrap().transform(affine);
     }
 
     fn clip(&mut self, layer: &str, path: kurbo::BezPath) {
@@ -191,11 +477,17 @@ impl<R: piet::RenderContext> crate::api::RenderContext for Renderer<R> {
     }
 
     fn restore(&mut self, layer: &str) {
-        self.backends
+        let saved_layer = self
+            .saved_layers
             .get_mut(layer)
-            .unwrap()
-            .restore()
-            .expect("failed to restore piet state");
+            .and_then(|saved_layers| saved_layers.pop());
+        let backend = self.backends.get_mut(layer).unwrap();
+        if let Some((_, pop_layer)) = self.layer_compositing {
+            if saved_layer.is_some_and(|saved_layer| saved_layer.composited) {
+                pop_layer(backend);
+            }
+        }
+        backend.restore().expect("failed to restore piet state");
     }
 
     fn load_image(&mut self, path: &str, buf: &[u8], width: usize, height: usize) {
@@ -208,6 +500,10 @@ impl<R: piet::RenderContext> crate::api::RenderContext for Renderer<R> {
     }
 
     fn draw_image(&mut self, layer: &str, image_path: &str, rect: kurbo::Rect) {
+        // piet can't draw images translucently, so they're only faded by layer compositing
+        if self.opacity(layer) <= 0.0 {
+            return;
+        }
         let Some(img) = self.image_map.get(image_path) else {
             return;
         };
@@ -222,6 +518,46 @@ impl<R: piet::RenderContext> crate::api::RenderContext for Renderer<R> {
     }
 }
 
+/// The outward offset and opacity of each of the nested copies drawn, largest first, to blur a
+/// shape by `blur_radius`.  Each copy is opaque enough that coverage ramps linearly from the
+/// outermost copy's edge to the innermost's, as across the edge of a box-blurred shape: a box
+/// blur with the same standard deviation spreads an edge over `2 * sqrt(3) * blur_radius`.
+fn blur_steps(blur_radius: f64) -> impl Iterator<Item = (f64, f64)> {
+    let half_span = 3f64.sqrt() * blur_radius;
+    let steps = ((2.0 * half_span).ceil() as usize).clamp(2, 16);
+    (0..steps).map(move |step| {
+        let offset = half_span * (1.0 - (2 * step + 1) as f64 / steps as f64);
+        (offset, 1.0 / (steps - step) as f64)
+    })
+}
+
+/// `brush` with its alpha multiplied by `opacity`.  Gradients in piet's unit space are first
+/// resolved over `bbox`, the bounds of the shape they're drawn on, as the backend would.
+fn faded(
+    brush: &piet_common::PaintBrush,
+    opacity: f64,
+    bbox: impl FnOnce() -> kurbo::Rect,
+) -> Cow<'_, piet_common::PaintBrush> {
+    use piet_common::{FixedGradient, PaintBrush};
+    if opacity >= 1.0 {
+        return Cow::Borrowed(brush);
+    }
+    let fade = |color: &piet::Color| color.with_alpha(color.as_rgba().3 * opacity);
+    let mut gradient = match brush {
+        PaintBrush::Color(color) => return Cow::Owned(PaintBrush::Color(fade(color))),
+        PaintBrush::Fixed(gradient) => gradient.clone(),
+        unit_space_gradient => gradient::resolve_gradient(unit_space_gradient, bbox),
+    };
+    let stops = match &mut gradient {
+        FixedGradient::Linear(linear) => &mut linear.stops,
+        FixedGradient::Radial(radial) => &mut radial.stops,
+    };
+    for stop in stops.iter_mut() {
+        stop.color = fade(&stop.color);
+    }
+    Cow::Owned(PaintBrush::Fixed(gradient))
+}
+
 pub struct ExpressionTable {
     pub table: HashMap<usize, Box<dyn Fn(ExpressionContext) -> Box<dyn Any>>>,
 }
@@ -248,6 +584,16 @@ impl ExpressionTable {
         }
     }
 
+    /// Evaluates the expression at `vtable_id`, returning its value alongside the
+    /// properties read during evaluation, for use in dirty-checking on subsequent ticks
+    pub fn compute_tracked_vtable_value(
+        &self,
+        stack: &Rc<RuntimePropertiesStackFrame>,
+        vtable_id: usize,
+    ) -> (Box<dyn Any>, Vec<Dependency>) {
+        reactivity::track_dependencies(|| self.compute_vtable_value(stack, vtable_id))
+    }
+
     pub fn compute_eased_value<T: Clone + Interpolatable>(
         &self,
         transition_manager: Option<&mut TransitionManager<T>>,
@@ -258,36 +604,92 @@ impl ExpressionTable {
                 let current_transition = tm.queue.get_mut(0).unwrap();
                 if let None = current_transition.global_frame_started {
                     current_transition.global_frame_started = Some(globals.frames_elapsed);
+                    // A spring runs until it settles, however long that takes
+                    if let Some(spring) = current_transition.spring_motion() {
+                        current_transition.duration = spring.settling_time().into();
+                    }
                 }
-                let progress = (1.0 + globals.frames_elapsed as f64
-                    - current_transition.global_frame_started.unwrap() as f64)
-                    / (current_transition.duration_frames as f64);
+                // A transition that interrupts another starts from the value computed on the
+                // last tick, so it picks up from that moment rather than this one
+                current_transition
+                    .global_time_started
+                    .get_or_insert(tm.last_updated.unwrap_or(globals.time_elapsed));
+                let progress = match current_transition.duration {
+                    TransitionDuration::Frames(duration_frames) => {
+                        (1.0 + globals.frames_elapsed as f64
+                            - current_transition.global_frame_started.unwrap() as f64)
+                            / (duration_frames as f64)
+                    }
+                    TransitionDuration::Time(duration) if duration.is_zero() => 1.0,
+                    TransitionDuration::Time(duration) => {
+                        let elapsed = globals
+                            .time_elapsed
+                            .saturating_sub(current_transition.global_time_started.unwrap());
+                        elapsed.as_secs_f64() / duration.as_secs_f64()
+                    }
+                };
                 return if progress >= 1.0 {
                     //NOTE: we may encounter float imprecision here, consider `progress >= 1.0 - EPSILON` for some `EPSILON`
-                    let new_value = current_transition.curve.interpolate(
-                        &current_transition.starting_value,
-                        &current_transition.ending_value,
-                        progress,
-                    );
-                    tm.value = Some(new_value.clone());
-
-                    tm.queue.pop_front();
+                    // (clamped, since a slow frame can land well past the end of a time-based transition)
+                    let new_value = current_transition
+                        .starting_value
+                        .interpolate(&current_transition.ending_value, 1.0);
+                    Self::update_transition_value(tm, new_value, globals);
+
+                    let finished = tm.queue.pop_front().unwrap();
+                    // A queued time-based transition picks up exactly where this one ended,
+                    // rather than at the (possibly later) current frame
+                    if let (TransitionDuration::Time(duration), Some(next)) =
+                        (finished.duration, tm.queue.front_mut())
+                    {
+                        next.global_time_started =
+                            Some(finished.global_time_started.unwrap() + duration);
+                    }
                     self.compute_eased_value(Some(tm), globals)
                 } else {
-                    let new_value = current_transition.curve.interpolate(
-                        &current_transition.starting_value,
-                        &current_transition.ending_value,
-                        progress,
-                    );
-                    tm.value = Some(new_value.clone());
+                    let multiplier = match (
+                        current_transition.spring_motion(),
+                        current_transition.duration,
+                    ) {
+                        (Some(spring), TransitionDuration::Time(duration)) => {
+                            spring.position(progress * duration.as_secs_f64())
+                        }
+                        _ => current_transition.curve.evaluate(progress),
+                    };
+                    let new_value = current_transition
+                        .starting_value
+                        .interpolate(&current_transition.ending_value, multiplier);
+                    Self::update_transition_value(tm, new_value, globals);
                     tm.value.clone()
                 };
             } else {
-                return tm.value.clone();
+                // Queue is drained: emit the settled value one last time, then release the
+                // transition manager so this property stops being written (and dirtying its dependents) every tick
+                tm.velocity = 0.0;
+                tm.last_updated = None;
+                return tm.value.take();
             }
         }
         None
     }
+
+    /// Records a transitioning property's newly computed value, along with how fast it's moving
+    fn update_transition_value<T: Interpolatable>(
+        tm: &mut TransitionManager<T>,
+        new_value: T,
+        globals: &Globals,
+    ) {
+        let now = globals.time_elapsed;
+        if let (Some(previous), Some(last_updated)) = (&tm.value, tm.last_updated) {
+            let elapsed = now.saturating_sub(last_updated).as_secs_f64();
+            if elapsed > 0.0 {
+                let distance = previous.displacement_to(&new_value).unwrap_or(0.0);
+                tm.velocity = distance / elapsed;
+            }
+        }
+        tm.value = Some(new_value);
+        tm.last_updated = Some(now);
+    }
 }
 
 /// Central instance of the PaxEngine and runtime, intended to be created by a particular chassis.
@@ -304,6 +706,7 @@ impl PaxEngine {
 
         let globals = Globals {
             frames_elapsed: 0,
+            time_elapsed: Duration::ZERO,
             viewport: TransformAndBounds {
                 transform: Transform2::identity(),
                 bounds: viewport_size,
@@ -319,6 +722,7 @@ impl PaxEngine {
             runtime_context,
             root_node,
             main_component_instance,
+            clock_origin: None,
         }
     }
 
@@ -332,6 +736,7 @@ impl PaxEngine {
         use pax_runtime_api::math::Transform2;
         let globals = Globals {
             frames_elapsed: 0,
+            time_elapsed: Duration::ZERO,
             viewport: TransformAndBounds {
                 transform: Transform2::default(),
                 bounds: viewport_size,
@@ -348,6 +753,7 @@ impl PaxEngine {
             runtime_context,
             root_node,
             main_component_instance,
+            clock_origin: None,
         }
     }
 
@@ -447,10 +853,12 @@ impl PaxEngine {
     ///     b. start rendering, from lowest node on-up, throughout tree
     pub fn tick(&mut self) -> Vec<NativeMessage> {
         //
-        // 1. UPDATE NODES (properties, etc.). This part we should be able to
-        // completely remove once reactive properties dirty-dag is a thing.
+        // 1. UPDATE NODES (properties, etc.). Only expressions whose dependencies
+        // changed since last tick are re-evaluated (see `api::reactivity`), and
+        // layout is only recomputed for nodes affected by those changes.
         //
         self.root_node.recurse_update(&mut self.runtime_context);
+        self.update_cursor();
 
         // 2. LAYER-IDS, z-index list creation Will always be recomputed each
         // frame. Nothing intensive is to be done here.
@@ -484,7 +892,13 @@ impl PaxEngine {
             *curr_occlusion_ind = new_occlusion_ind;
         }
 
+        self.gesture_tick();
+        self.runtime_context.advance_timelines();
+
         self.runtime_context.globals_mut().frames_elapsed += 1;
+        if self.clock_origin.is_none() {
+            self.runtime_context.globals_mut().time_elapsed += NOMINAL_FRAME_DURATION;
+        }
 
         self.runtime_context.take_native_messages()
     }
@@ -501,47 +915,18 @@ impl PaxEngine {
         self.runtime_context.node_cache.get(&id)
     }
 
+    /// Called by chassis before each tick with the current time on a monotonic clock (e.g.
+    /// `performance.now()`, relative to any fixed origin).  `Globals::time_elapsed` is measured
+    /// from the first time passed here and never decreases.  Chassis that never call this get a
+    /// clock advancing by a nominal 60fps frame each tick.
+    pub fn set_clock(&mut self, now: Duration) {
+        let origin = *self.clock_origin.get_or_insert(now);
+        let globals = self.runtime_context.globals_mut();
+        globals.time_elapsed = globals.time_elapsed.max(now.saturating_sub(origin));
+    }
+
     /// Called by chassis when viewport size changes, e.g. with native window resizes
     pub fn set_viewport_size(&mut self, new_viewport_size: (f64, f64)) {
         self.runtime_context.globals_mut().viewport.bounds = new_viewport_size;
     }
-
-    pub fn global_dispatch_key_down(&self, args: KeyDown) {
-        self.root_node.recurse_visit_postorder(
-            &|expanded_node, _| {
-                expanded_node.dispatch_key_down(
-                    args.clone(),
-                    self.runtime_context.globals(),
-                    &self.runtime_context,
-                );
-            },
-            &mut (),
-        );
-    }
-
-    pub fn global_dispatch_key_up(&self, args: KeyUp) {
-        self.root_node.recurse_visit_postorder(
-            &|expanded_node, _| {
-                expanded_node.dispatch_key_up(
-                    args.clone(),
-                    self.runtime_context.globals(),
-                    &self.runtime_context,
-                );
-            },
-            &mut (),
-        );
-    }
-
-    pub fn global_dispatch_key_press(&self, args: KeyPress) {
-        self.root_node.recurse_visit_postorder(
-            &|expanded_node, _| {
-                expanded_node.dispatch_key_press(
-                    args.clone(),
-                    self.runtime_context.globals(),
-                    &self.runtime_context,
-                );
-            },
-            &mut (),
-        );
-    }
 }
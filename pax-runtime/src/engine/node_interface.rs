@@ -20,6 +20,12 @@ impl From<Rc<ExpandedNode>> for NodeInterface {
     }
 }
 
+impl PartialEq for NodeInterface {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
 pub struct NodeLocal;
 
 pub struct Properties {
@@ -10,8 +10,12 @@ pub fn compute_tab(node: &ExpandedNode, container_tab: &TransformAndBounds) -> T
     //it as the new accumulated bounds: both for this node's children (their parent container bounds)
     //and for this node itself (e.g. for specifying the size of a Rectangle node)
 
-    let new_accumulated_bounds_and_current_node_size =
-        { node.get_size_computed(container_tab.bounds) };
+    // Items of layout containers fill the cell they're laid out in, within their size limits
+    let new_accumulated_bounds_and_current_node_size = if node.layout_cell.borrow().is_some() {
+        node.clamp_size(container_tab.bounds, container_tab.bounds)
+    } else {
+        node.get_size_computed(container_tab.bounds)
+    };
 
     let node_transform_property_computed = {
         node.get_common_properties()
@@ -119,6 +123,23 @@ pub fn compute_tab(node: &ExpandedNode, container_tab: &TransformAndBounds) -> T
     }
 }
 
+/// The area `node`'s children are laid out in: its `tab` inset on each side by its `padding`,
+/// which is resolved against `container_bounds` like its size
+pub fn compute_content_tab(
+    node: &ExpandedNode,
+    tab: &TransformAndBounds,
+    container_bounds: (f64, f64),
+) -> TransformAndBounds {
+    let (x, y) = node.get_padding(container_bounds);
+    TransformAndBounds {
+        transform: tab.transform * Transform2::translate(Vector2::new(x, y)),
+        bounds: (
+            (tab.bounds.0 - 2.0 * x).max(0.0),
+            (tab.bounds.1 - 2.0 * y).max(0.0),
+        ),
+    }
+}
+
 pub trait ComputableTransform {
     fn compute_transform2d_matrix(
         &self,
@@ -140,18 +161,21 @@ impl ComputableTransform for Transform2D {
         // b.) decompose "vanilla" affine matrix
         // c.) combine with previous transform chain (assembled via multiplication of two Transform2Ds, e.g. in PAXEL)
 
-        // Compute anchor
+        // Compute anchor.  An anchor has no content of its own to measure, so `Auto` falls back
+        // to 100% of the node's size, as it does anywhere content can't be measured
         let anchor_transform = match &self.anchor {
             Some(anchor) => Transform2::translate(Vector2::<Generic>::new(
                 match anchor[0] {
                     Size::Pixels(pix) => -pix.to_float(),
                     Size::Percent(per) => -node_size.0 * (per / 100.0),
                     Size::Combined(pix, per) => -pix.to_float() + (-node_size.0 * (per / 100.0)),
+                    Size::Auto => -node_size.0,
                 },
                 match anchor[1] {
                     Size::Pixels(pix) => -pix.to_float(),
                     Size::Percent(per) => -node_size.1 * (per / 100.0),
                     Size::Combined(pix, per) => -pix.to_float() + (-node_size.0 * (per / 100.0)),
+                    Size::Auto => -node_size.1,
                 },
             )),
             //No anchor applied: treat as 0,0; identity matrix
@@ -13,6 +13,7 @@ pub mod properties;
 pub mod rendering;
 pub mod repeat;
 pub mod slot;
+pub mod timelines;
 
 pub use crate::component::*;
 pub use crate::conditional::*;
@@ -1,13 +1,14 @@
 use crate::api::math::Point2;
-use crate::api::Window;
+use crate::api::{Cursor, Timeline, Window};
 use crate::numeric::Numeric;
+use kurbo::Shape;
 use pax_manifest::UniqueTemplateNodeIdentifier;
 use pax_message::NativeMessage;
 use std::cell::RefCell;
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
 use std::{any::Any, collections::HashMap};
 
-use crate::{ExpandedNode, ExpressionTable, Globals};
+use crate::{ExpandedNode, ExpressionTable, GestureTracker, Globals, Pointer, PointerCapture};
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(debug_assertions, derive(Debug))]
@@ -28,6 +29,18 @@ pub struct RuntimeContext {
     pub z_index_node_cache: Vec<Rc<ExpandedNode>>,
     pub node_cache: HashMap<u32, Rc<ExpandedNode>>,
     pub uni_to_eid: HashMap<UniqueTemplateNodeIdentifier, Vec<u32>>,
+    /// The node currently receiving keyboard events, if any (see `PaxEngine::focus`)
+    pub(crate) focused_node: Option<Weak<ExpandedNode>>,
+    /// Each pressed pointer and the node it was pressed on (see `PaxEngine::capture_pointer`)
+    pub(crate) pointer_captures: HashMap<Pointer, PointerCapture>,
+    /// The touch gesture in progress, if any
+    pub(crate) gesture: Option<GestureTracker>,
+    /// The node beneath the pointer and its ancestors, innermost first (see `PaxEngine::update_hover`)
+    pub(crate) hovered_nodes: Vec<Weak<ExpandedNode>>,
+    /// The cursor last sent to the chassis (see `PaxEngine::update_cursor`)
+    pub(crate) cursor: Cursor,
+    /// The timelines of all live components, advanced once per tick (see `register_timeline`)
+    pub(crate) timelines: Vec<Weak<RefCell<Timeline>>>,
 }
 
 impl RuntimeContext {
@@ -40,6 +53,12 @@ impl RuntimeContext {
             z_index_node_cache: vec![],
             node_cache: HashMap::default(),
             uni_to_eid: HashMap::default(),
+            focused_node: None,
+            pointer_captures: HashMap::default(),
+            gesture: None,
+            hovered_nodes: Vec::new(),
+            cursor: Cursor::Default,
+            timelines: Vec::new(),
         }
     }
 
@@ -95,6 +114,7 @@ impl RuntimeContext {
         //First: check whether events are suppressed
         //Next: check whether ancestral clipping bounds (hit_test) are satisfied
         //Finally: check whether element itself satisfies hit_test(ray)
+        let point = kurbo::Point::new(ray.x, ray.y);
 
         for node in self.z_index_node_cache.iter().rev().skip(1) {
             if node.ray_cast_test(ray) {
@@ -108,10 +128,13 @@ impl RuntimeContext {
 
                 loop {
                     if let Some(unwrapped_parent) = parent {
-                        if let Some(_) = unwrapped_parent.get_clipping_size() {
-                            ancestral_clipping_bounds_are_satisfied =
-                                (*unwrapped_parent).ray_cast_test(ray);
-                            break;
+                        // Every clipping ancestor, e.g. each of several nested `Frame`s, must
+                        // contain the ray
+                        if let Some(clip_path) = unwrapped_parent.get_clip_path() {
+                            if !clip_path.contains(point) {
+                                ancestral_clipping_bounds_are_satisfied = false;
+                                break;
+                            }
                         }
                         parent = unwrapped_parent.parent_expanded_node.borrow().upgrade();
                     } else {
@@ -142,6 +165,11 @@ impl RuntimeContext {
         }
     }
 
+    /// The node currently holding keyboard focus, if any
+    pub fn focused_node(&self) -> Option<Rc<ExpandedNode>> {
+        self.focused_node.as_ref().and_then(Weak::upgrade)
+    }
+
     pub fn gen_uid(&mut self) -> Uid {
         self.next_uid.0 += 1;
         self.next_uid
@@ -171,7 +199,7 @@ impl RuntimeContext {
 /// Data structure for a single frame of our runtime stack, including
 /// a reference to its parent frame and `properties` for
 /// runtime evaluation, e.g. of Expressions.  `RuntimePropertiesStackFrame`s also track
-/// timeline playhead position.
+/// the timeline whose playhead keyframed properties are evaluated at.
 ///
 /// `Component`s push `RuntimePropertiesStackFrame`s before computing properties and pop them after computing, thus providing a
 /// hierarchical store of node-relevant data that can be bound to symbols in expressions.
@@ -179,6 +207,7 @@ impl RuntimeContext {
 pub struct RuntimePropertiesStackFrame {
     properties: Rc<RefCell<dyn Any>>,
     parent: Option<Rc<RuntimePropertiesStackFrame>>,
+    timeline: Option<Rc<RefCell<Timeline>>>,
 }
 
 impl RuntimePropertiesStackFrame {
@@ -186,13 +215,30 @@ impl RuntimePropertiesStackFrame {
         Rc::new(Self {
             properties,
             parent: None,
+            timeline: None,
         })
     }
 
+    /// Pushes a frame that shares the timeline of this one
     pub fn push(self: &Rc<Self>, properties: &Rc<RefCell<dyn Any>>) -> Rc<Self> {
         Rc::new(RuntimePropertiesStackFrame {
             parent: Some(Rc::clone(&self)),
             properties: Rc::clone(properties),
+            timeline: self.timeline.clone(),
+        })
+    }
+
+    /// Pushes the frame for a component's template, which evaluates keyframed properties
+    /// against the component's own `timeline`, if any
+    pub fn push_component(
+        self: &Rc<Self>,
+        properties: &Rc<RefCell<dyn Any>>,
+        timeline: Option<Rc<RefCell<Timeline>>>,
+    ) -> Rc<Self> {
+        Rc::new(RuntimePropertiesStackFrame {
+            parent: Some(Rc::clone(&self)),
+            properties: Rc::clone(properties),
+            timeline,
         })
     }
 
@@ -214,6 +260,10 @@ impl RuntimePropertiesStackFrame {
     pub fn get_properties(&self) -> Rc<RefCell<dyn Any>> {
         Rc::clone(&self.properties)
     }
+
+    pub fn get_timeline(&self) -> Option<Rc<RefCell<Timeline>>> {
+        self.timeline.clone()
+    }
 }
 
 pub fn get_numeric_from_wrapped_properties(wrapped: Rc<RefCell<dyn Any>>) -> Numeric {
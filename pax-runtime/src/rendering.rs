@@ -8,10 +8,11 @@ use std::rc::Rc;
 use crate::api::math::Transform2;
 use crate::api::{CommonProperties, RenderContext, Window};
 use crate::node_interface::NodeLocal;
+use kurbo::BezPath;
 use pax_manifest::UniqueTemplateNodeIdentifier;
 use piet::{Color, StrokeStyle};
 
-use crate::api::{Layer, Scroll, Size};
+use crate::api::{Layer, Scroll, Size, Timeline};
 
 use crate::{ExpandedNode, ExpressionTable, Globals, HandlerRegistry, RuntimeContext};
 
@@ -31,6 +32,10 @@ pub struct InstantiationArgs {
     ///and recurse into descendant property computation
     pub compute_properties_fn: Option<Box<dyn Fn(&ExpandedNode, &ExpressionTable, &Globals)>>,
 
+    ///used by Component instances declaring keyframes, as the initial state of each
+    ///expanded instance's timeline
+    pub timeline: Option<Timeline>,
+
     pub template_node_identifier: Option<UniqueTemplateNodeIdentifier>,
 }
 
@@ -152,6 +157,42 @@ pub trait InstanceNode {
         None
     }
 
+    /// The shape this node clips its contents to, in its own coordinate space (i.e. before its
+    /// transform), if any.  Hit-testing and native elements respect it; implementors still
+    /// apply the clip when rendering, e.g. in `handle_pre_render`.
+    #[allow(unused_variables)]
+    fn get_clip_path(&self, expanded_node: &ExpandedNode) -> Option<BezPath> {
+        None
+    }
+
+    /// Whether this node positions and sizes its children itself, through `layout_children`, as
+    /// layout containers like `Flex` do
+    fn lays_out_children(&self) -> bool {
+        false
+    }
+
+    /// For nodes that `lays_out_children`: the cell, in this node's coordinate space, that each
+    /// of `children` (flattened through `if` and `for`) fills, given this node's `bounds`.
+    /// Called during layout, once the children's common properties are up to date.
+    #[allow(unused_variables)]
+    fn layout_children(
+        &self,
+        expanded_node: &ExpandedNode,
+        bounds: (f64, f64),
+        children: &[Rc<ExpandedNode>],
+    ) -> Vec<kurbo::Rect> {
+        Vec::new()
+    }
+
+    /// The size of this node's content, for `Size::Auto` widths and heights, given the `bounds`
+    /// it's laid out in.  By default, the size the chassis last measured for a natively rendered
+    /// node, or that a component reported for itself, if any; `None` leaves `Auto` sizes to fill
+    /// `bounds`.
+    #[allow(unused_variables)]
+    fn measure(&self, expanded_node: &ExpandedNode, bounds: (f64, f64)) -> Option<(f64, f64)> {
+        expanded_node.get_native_measure()
+    }
+
     #[cfg(debug_assertions)]
     fn resolve_debug(
         &self,
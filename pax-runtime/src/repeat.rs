@@ -1,9 +1,11 @@
 use std::any::Any;
 use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
 use std::iter;
 use std::rc::Rc;
 
-use crate::api::Layer;
+use crate::api::reactivity::{self, Dependency, PropertyVersion};
+use crate::api::{Layer, Numeric, StringBox};
 use crate::declarative_macros::handle_vtable_update_optional;
 use crate::{
     BaseInstance, ExpandedNode, InstanceFlags, InstanceNode, InstantiationArgs, RuntimeContext,
@@ -13,7 +15,9 @@ use crate::{
 /// Repeat allows for nodes to be rendered dynamically per data specified in `source_expression`.
 /// That is: for a `source_expression` of length `n`, `Repeat` will render its
 /// template `n` times, each with an embedded component context (`RepeatItem`)
-/// with an index `i` and a pointer to that relevant datum `source_expression[i]`
+/// with an index `i` and a pointer to that relevant datum `source_expression[i]`.
+/// Without a `key`, the template is expanded anew whenever the source changes length; with one,
+/// the nodes expanded for each key are kept, and moved along with their element.
 pub struct RepeatInstance {
     pub base: BaseInstance,
 }
@@ -26,13 +30,133 @@ pub struct RepeatProperties {
         Option<Box<dyn crate::api::PropertyInstance<Vec<Rc<RefCell<dyn Any>>>>>>,
     pub source_expression_range:
         Option<Box<dyn crate::api::PropertyInstance<std::ops::Range<isize>>>>,
+    /// For keyed `for`s, the vtable id of the `key` expression, which evaluates to a
+    /// [`RepeatKey`] for each element
+    pub key_expression_vtable_id: Option<usize>,
     last_len: usize,
     last_bounds: (f64, f64),
+    /// For keyed `for`s, what the source was last reconciled from
+    source_dependencies: Option<Vec<Dependency>>,
+    /// For keyed `for`s, each element's key and nodes, in order
+    keyed_items: Vec<KeyedItem>,
 }
 
+impl RepeatProperties {
+    fn source(&self) -> Vec<Rc<RefCell<dyn Any>>> {
+        if let Some(ref source) = self.source_expression_range {
+            source
+                .get()
+                .clone()
+                .map(|v| Rc::new(RefCell::new(v)) as Rc<RefCell<dyn Any>>)
+                .collect()
+        } else if let Some(ref source) = self.source_expression_vec {
+            source.get().clone()
+        } else {
+            //A valid Repeat must have a repeat source; presumably this has been gated by the parser / compiler
+            unreachable!();
+        }
+    }
+}
+
+/// The stack frame the template of a `for` is expanded in, for one element of its source
 pub struct RepeatItem {
-    pub elem: Rc<RefCell<dyn Any>>,
-    pub i: usize,
+    elem: Rc<RefCell<dyn Any>>,
+    i: usize,
+    /// Bumped whenever a keyed `for` reconciles this item, since it may have moved or been given
+    /// a changed element, so that expressions reading either are re-evaluated
+    version: PropertyVersion,
+}
+
+impl RepeatItem {
+    pub fn new(elem: Rc<RefCell<dyn Any>>, i: usize) -> Self {
+        Self {
+            elem,
+            i,
+            version: PropertyVersion::new(),
+        }
+    }
+
+    /// This item's element
+    pub fn elem(&self) -> Rc<RefCell<dyn Any>> {
+        self.version.track_read();
+        Rc::clone(&self.elem)
+    }
+
+    /// The index of this item's element in the source
+    pub fn i(&self) -> usize {
+        self.version.track_read();
+        self.i
+    }
+
+    fn set(&mut self, elem: Rc<RefCell<dyn Any>>, i: usize) {
+        self.elem = elem;
+        self.i = i;
+        self.version.bump();
+    }
+}
+
+/// What identifies an element of a keyed `for`'s source, as its `key` expression evaluates to,
+/// e.g. an id or a name
+#[cfg_attr(debug_assertions, derive(Debug))]
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub enum RepeatKey {
+    Integer(isize),
+    /// The bits of a float key
+    Float(u64),
+    String(String),
+    Bool(bool),
+}
+
+impl From<Numeric> for RepeatKey {
+    fn from(value: Numeric) -> Self {
+        match value {
+            Numeric::Integer(i) => RepeatKey::Integer(i),
+            Numeric::Float(f) => RepeatKey::Float(f.to_bits()),
+        }
+    }
+}
+
+impl From<isize> for RepeatKey {
+    fn from(value: isize) -> Self {
+        RepeatKey::Integer(value)
+    }
+}
+
+impl From<usize> for RepeatKey {
+    fn from(value: usize) -> Self {
+        RepeatKey::Integer(value as isize)
+    }
+}
+
+impl From<String> for RepeatKey {
+    fn from(value: String) -> Self {
+        RepeatKey::String(value)
+    }
+}
+
+impl From<&str> for RepeatKey {
+    fn from(value: &str) -> Self {
+        RepeatKey::String(value.to_string())
+    }
+}
+
+impl From<StringBox> for RepeatKey {
+    fn from(value: StringBox) -> Self {
+        RepeatKey::String(value.string)
+    }
+}
+
+impl From<bool> for RepeatKey {
+    fn from(value: bool) -> Self {
+        RepeatKey::Bool(value)
+    }
+}
+
+/// An element of a keyed `for`'s source, with the nodes expanded for it
+struct KeyedItem {
+    key: RepeatKey,
+    repeat_item: Rc<RefCell<RepeatItem>>,
+    nodes: Vec<Rc<ExpandedNode>>,
 }
 
 impl InstanceNode for RepeatInstance {
@@ -67,7 +191,7 @@ impl InstanceNode for RepeatInstance {
     }
 
     fn update(self: Rc<Self>, expanded_node: &Rc<ExpandedNode>, context: &mut RuntimeContext) {
-        let new_vec =
+        let (new_vec, key_expression) =
             expanded_node.with_properties_unwrapped(|properties: &mut RepeatProperties| {
                 handle_vtable_update_optional(
                     context.expression_table(),
@@ -82,21 +206,21 @@ impl InstanceNode for RepeatInstance {
                     context.globals(),
                 );
 
-                let vec = if let Some(ref source) = properties.source_expression_range {
-                    Box::new(
-                        source
-                            .get()
-                            .clone()
-                            .map(|v| Rc::new(RefCell::new(v)) as Rc<RefCell<dyn Any>>),
-                    ) as Box<dyn ExactSizeIterator<Item = Rc<RefCell<dyn Any>>>>
-                } else if let Some(ref source) = properties.source_expression_vec {
-                    Box::new(source.get().clone().into_iter())
-                        as Box<dyn ExactSizeIterator<Item = Rc<RefCell<dyn Any>>>>
-                } else {
-                    //A valid Repeat must have a repeat source; presumably this has been gated by the parser / compiler
-                    unreachable!();
-                };
+                if let Some(key_expression) = properties.key_expression_vtable_id {
+                    // Keyed `for`s reconcile whenever their source has changed at all.  Unlike
+                    // below, a change of bounds doesn't call for new nodes: kept nodes are laid
+                    // out again whenever their container changes (see `ExpandedNode::update_layout`)
+                    let (vec, dependencies) =
+                        reactivity::track_dependencies(|| properties.source());
+                    let changed = properties
+                        .source_dependencies
+                        .as_ref()
+                        .map_or(true, |dependencies| reactivity::any_changed(dependencies));
+                    properties.source_dependencies = Some(dependencies);
+                    return (changed.then_some(vec), Some(key_expression));
+                }
 
+                let vec = properties.source();
                 let current_len = vec.len();
 
                 let exp_props = expanded_node.layout_properties.borrow();
@@ -109,27 +233,31 @@ impl InstanceNode for RepeatInstance {
 
                 properties.last_len = current_len;
                 properties.last_bounds = current_bounds;
-                update_children.then_some(vec)
+                (update_children.then_some(vec), None)
             });
 
-        if let Some(vec) = new_vec {
-            let template_children = self.base().get_instance_children();
-            let children_with_envs = iter::repeat(template_children)
-                .zip(vec.into_iter())
-                .enumerate()
-                .flat_map(|(i, (children, elem))| {
-                    let new_repeat_item = Rc::new(RefCell::new(RepeatItem {
-                        i,
-                        elem: Rc::clone(&elem),
-                    })) as Rc<RefCell<dyn Any>>;
-                    let new_env = expanded_node.stack.push(&new_repeat_item);
-                    children
-                        .borrow()
-                        .clone()
-                        .into_iter()
-                        .zip(iter::repeat(new_env))
-                });
-            expanded_node.set_children(children_with_envs, context);
+        match (new_vec, key_expression) {
+            (Some(vec), Some(key_expression)) => {
+                self.reconcile(expanded_node, vec, key_expression, context)
+            }
+            (Some(vec), None) => {
+                let template_children = self.base().get_instance_children();
+                let children_with_envs = iter::repeat(template_children)
+                    .zip(vec.into_iter())
+                    .enumerate()
+                    .flat_map(|(i, (children, elem))| {
+                        let new_repeat_item =
+                            Rc::new(RefCell::new(RepeatItem::new(elem, i))) as Rc<RefCell<dyn Any>>;
+                        let new_env = expanded_node.stack.push(&new_repeat_item);
+                        children
+                            .borrow()
+                            .clone()
+                            .into_iter()
+                            .zip(iter::repeat(new_env))
+                    });
+                expanded_node.set_children(children_with_envs, context);
+            }
+            (None, _) => {}
         }
     }
 
@@ -138,3 +266,69 @@ impl InstanceNode for RepeatInstance {
         // condition has then been evaluated
     }
 }
+
+impl RepeatInstance {
+    /// Brings the children of a keyed `for` in line with `vec`: elements whose key was already
+    /// present keep their nodes, which are moved into place and given the element and its new
+    /// index; other elements have the template expanded for them, and the nodes of keys no
+    /// longer present are removed.  Elements sharing a key keep the nodes of that key's
+    /// elements in order, and get nodes of their own once those run out.
+    fn reconcile(
+        &self,
+        expanded_node: &Rc<ExpandedNode>,
+        vec: Vec<Rc<RefCell<dyn Any>>>,
+        key_expression: usize,
+        context: &mut RuntimeContext,
+    ) {
+        let mut previous: HashMap<RepeatKey, VecDeque<KeyedItem>> = HashMap::new();
+        expanded_node.with_properties_unwrapped(|properties: &mut RepeatProperties| {
+            for item in properties.keyed_items.drain(..) {
+                previous
+                    .entry(item.key.clone())
+                    .or_default()
+                    .push_back(item);
+            }
+        });
+        let template_children = self.base().get_instance_children();
+        let mut items = Vec::with_capacity(vec.len());
+        for (i, elem) in vec.into_iter().enumerate() {
+            let repeat_item = Rc::new(RefCell::new(RepeatItem::new(Rc::clone(&elem), i)));
+            let env = expanded_node
+                .stack
+                .push(&(Rc::clone(&repeat_item) as Rc<RefCell<dyn Any>>));
+            let key = *context
+                .expression_table()
+                .compute_vtable_value(&env, key_expression)
+                .downcast::<RepeatKey>()
+                .expect("`for` key should evaluate to a RepeatKey");
+            let item = match previous.get_mut(&key).and_then(VecDeque::pop_front) {
+                Some(item) => {
+                    item.repeat_item.borrow_mut().set(elem, i);
+                    item
+                }
+                None => {
+                    let templates = template_children.borrow().clone();
+                    let nodes = expanded_node.create_children_detached(
+                        templates.into_iter().zip(iter::repeat(env)),
+                        context,
+                    );
+                    KeyedItem {
+                        key,
+                        repeat_item,
+                        nodes,
+                    }
+                }
+            };
+            items.push(item);
+        }
+
+        let children = items
+            .iter()
+            .flat_map(|item| item.nodes.iter().cloned())
+            .collect();
+        expanded_node.attach_children(children, context);
+        expanded_node.with_properties_unwrapped(|properties: &mut RepeatProperties| {
+            properties.keyed_items = items;
+        });
+    }
+}
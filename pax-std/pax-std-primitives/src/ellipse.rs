@@ -3,6 +3,8 @@ use pax_runtime::api::{Layer, RenderContext};
 use pax_runtime::{declarative_macros::handle_vtable_update, BaseInstance};
 use pax_std::{primitives::Ellipse, types::Fill};
 
+use crate::shadow::draw_shadows;
+
 use pax_runtime::{ExpandedNode, InstanceFlags, InstanceNode, InstantiationArgs, RuntimeContext};
 
 use std::rc::Rc;
@@ -47,8 +49,13 @@ impl InstanceNode for EllipseInstance {
             let ellipse = kurbo::Ellipse::from_rect(rect);
             let accuracy = 0.1;
             let bez_path = ellipse.to_path(accuracy);
+            let transform = Into::<kurbo::Affine>::into(tab.transform);
+            let layer_id = format!("{}", expanded_node.occlusion_id.borrow());
+            let shadows = properties.shadow.get();
+            draw_shadows(rc, &layer_id, &bez_path, transform, tab.bounds, shadows);
+            let blur = properties.blur.get().to_float();
 
-            let transformed_bez_path = Into::<kurbo::Affine>::into(tab.transform) * bez_path;
+            let transformed_bez_path = transform * bez_path;
             let duplicate_transformed_bez_path = transformed_bez_path.clone();
 
             let color = if let Fill::Solid(properties_color) = properties.fill.get() {
@@ -57,18 +64,18 @@ impl InstanceNode for EllipseInstance {
                 unimplemented!("gradients not supported on ellipse")
             };
 
-            let layer_id = format!("{}", expanded_node.occlusion_id.borrow());
-            rc.fill(&layer_id, transformed_bez_path, &color.into());
+            rc.fill_blurred(&layer_id, transformed_bez_path, &color.into(), blur);
 
             //hack to address "phantom stroke" bug on Web
             let width: f64 = *&properties.stroke.get().width.get().expect_pixels().into();
 
             if width > f64::EPSILON {
-                rc.stroke(
+                rc.stroke_blurred(
                     &layer_id,
                     duplicate_transformed_bez_path,
                     &properties.stroke.get().color.get().to_piet_color().into(),
                     width,
+                    blur,
                 );
             }
         });
@@ -106,6 +113,18 @@ impl InstanceNode for EllipseInstance {
                 &mut properties.fill,
                 context.globals(),
             );
+            handle_vtable_update(
+                context.expression_table(),
+                &expanded_node.stack,
+                &mut properties.shadow,
+                context.globals(),
+            );
+            handle_vtable_update(
+                context.expression_table(),
+                &expanded_node.stack,
+                &mut properties.blur,
+                context.globals(),
+            );
         });
     }
 }
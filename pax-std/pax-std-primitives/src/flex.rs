@@ -0,0 +1,313 @@
+use std::rc::Rc;
+
+use kurbo::Rect;
+use pax_runtime::api::{Axis, Layer};
+use pax_runtime::declarative_macros::handle_vtable_update;
+use pax_runtime::{
+    BaseInstance, ExpandedNode, InstanceFlags, InstanceNode, InstantiationArgs, RuntimeContext,
+};
+use pax_std::primitives::Flex;
+use pax_std::types::{AlignItems, FlexDirection, JustifyContent};
+
+/// Lays out its children flexbox-style as part of the engine's layout pass, assigning each
+/// the cell it fills.  Like [`Group`](crate::group::GroupInstance), it draws nothing itself.
+pub struct FlexInstance {
+    base: BaseInstance,
+}
+
+impl InstanceNode for FlexInstance {
+    fn instantiate(args: InstantiationArgs) -> Rc<Self>
+    where
+        Self: Sized,
+    {
+        Rc::new(Self {
+            base: BaseInstance::new(
+                args,
+                InstanceFlags {
+                    invisible_to_slot: false,
+                    invisible_to_raycasting: true,
+                    layer: Layer::DontCare,
+                    is_component: false,
+                },
+            ),
+        })
+    }
+
+    fn update(self: Rc<Self>, expanded_node: &Rc<ExpandedNode>, context: &mut RuntimeContext) {
+        expanded_node.with_properties_unwrapped(|properties: &mut Flex| {
+            let tbl = context.expression_table();
+            let stk = &expanded_node.stack;
+            handle_vtable_update(tbl, stk, &mut properties.direction, context.globals());
+            handle_vtable_update(tbl, stk, &mut properties.wrap, context.globals());
+            handle_vtable_update(tbl, stk, &mut properties.justify_content, context.globals());
+            handle_vtable_update(tbl, stk, &mut properties.align_items, context.globals());
+            handle_vtable_update(tbl, stk, &mut properties.gap, context.globals());
+            handle_vtable_update(tbl, stk, &mut properties.line_gap, context.globals());
+            handle_vtable_update(tbl, stk, &mut properties.items, context.globals());
+        });
+    }
+
+    fn lays_out_children(&self) -> bool {
+        true
+    }
+
+    fn layout_children(
+        &self,
+        expanded_node: &ExpandedNode,
+        bounds: (f64, f64),
+        children: &[Rc<ExpandedNode>],
+    ) -> Vec<Rect> {
+        expanded_node.with_properties_unwrapped(|properties: &mut Flex| {
+            let axes = Axes::of(properties);
+            let container = FlexContainer {
+                main: axes.along(bounds),
+                cross: axes.across(bounds),
+                wrap: *properties.wrap.get(),
+                justify_content: properties.justify_content.get().clone(),
+                align_items: properties.align_items.get().clone(),
+                gap: properties.gap.get().evaluate(bounds, axes.main_axis()),
+                line_gap: properties
+                    .line_gap
+                    .get()
+                    .evaluate(bounds, axes.cross_axis()),
+            };
+            container
+                .layout(&items(properties, axes, bounds, children))
+                .into_iter()
+                .map(|cell| {
+                    if axes.row {
+                        cell
+                    } else {
+                        Rect::new(cell.y0, cell.x0, cell.y1, cell.x1)
+                    }
+                })
+                .collect()
+        })
+    }
+
+    /// The size of the items on a single line at their basis, as thick as the thickest
+    fn measure(&self, expanded_node: &ExpandedNode, bounds: (f64, f64)) -> Option<(f64, f64)> {
+        expanded_node.with_properties_unwrapped(|properties: &mut Flex| {
+            let axes = Axes::of(properties);
+            let children = expanded_node.get_flattened_children();
+            let items = items(properties, axes, bounds, &children);
+            let gap = properties.gap.get().evaluate(bounds, axes.main_axis());
+            let main = items.iter().map(|item| item.basis).sum::<f64>()
+                + gap * items.len().saturating_sub(1) as f64;
+            let cross = items.iter().map(|item| item.cross).fold(0.0, f64::max);
+            Some(if axes.row {
+                (main, cross)
+            } else {
+                (cross, main)
+            })
+        })
+    }
+
+    #[cfg(debug_assertions)]
+    fn resolve_debug(
+        &self,
+        f: &mut std::fmt::Formatter,
+        expanded_node: Option<&ExpandedNode>,
+    ) -> std::fmt::Result {
+        match expanded_node {
+            Some(expanded_node) => expanded_node
+                .with_properties_unwrapped(|_f: &mut Flex| f.debug_struct("Flex").finish()),
+            None => f.debug_struct("Flex").finish_non_exhaustive(),
+        }
+    }
+
+    fn base(&self) -> &BaseInstance {
+        &self.base
+    }
+}
+
+/// Whether a `Flex`'s main axis is horizontal, for resolving sizes along and across it
+#[derive(Clone, Copy)]
+struct Axes {
+    row: bool,
+}
+
+impl Axes {
+    fn of(properties: &Flex) -> Self {
+        Self {
+            row: matches!(properties.direction.get(), FlexDirection::Row),
+        }
+    }
+
+    fn main_axis(self) -> Axis {
+        if self.row {
+            Axis::X
+        } else {
+            Axis::Y
+        }
+    }
+
+    fn cross_axis(self) -> Axis {
+        if self.row {
+            Axis::Y
+        } else {
+            Axis::X
+        }
+    }
+
+    fn along(self, size: (f64, f64)) -> f64 {
+        if self.row {
+            size.0
+        } else {
+            size.1
+        }
+    }
+
+    fn across(self, size: (f64, f64)) -> f64 {
+        if self.row {
+            size.1
+        } else {
+            size.0
+        }
+    }
+}
+
+/// `children` as the items of a `Flex` with `properties`, laid out against `bounds`
+fn items(
+    properties: &Flex,
+    axes: Axes,
+    bounds: (f64, f64),
+    children: &[Rc<ExpandedNode>],
+) -> Vec<Item> {
+    children
+        .iter()
+        .enumerate()
+        .map(|(i, child)| {
+            let spec = properties.items.get().get(i).cloned().unwrap_or_default();
+            let own_size = child.get_size_computed(bounds);
+            let (min, max) = child.get_size_limits(bounds);
+            let (min, max) = (axes.along(min), axes.along(max));
+            Item {
+                basis: spec.basis.map_or(axes.along(own_size), |basis| {
+                    basis.evaluate(bounds, axes.main_axis()).min(max).max(min)
+                }),
+                grow: spec.grow.to_float(),
+                shrink: spec.shrink.to_float(),
+                cross: axes.across(own_size),
+                min,
+                max,
+            }
+        })
+        .collect()
+}
+
+/// An item's sizing along (`basis`, `grow`, `shrink`, limited to `min`..`max`) and across
+/// (`cross`) the main axis
+struct Item {
+    basis: f64,
+    grow: f64,
+    shrink: f64,
+    cross: f64,
+    min: f64,
+    max: f64,
+}
+
+/// A `Flex`'s settings, resolved to pixels along (`main`, `gap`) and across (`cross`,
+/// `line_gap`) its main axis
+struct FlexContainer {
+    main: f64,
+    cross: f64,
+    wrap: bool,
+    justify_content: JustifyContent,
+    align_items: AlignItems,
+    gap: f64,
+    line_gap: f64,
+}
+
+impl FlexContainer {
+    /// The cells of `items`, as if the main axis were horizontal
+    fn layout(&self, items: &[Item]) -> Vec<Rect> {
+        let mut cells = Vec::with_capacity(items.len());
+        let mut line_start = 0.0;
+        for line in self.lines(items) {
+            // A single line spans the container; wrapped lines are as thick as their thickest item
+            let thickness = if self.wrap {
+                line.iter().map(|item| item.cross).fold(0.0, f64::max)
+            } else {
+                self.cross
+            };
+            let sizes = self.resolve_sizes(line);
+            let free = self.main - sizes.iter().sum::<f64>() - self.gap * (line.len() - 1) as f64;
+            let (mut position, spacing) = self.justify(free, line.len());
+            for (item, size) in line.iter().zip(sizes) {
+                let (offset, cross) = match self.align_items {
+                    AlignItems::Stretch => (0.0, thickness),
+                    AlignItems::Start => (0.0, item.cross),
+                    AlignItems::End => (thickness - item.cross, item.cross),
+                    AlignItems::Center => ((thickness - item.cross) / 2.0, item.cross),
+                };
+                let top = line_start + offset;
+                cells.push(Rect::new(position, top, position + size, top + cross));
+                position += size + self.gap + spacing;
+            }
+            line_start += thickness + self.line_gap;
+        }
+        cells
+    }
+
+    /// `items` broken into lines: all of them if not wrapping, otherwise as many as fit on each
+    fn lines<'a>(&self, items: &'a [Item]) -> Vec<&'a [Item]> {
+        if !self.wrap {
+            return items.chunks(items.len().max(1)).collect();
+        }
+        let mut lines = Vec::new();
+        let (mut start, mut used) = (0, 0.0);
+        for (i, item) in items.iter().enumerate() {
+            if i > start && used + self.gap + item.basis > self.main {
+                lines.push(&items[start..i]);
+                (start, used) = (i, 0.0);
+            }
+            if i > start {
+                used += self.gap;
+            }
+            used += item.basis;
+        }
+        if start < items.len() {
+            lines.push(&items[start..]);
+        }
+        lines
+    }
+
+    /// The size along the line of each of `line`'s items, grown into free space or shrunk to
+    /// fit, but no further than the item's limits
+    fn resolve_sizes(&self, line: &[Item]) -> Vec<f64> {
+        let bases = line.iter().map(|item| item.basis);
+        let free = self.main - bases.sum::<f64>() - self.gap * (line.len() - 1) as f64;
+        let total_grow: f64 = line.iter().map(|item| item.grow).sum();
+        let total_shrink: f64 = line.iter().map(|item| item.shrink * item.basis).sum();
+        line.iter()
+            .map(|item| {
+                let size = if free > 0.0 && total_grow > 0.0 {
+                    item.basis + free * item.grow / total_grow
+                } else if free < 0.0 && total_shrink > 0.0 {
+                    (item.basis + free * item.shrink * item.basis / total_shrink).max(0.0)
+                } else {
+                    item.basis
+                };
+                size.min(item.max).max(item.min)
+            })
+            .collect()
+    }
+
+    /// Where along the line the first item starts, and the space added between neighbouring
+    /// items on top of `gap`, to distribute `free` space between `count` items
+    fn justify(&self, free: f64, count: usize) -> (f64, f64) {
+        let count = count as f64;
+        match self.justify_content {
+            JustifyContent::Start => (0.0, 0.0),
+            JustifyContent::End => (free, 0.0),
+            JustifyContent::Center => (free / 2.0, 0.0),
+            // Overflowing lines can't be spaced out, and start at the start
+            _ if free <= 0.0 => (0.0, 0.0),
+            JustifyContent::SpaceBetween if count > 1.0 => (0.0, free / (count - 1.0)),
+            JustifyContent::SpaceBetween => (0.0, 0.0),
+            JustifyContent::SpaceAround => (free / count / 2.0, free / count),
+            JustifyContent::SpaceEvenly => (free / (count + 1.0), free / (count + 1.0)),
+        }
+    }
+}
@@ -3,16 +3,24 @@ use core::option::Option::Some;
 
 use std::rc::Rc;
 
-use kurbo::{Affine, BezPath};
+use kurbo::{BezPath, RoundedRect, Shape};
 use pax_runtime::api::{Layer, RenderContext, Size};
+use pax_runtime::declarative_macros::handle_vtable_update;
 use pax_runtime::{
     BaseInstance, ExpandedNode, InstanceFlags, InstanceNode, InstantiationArgs, RuntimeContext,
 };
+use pax_std::primitives::Frame;
+use pax_std::types::RectangleCornerRadii;
+
+use crate::path::build_path;
 
 /// A primitive that gathers children underneath a single render node with a shared base transform,
 /// like [`Group`], except [`Frame`] has the option of clipping rendering outside
 /// of its bounds.
 ///
+/// The clip is the frame's bounds with its `corner_radii` rounded off, or, if it is given
+/// one, its `clip_path` (e.g. to clip to an ellipse).
+///
 /// If clipping or the option of clipping is not required,
 /// a [`Group`] will generally be a more performant and otherwise-equivalent
 /// to [`Frame`], since `[Frame]` creates a clipping mask.
@@ -38,10 +46,43 @@ impl InstanceNode for FrameInstance {
         })
     }
 
+    fn update(self: Rc<Self>, expanded_node: &Rc<ExpandedNode>, context: &mut RuntimeContext) {
+        expanded_node.with_properties_unwrapped(|properties: &mut Frame| {
+            let tbl = context.expression_table();
+            let stk = &expanded_node.stack;
+            handle_vtable_update(tbl, stk, &mut properties.corner_radii, context.globals());
+            // `get_mut` marks the radii changed for everything reading them (e.g. the clip), so
+            // it's only taken when one of them can actually change this tick
+            if corner_radii_need_update(properties.corner_radii.get()) {
+                let radii = properties.corner_radii.get_mut();
+                handle_vtable_update(tbl, stk, &mut radii.top_left, context.globals());
+                handle_vtable_update(tbl, stk, &mut radii.top_right, context.globals());
+                handle_vtable_update(tbl, stk, &mut radii.bottom_right, context.globals());
+                handle_vtable_update(tbl, stk, &mut radii.bottom_left, context.globals());
+            }
+            handle_vtable_update(tbl, stk, &mut properties.clip_path, context.globals());
+        });
+    }
+
     fn get_clipping_size(&self, expanded_node: &ExpandedNode) -> Option<(Size, Size)> {
         Some(self.get_size(expanded_node))
     }
 
+    fn get_clip_path(&self, expanded_node: &ExpandedNode) -> Option<BezPath> {
+        let layout_properties = expanded_node.layout_properties.borrow();
+        let (width, height) = layout_properties.as_ref()?.computed_tab.bounds;
+        expanded_node.with_properties_unwrapped(|properties: &mut Frame| {
+            let clip_path = properties.clip_path.get();
+            let custom = (!clip_path.is_empty())
+                .then(|| build_path(clip_path, (width, height)))
+                .flatten();
+            Some(custom.unwrap_or_else(|| {
+                RoundedRect::new(0.0, 0.0, width, height, properties.corner_radii.get())
+                    .to_path(0.1)
+            }))
+        })
+    }
+
     // fn handle_native_patches(
     //     &mut self,
     //     rtc: &mut RenderTreeContext<R>,
@@ -111,22 +152,7 @@ impl InstanceNode for FrameInstance {
         _context: &mut RuntimeContext,
         rcs: &mut dyn RenderContext,
     ) {
-        let comp_props = &expanded_node.layout_properties.borrow();
-        let comp_props = comp_props.as_ref().unwrap();
-        let transform = comp_props.computed_tab.transform;
-        let bounding_dimens = comp_props.computed_tab.bounds;
-        let width = bounding_dimens.0;
-        let height = bounding_dimens.1;
-
-        let mut bez_path = BezPath::new();
-        bez_path.move_to((0.0, 0.0));
-        bez_path.line_to((width, 0.0));
-        bez_path.line_to((width, height));
-        bez_path.line_to((0.0, height));
-        bez_path.line_to((0.0, 0.0));
-        bez_path.close_path();
-
-        let transformed_bez_path = <Affine>::from(transform) * bez_path;
+        let transformed_bez_path = expanded_node.get_clip_path().unwrap_or_default();
 
         let layers = rcs.layers();
         let layers: Vec<String> = layers.iter().map(|s| s.to_string()).collect();
@@ -178,8 +204,6 @@ impl InstanceNode for FrameInstance {
         f: &mut std::fmt::Formatter,
         expanded_node: Option<&ExpandedNode>,
     ) -> std::fmt::Result {
-        use pax_std::primitives::Frame;
-
         match expanded_node {
             Some(expanded_node) => expanded_node
                 .with_properties_unwrapped(|_f: &mut Frame| f.debug_struct("Frame").finish()),
@@ -191,3 +215,19 @@ impl InstanceNode for FrameInstance {
         &self.base
     }
 }
+
+/// Whether any of `radii` is an expression with changed inputs, is animating or is keyframed
+fn corner_radii_need_update(radii: &RectangleCornerRadii) -> bool {
+    [
+        &radii.top_left,
+        &radii.top_right,
+        &radii.bottom_right,
+        &radii.bottom_left,
+    ]
+    .into_iter()
+    .any(|radius| {
+        (radius._get_vtable_id().is_some() && radius._is_dirty())
+            || radius.is_animating()
+            || radius._get_timeline().is_some()
+    })
+}
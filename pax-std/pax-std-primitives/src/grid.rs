@@ -0,0 +1,297 @@
+use std::rc::Rc;
+
+use kurbo::Rect;
+use pax_runtime::api::{Axis, Layer, Numeric};
+use pax_runtime::declarative_macros::handle_vtable_update;
+use pax_runtime::{
+    BaseInstance, ExpandedNode, InstanceFlags, InstanceNode, InstantiationArgs, RuntimeContext,
+};
+use pax_std::primitives::Grid;
+use pax_std::types::{GridPlacement, GridTrack};
+
+/// Lays out its children in rows and columns as part of the engine's layout pass, assigning
+/// each the cells it spans.  Like [`Flex`](crate::flex::FlexInstance), it draws nothing itself.
+pub struct GridInstance {
+    base: BaseInstance,
+}
+
+impl InstanceNode for GridInstance {
+    fn instantiate(args: InstantiationArgs) -> Rc<Self>
+    where
+        Self: Sized,
+    {
+        Rc::new(Self {
+            base: BaseInstance::new(
+                args,
+                InstanceFlags {
+                    invisible_to_slot: false,
+                    invisible_to_raycasting: true,
+                    layer: Layer::DontCare,
+                    is_component: false,
+                },
+            ),
+        })
+    }
+
+    fn update(self: Rc<Self>, expanded_node: &Rc<ExpandedNode>, context: &mut RuntimeContext) {
+        expanded_node.with_properties_unwrapped(|properties: &mut Grid| {
+            let tbl = context.expression_table();
+            let stk = &expanded_node.stack;
+            handle_vtable_update(tbl, stk, &mut properties.columns, context.globals());
+            handle_vtable_update(tbl, stk, &mut properties.rows, context.globals());
+            handle_vtable_update(tbl, stk, &mut properties.column_gap, context.globals());
+            handle_vtable_update(tbl, stk, &mut properties.row_gap, context.globals());
+            handle_vtable_update(tbl, stk, &mut properties.placements, context.globals());
+        });
+    }
+
+    fn lays_out_children(&self) -> bool {
+        true
+    }
+
+    fn layout_children(
+        &self,
+        expanded_node: &ExpandedNode,
+        bounds: (f64, f64),
+        children: &[Rc<ExpandedNode>],
+    ) -> Vec<Rect> {
+        expanded_node.with_properties_unwrapped(|properties: &mut Grid| {
+            let columns = properties.columns.get();
+            let placements: Vec<_> = (0..children.len())
+                .map(|i| {
+                    properties
+                        .placements
+                        .get()
+                        .get(i)
+                        .cloned()
+                        .unwrap_or_default()
+                })
+                .collect();
+            let areas = place(&placements, columns.len().max(1));
+            let row_count = areas.iter().map(|area| area.row + area.row_span).max();
+            let column_count = areas
+                .iter()
+                .map(|area| area.column + area.column_span)
+                .max();
+            let columns = Tracks::resolve(
+                columns,
+                column_count.unwrap_or(0),
+                properties.column_gap.get().evaluate(bounds, Axis::X),
+                bounds,
+                Axis::X,
+            );
+            let rows = Tracks::resolve(
+                properties.rows.get(),
+                row_count.unwrap_or(0),
+                properties.row_gap.get().evaluate(bounds, Axis::Y),
+                bounds,
+                Axis::Y,
+            );
+            areas
+                .iter()
+                .map(|area| {
+                    let (x0, x1) = columns.span(area.column, area.column_span);
+                    let (y0, y1) = rows.span(area.row, area.row_span);
+                    Rect::new(x0, y0, x1, y1)
+                })
+                .collect()
+        })
+    }
+
+    #[cfg(debug_assertions)]
+    fn resolve_debug(
+        &self,
+        f: &mut std::fmt::Formatter,
+        expanded_node: Option<&ExpandedNode>,
+    ) -> std::fmt::Result {
+        match expanded_node {
+            Some(expanded_node) => expanded_node
+                .with_properties_unwrapped(|_g: &mut Grid| f.debug_struct("Grid").finish()),
+            None => f.debug_struct("Grid").finish_non_exhaustive(),
+        }
+    }
+
+    fn base(&self) -> &BaseInstance {
+        &self.base
+    }
+}
+
+/// The cells a child covers, as zero-based indices of its first row and column
+#[derive(Clone, Copy)]
+struct Area {
+    row: usize,
+    column: usize,
+    row_span: usize,
+    column_span: usize,
+}
+
+/// Resolves `placements` to areas in a grid `width` columns wide.  Ones that give both a row
+/// and a column are placed as given, even if they overlap others.  Ones that give only a row
+/// (or column) take the first free cells in it; ones that give neither take the first free
+/// cells, row by row, after the previous such area.
+fn place(placements: &[GridPlacement], width: usize) -> Vec<Area> {
+    let index = |value: &Option<Numeric>| {
+        value
+            .as_ref()
+            .map(|value| value.to_float().max(0.0) as usize)
+    };
+    let span = |value: &Numeric| (value.to_float() as usize).max(1);
+    let mut occupied = Occupancy::default();
+
+    // Explicitly placed areas are claimed first, so the others flow around them
+    let mut areas: Vec<Option<Area>> = placements
+        .iter()
+        .map(|placement| {
+            let area = Area {
+                row: index(&placement.row)?,
+                column: index(&placement.column)?,
+                row_span: span(&placement.row_span),
+                column_span: span(&placement.column_span),
+            };
+            occupied.claim(&area);
+            Some(area)
+        })
+        .collect();
+
+    let mut cursor = (0, 0);
+    for (placement, area) in placements.iter().zip(areas.iter_mut()) {
+        if area.is_some() {
+            continue;
+        }
+        let mut candidate = Area {
+            row: 0,
+            column: 0,
+            row_span: span(&placement.row_span),
+            column_span: span(&placement.column_span).min(width),
+        };
+        match (index(&placement.row), index(&placement.column)) {
+            (Some(row), _) => {
+                candidate.row = row;
+                while !occupied.is_free(&candidate) {
+                    candidate.column += 1;
+                }
+            }
+            (None, Some(column)) => {
+                candidate.column = column;
+                while !occupied.is_free(&candidate) {
+                    candidate.row += 1;
+                }
+            }
+            (None, None) => {
+                (candidate.row, candidate.column) = cursor;
+                while candidate.column + candidate.column_span > width
+                    || !occupied.is_free(&candidate)
+                {
+                    if candidate.column + candidate.column_span >= width {
+                        (candidate.row, candidate.column) = (candidate.row + 1, 0);
+                    } else {
+                        candidate.column += 1;
+                    }
+                }
+                cursor = (candidate.row, candidate.column + candidate.column_span);
+            }
+        }
+        occupied.claim(&candidate);
+        *area = Some(candidate);
+    }
+    areas.into_iter().flatten().collect()
+}
+
+/// Which cells of a grid are covered, by row then column
+#[derive(Default)]
+struct Occupancy(Vec<Vec<bool>>);
+
+impl Occupancy {
+    fn claim(&mut self, area: &Area) {
+        let end = area.column + area.column_span;
+        for row in area.row..area.row + area.row_span {
+            if self.0.len() <= row {
+                self.0.resize(row + 1, Vec::new());
+            }
+            let cells = &mut self.0[row];
+            if cells.len() < end {
+                cells.resize(end, false);
+            }
+            cells[area.column..end].fill(true);
+        }
+    }
+
+    fn is_free(&self, area: &Area) -> bool {
+        (area.row..area.row + area.row_span).all(|row| {
+            (area.column..area.column + area.column_span).all(|column| {
+                !self
+                    .0
+                    .get(row)
+                    .and_then(|cells| cells.get(column))
+                    .copied()
+                    .unwrap_or(false)
+            })
+        })
+    }
+}
+
+/// The resolved positions of a grid's columns or rows
+struct Tracks {
+    /// The start and end of each track
+    extents: Vec<(f64, f64)>,
+}
+
+impl Tracks {
+    /// Sizes `count` tracks (at least as many as `tracks`) to fill `bounds` along `axis`, with
+    /// `gap` between neighbours.  Fixed tracks take their size; fractional ones share what's left.
+    fn resolve(
+        tracks: &[GridTrack],
+        count: usize,
+        gap: f64,
+        bounds: (f64, f64),
+        axis: Axis,
+    ) -> Self {
+        let count = count.max(tracks.len());
+        let last = tracks.last().cloned().unwrap_or_default();
+        let tracks: Vec<_> = (0..count)
+            .map(|i| tracks.get(i).cloned().unwrap_or_else(|| last.clone()))
+            .collect();
+        let extent = match axis {
+            Axis::X => bounds.0,
+            Axis::Y => bounds.1,
+        };
+        let fixed: f64 = tracks
+            .iter()
+            .map(|track| match track {
+                GridTrack::Fixed(size) => size.evaluate(bounds, axis),
+                GridTrack::Fraction(_) => 0.0,
+            })
+            .sum();
+        let weights: f64 = tracks
+            .iter()
+            .map(|track| match track {
+                GridTrack::Fraction(weight) => weight.to_float().max(0.0),
+                GridTrack::Fixed(_) => 0.0,
+            })
+            .sum();
+        let gaps = gap * count.saturating_sub(1) as f64;
+        let free = (extent - fixed - gaps).max(0.0);
+        let mut start = 0.0;
+        let extents = tracks
+            .iter()
+            .map(|track| {
+                let size = match track {
+                    GridTrack::Fixed(size) => size.evaluate(bounds, axis),
+                    GridTrack::Fraction(weight) if weights > 0.0 => {
+                        free * weight.to_float().max(0.0) / weights
+                    }
+                    GridTrack::Fraction(_) => 0.0,
+                };
+                let extent = (start, start + size);
+                start += size + gap;
+                extent
+            })
+            .collect();
+        Self { extents }
+    }
+
+    /// Where `span` tracks from `first` start and end, including the gaps between them
+    fn span(&self, first: usize, span: usize) -> (f64, f64) {
+        (self.extents[first].0, self.extents[first + span - 1].1)
+    }
+}
@@ -1,4 +1,4 @@
-use pax_runtime::{BaseInstance, InstanceFlags, InstanceNode, InstantiationArgs};
+use pax_runtime::{BaseInstance, ExpandedNode, InstanceFlags, InstanceNode, InstantiationArgs};
 use std::rc::Rc;
 
 use pax_runtime::api::Layer;
@@ -27,6 +27,10 @@ impl InstanceNode for GroupInstance {
         })
     }
 
+    fn measure(&self, expanded_node: &ExpandedNode, bounds: (f64, f64)) -> Option<(f64, f64)> {
+        Some(expanded_node.measure_children(bounds))
+    }
+
     #[cfg(debug_assertions)]
     fn resolve_debug(
         &self,
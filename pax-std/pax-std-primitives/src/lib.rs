@@ -1,9 +1,12 @@
 pub mod ellipse;
+pub mod flex;
 pub mod frame;
+pub mod grid;
 pub mod group;
 pub mod image;
 pub mod path;
 pub mod rectangle;
+mod shadow;
 // pub mod scroller;
 pub mod button;
 pub mod checkbox;
@@ -8,6 +8,8 @@ use pax_runtime::{
 use pax_std::primitives::Path;
 use pax_std::types::{PathElement, Point};
 
+use crate::shadow::draw_shadows;
+
 use std::rc::Rc;
 
 /// A basic 2D vector path for arbitrary Bézier / line-segment chains
@@ -52,6 +54,8 @@ impl InstanceNode for PathInstance {
             );
             handle_vtable_update(tbl, stk, &mut properties.fill, context.globals());
             handle_vtable_update(tbl, stk, &mut properties.elements, context.globals());
+            handle_vtable_update(tbl, stk, &mut properties.shadow, context.globals());
+            handle_vtable_update(tbl, stk, &mut properties.blur, context.globals());
         });
     }
 
@@ -64,59 +68,25 @@ impl InstanceNode for PathInstance {
         let layer_id = format!("{}", expanded_node.occlusion_id.borrow());
 
         expanded_node.with_properties_unwrapped(|properties: &mut Path| {
-            let mut bez_path = BezPath::new();
-
             let layout_props = expanded_node.layout_properties.borrow();
             let bounds = layout_props.as_ref().unwrap().computed_tab.bounds;
-
-            let mut itr_elems = properties.elements.get().iter();
-
-            if let Some(elem) = itr_elems.next() {
-                if let &PathElement::Point(x, y) = elem {
-                    bez_path.move_to(Point { x, y }.to_kurbo_point(bounds));
-                } else {
-                    log::warn!("path must start with point");
-                    return;
-                }
-            }
-
-            while let Some(elem) = itr_elems.next() {
-                match elem {
-                    &PathElement::Point(x, y) => {
-                        bez_path.move_to(Point { x, y }.to_kurbo_point(bounds));
-                    }
-                    &PathElement::Line => {
-                        let Some(&PathElement::Point(x, y)) = itr_elems.next() else {
-                            log::warn!("line expects to be followed by a point");
-                            return;
-                        };
-                        bez_path.line_to(Point { x, y }.to_kurbo_point(bounds));
-                    }
-                    &PathElement::Curve(h_x, h_y) => {
-                        let Some(&PathElement::Point(x, y)) = itr_elems.next() else {
-                            log::warn!("curve expects to be followed by a point");
-                            return;
-                        };
-                        bez_path.quad_to(
-                            Point { x: h_x, y: h_y }.to_kurbo_point(bounds),
-                            Point { x, y }.to_kurbo_point(bounds),
-                        );
-                    }
-                    &PathElement::Close => {
-                        bez_path.close_path();
-                    }
-                    PathElement::Empty => (), //no-op
-                }
-            }
+            let Some(bez_path) = build_path(properties.elements.get(), bounds) else {
+                return;
+            };
 
             let computed_props = expanded_node.layout_properties.borrow();
             let tab = &computed_props.as_ref().unwrap().computed_tab;
 
-            let transformed_bez_path = Into::<kurbo::Affine>::into(tab.transform) * bez_path;
+            let transform = Into::<kurbo::Affine>::into(tab.transform);
+            let shadows = properties.shadow.get();
+            draw_shadows(rc, &layer_id, &bez_path, transform, bounds, shadows);
+            let blur = properties.blur.get().to_float();
+
+            let transformed_bez_path = transform * bez_path;
             let duplicate_transformed_bez_path = transformed_bez_path.clone();
 
             let color = properties.fill.get().to_piet_color();
-            rc.fill(&layer_id, transformed_bez_path, &color.into());
+            rc.fill_blurred(&layer_id, transformed_bez_path, &color.into(), blur);
             if properties
                 .stroke
                 .get()
@@ -126,11 +96,12 @@ impl InstanceNode for PathInstance {
                 .to_float()
                 > f64::EPSILON
             {
-                rc.stroke(
+                rc.stroke_blurred(
                     &layer_id,
                     duplicate_transformed_bez_path,
                     &properties.stroke.get().color.get().to_piet_color().into(),
                     properties.stroke.get().width.get().expect_pixels().into(),
+                    blur,
                 );
             }
         });
@@ -149,3 +120,49 @@ impl InstanceNode for PathInstance {
         f.debug_struct("Path").finish()
     }
 }
+
+/// Builds `elements` into a path, resolving `Size::Percent` coordinates against `bounds`.
+/// Returns `None`, having logged why, for malformed paths.
+pub(crate) fn build_path(elements: &[PathElement], bounds: (f64, f64)) -> Option<BezPath> {
+    let mut bez_path = BezPath::new();
+    let mut itr_elems = elements.iter();
+
+    if let Some(elem) = itr_elems.next() {
+        if let &PathElement::Point(x, y) = elem {
+            bez_path.move_to(Point { x, y }.to_kurbo_point(bounds));
+        } else {
+            log::warn!("path must start with point");
+            return None;
+        }
+    }
+
+    while let Some(elem) = itr_elems.next() {
+        match elem {
+            &PathElement::Point(x, y) => {
+                bez_path.move_to(Point { x, y }.to_kurbo_point(bounds));
+            }
+            &PathElement::Line => {
+                let Some(&PathElement::Point(x, y)) = itr_elems.next() else {
+                    log::warn!("line expects to be followed by a point");
+                    return None;
+                };
+                bez_path.line_to(Point { x, y }.to_kurbo_point(bounds));
+            }
+            &PathElement::Curve(h_x, h_y) => {
+                let Some(&PathElement::Point(x, y)) = itr_elems.next() else {
+                    log::warn!("curve expects to be followed by a point");
+                    return None;
+                };
+                bez_path.quad_to(
+                    Point { x: h_x, y: h_y }.to_kurbo_point(bounds),
+                    Point { x, y }.to_kurbo_point(bounds),
+                );
+            }
+            &PathElement::Close => {
+                bez_path.close_path();
+            }
+            PathElement::Empty => (), //no-op
+        }
+    }
+    Some(bez_path)
+}
@@ -1,11 +1,13 @@
-use kurbo::{RoundedRect, Shape};
+use kurbo::{Rect, RoundedRect, Shape};
 use pax_runtime::{declarative_macros::handle_vtable_update, BaseInstance};
-use piet::{LinearGradient, RadialGradient};
+use piet::{FixedLinearGradient, FixedRadialGradient, PaintBrush};
 
 use pax_runtime::{ExpandedNode, InstanceFlags, InstanceNode, InstantiationArgs, RuntimeContext};
 use pax_std::primitives::Rectangle;
 use pax_std::types::Fill;
 
+use crate::shadow::draw_shadows;
+
 use pax_runtime::api::{Layer, RenderContext, Size};
 
 use std::rc::Rc;
@@ -64,6 +66,18 @@ impl InstanceNode for RectangleInstance {
                 &mut properties.corner_radii,
                 context.globals(),
             );
+            handle_vtable_update(
+                context.expression_table(),
+                &expanded_node.stack,
+                &mut properties.shadow,
+                context.globals(),
+            );
+            handle_vtable_update(
+                context.expression_table(),
+                &expanded_node.stack,
+                &mut properties.blur,
+                context.globals(),
+            );
 
             // TODO: figure out best practice for nested properties struct (perhaps higher-level struct is not Property<> wrapped?)
             // handle_vtable_update!(ptc, corner_radii.bottom_left, f64);
@@ -94,45 +108,62 @@ impl InstanceNode for RectangleInstance {
         expanded_node.with_properties_unwrapped(|properties: &mut Rectangle| {
             let rect = RoundedRect::new(0.0, 0.0, width, height, properties.corner_radii.get());
             let bez_path = rect.to_path(0.1);
+            let transform = Into::<kurbo::Affine>::into(tab.transform);
+            let shadows = properties.shadow.get();
+            draw_shadows(rc, &layer_id, &bez_path, transform, tab.bounds, shadows);
+            let blur = properties.blur.get().to_float();
 
-            let transformed_bez_path = Into::<kurbo::Affine>::into(tab.transform) * bez_path;
+            let transformed_bez_path = transform * bez_path;
             let duplicate_transformed_bez_path = transformed_bez_path.clone();
 
             match properties.fill.get() {
                 Fill::Solid(color) => {
-                    rc.fill(
+                    rc.fill_blurred(
                         &layer_id,
                         transformed_bez_path,
                         &color.to_piet_color().into(),
+                        blur,
                     );
                 }
+                // Gradients are resolved against the outline here rather than left in piet's
+                // unit space, so that translucent ancestors can fade their stops
                 Fill::LinearGradient(linear) => {
-                    let linear_gradient = LinearGradient::new(
-                        Fill::to_unit_point(linear.start, (width, height)),
-                        Fill::to_unit_point(linear.end, (width, height)),
-                        Fill::to_piet_gradient_stops(linear.stops.clone()),
-                    );
-                    rc.fill(&layer_id, transformed_bez_path, &linear_gradient.into())
+                    let bbox = transformed_bez_path.bounding_box();
+                    let linear_gradient = FixedLinearGradient {
+                        start: Fill::to_unit_point(linear.start, (width, height)).resolve(bbox),
+                        end: Fill::to_unit_point(linear.end, (width, height)).resolve(bbox),
+                        stops: Fill::to_piet_gradient_stops(linear.stops.clone()),
+                    };
+                    let brush = PaintBrush::Fixed(linear_gradient.into());
+                    rc.fill_blurred(&layer_id, transformed_bez_path, &brush, blur)
                 }
                 Fill::RadialGradient(radial) => {
-                    let origin = Fill::to_unit_point(radial.start, (width, height));
-                    let center = Fill::to_unit_point(radial.end, (width, height));
-                    let gradient_stops = Fill::to_piet_gradient_stops(radial.stops.clone());
-                    let radial_gradient = RadialGradient::new(radial.radius, gradient_stops)
-                        .with_center(center)
-                        .with_origin(origin);
-                    rc.fill(&layer_id, transformed_bez_path, &radial_gradient.into());
+                    // As piet's `ScaleMode::Fill`: unit space is the square around the outline
+                    let bbox = transformed_bez_path.bounding_box();
+                    let side = bbox.width().max(bbox.height());
+                    let square = Rect::from_center_size(bbox.center(), (side, side));
+                    let origin = Fill::to_unit_point(radial.start, (width, height)).resolve(square);
+                    let center = Fill::to_unit_point(radial.end, (width, height)).resolve(square);
+                    let radial_gradient = FixedRadialGradient {
+                        center,
+                        origin_offset: origin - center,
+                        radius: radial.radius * side,
+                        stops: Fill::to_piet_gradient_stops(radial.stops.clone()),
+                    };
+                    let brush = PaintBrush::Fixed(radial_gradient.into());
+                    rc.fill_blurred(&layer_id, transformed_bez_path, &brush, blur);
                 }
             }
 
             //hack to address "phantom stroke" bug on Web
             let width: f64 = *&properties.stroke.get().width.get().expect_pixels().into();
             if width > f64::EPSILON {
-                rc.stroke(
+                rc.stroke_blurred(
                     &layer_id,
                     duplicate_transformed_bez_path,
                     &properties.stroke.get().color.get().to_piet_color().into(),
                     width,
+                    blur,
                 );
             }
         });
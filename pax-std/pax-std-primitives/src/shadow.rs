@@ -0,0 +1,54 @@
+use kurbo::{Affine, BezPath, Shape, Vec2};
+use pax_runtime::api::{Axis, RenderContext};
+use pax_std::types::Shadow;
+
+/// Draws `shadows` of a shape with the untransformed `outline`, which should be drawn over
+/// them.  Offsets and spread are in the shape's own coordinate space, resolved against `bounds`,
+/// like its outline.
+pub(crate) fn draw_shadows(
+    rc: &mut dyn RenderContext,
+    layer_id: &str,
+    outline: &BezPath,
+    transform: Affine,
+    bounds: (f64, f64),
+    shadows: &[Shadow],
+) {
+    // The first shadow is on top, so is drawn last
+    for shadow in shadows.iter().rev() {
+        let Some(grown) = grow(outline, shadow.spread.to_float()) else {
+            continue;
+        };
+        let offset = Vec2::new(
+            shadow.offset_x.evaluate(bounds, Axis::X),
+            shadow.offset_y.evaluate(bounds, Axis::Y),
+        );
+        rc.fill_blurred(
+            layer_id,
+            transform * Affine::translate(offset) * grown,
+            &shadow.color.to_piet_color().into(),
+            shadow.blur.to_float(),
+        );
+    }
+}
+
+/// `path` scaled about its center so that its bounding box grows by `amount` on each side, which
+/// is exact for rectangles and ellipses.  `None` if it would shrink away entirely.
+fn grow(path: &BezPath, amount: f64) -> Option<BezPath> {
+    if amount == 0.0 {
+        return Some(path.clone());
+    }
+    let bbox = path.bounding_box();
+    if bbox.area() <= 0.0 {
+        return None;
+    }
+    let scale_x = (bbox.width() + 2.0 * amount) / bbox.width();
+    let scale_y = (bbox.height() + 2.0 * amount) / bbox.height();
+    if !(scale_x > 0.0 && scale_y > 0.0) {
+        return None;
+    }
+    let center = bbox.center().to_vec2();
+    let scale = Affine::translate(center)
+        * Affine::scale_non_uniform(scale_x, scale_y)
+        * Affine::translate(-center);
+    Some(scale * path.clone())
+}
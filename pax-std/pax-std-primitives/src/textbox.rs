@@ -107,6 +107,29 @@ impl InstanceNode for TextboxInstance {
                     &mut patch.transform,
                     computed_tab.transform.coeffs().to_vec(),
                 ),
+                patch_if_needed(
+                    &mut old_state.opacity,
+                    &mut patch.opacity,
+                    expanded_node.get_computed_opacity(),
+                ),
+                patch_if_needed(
+                    &mut old_state.visible,
+                    &mut patch.visible,
+                    expanded_node.get_computed_visibility(),
+                ),
+                patch_if_needed(
+                    &mut old_state.pointer_events,
+                    &mut patch.pointer_events,
+                    expanded_node.get_computed_pointer_events(),
+                ),
+                patch_if_needed(
+                    &mut old_state.clip_path,
+                    &mut patch.clip_path,
+                    expanded_node
+                        .get_ancestral_clip_path()
+                        .map(|clip_path| clip_path.to_svg())
+                        .unwrap_or_default(),
+                ),
                 patch_if_needed(
                     &mut old_state.style,
                     &mut patch.style,
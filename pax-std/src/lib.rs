@@ -9,23 +9,92 @@ pub mod components {
 
 pub mod primitives {
     use pax_engine::pax;
-    use pax_runtime::api::{Color, Property, Size, StringBox};
+    use pax_runtime::api::{Color, Property, PropertyLiteral, Size, StringBox};
     use pax_runtime::numeric::Numeric;
 
     use crate::types::text::TextStyle;
     use crate::types::Fill;
+    use crate::types::{AlignItems, FlexDirection, FlexItem, JustifyContent};
+    use crate::types::{GridPlacement, GridTrack};
 
     use crate::types::PathElement;
+    use crate::types::Shadow;
     use crate::types::Stroke;
 
+    /// Clips its contents to its bounds, rounded by `corner_radii`, or to `clip_path` if set
     #[pax]
     #[primitive("pax_std_primitives::frame::FrameInstance")]
-    pub struct Frame {}
+    pub struct Frame {
+        pub corner_radii: Property<crate::types::RectangleCornerRadii>,
+        pub clip_path: Property<Vec<PathElement>>,
+    }
 
     #[pax]
     #[primitive("pax_std_primitives::group::GroupInstance")]
     pub struct Group {}
 
+    /// Lays out its children in a row or column, flexbox-style, wrapping onto further lines if
+    /// `wrap` is set.  Each child fills the cell it's assigned, sized along the line per its
+    /// entry in `items` and across it per `align_items`; its `x` and `y` offset it from the cell.
+    #[pax]
+    #[custom(Default)]
+    #[primitive("pax_std_primitives::flex::FlexInstance")]
+    pub struct Flex {
+        pub direction: Property<FlexDirection>,
+        pub wrap: Property<bool>,
+        pub justify_content: Property<JustifyContent>,
+        pub align_items: Property<AlignItems>,
+        /// Space between neighbouring items on a line
+        pub gap: Property<Size>,
+        /// Space between wrapped lines
+        pub line_gap: Property<Size>,
+        /// Grow, shrink and basis of each child, by index; children beyond the end of `items`
+        /// use `FlexItem::default()`
+        pub items: Property<Vec<FlexItem>>,
+    }
+
+    impl Default for Flex {
+        fn default() -> Self {
+            Self {
+                direction: Default::default(),
+                wrap: Default::default(),
+                justify_content: Default::default(),
+                align_items: Default::default(),
+                gap: Box::new(PropertyLiteral::new(Size::Pixels(0.into()))),
+                line_gap: Box::new(PropertyLiteral::new(Size::Pixels(0.into()))),
+                items: Default::default(),
+            }
+        }
+    }
+
+    /// Lays out its children in a grid of `columns` and `rows`, each child filling the cells
+    /// its entry in `placements` covers.  Rows (or columns) beyond those given, e.g. to fit
+    /// more children, are sized like the last given one, or as `1fr` if none are.
+    #[pax]
+    #[custom(Default)]
+    #[primitive("pax_std_primitives::grid::GridInstance")]
+    pub struct Grid {
+        pub columns: Property<Vec<GridTrack>>,
+        pub rows: Property<Vec<GridTrack>>,
+        pub column_gap: Property<Size>,
+        pub row_gap: Property<Size>,
+        /// Placement of each child, by index; children beyond the end of `placements` are
+        /// placed automatically, one cell each
+        pub placements: Property<Vec<GridPlacement>>,
+    }
+
+    impl Default for Grid {
+        fn default() -> Self {
+            Self {
+                columns: Default::default(),
+                rows: Default::default(),
+                column_gap: Box::new(PropertyLiteral::new(Size::Pixels(0.into()))),
+                row_gap: Box::new(PropertyLiteral::new(Size::Pixels(0.into()))),
+                placements: Default::default(),
+            }
+        }
+    }
+
     #[pax]
     #[primitive("pax_std_primitives::scroller::ScrollerInstance")]
     pub struct Scroller {
@@ -42,6 +111,10 @@ pub mod primitives {
         pub stroke: Property<Stroke>,
         pub fill: Property<Fill>,
         pub corner_radii: Property<crate::types::RectangleCornerRadii>,
+        /// Drawn beneath the shape, the first on top
+        pub shadow: Property<Vec<Shadow>>,
+        /// Standard deviation, in pixels, of a blur applied to the shape's fill and stroke
+        pub blur: Property<Numeric>,
     }
 
     #[pax]
@@ -50,6 +123,8 @@ pub mod primitives {
     pub struct Ellipse {
         pub stroke: Property<Stroke>,
         pub fill: Property<Fill>,
+        pub shadow: Property<Vec<Shadow>>,
+        pub blur: Property<Numeric>,
     }
 
     #[pax]
@@ -59,6 +134,8 @@ pub mod primitives {
         pub elements: Property<Vec<PathElement>>,
         pub stroke: Property<Stroke>,
         pub fill: Property<Color>,
+        pub shadow: Property<Vec<Shadow>>,
+        pub blur: Property<Numeric>,
     }
 
     #[pax]
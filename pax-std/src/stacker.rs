@@ -1,7 +1,7 @@
 use crate::primitives::*;
 use crate::types::{StackerCell, StackerDirection};
 use pax_engine::api::Numeric;
-use pax_engine::api::{Property, Size, Transform2D};
+use pax_engine::api::{Axis, Property, Size, Transform2D};
 use pax_engine::*;
 use pax_runtime::api::{NodeContext, PropertyLiteral};
 
@@ -53,12 +53,25 @@ impl Default for Stacker {
 impl Stacker {
     pub fn handle_tick(&mut self, ctx: &NodeContext) {
         let cells = self.cells.get().to_float();
-        let bounds = ctx.bounds_self;
+        let bounds = ctx.bounds_content;
 
-        let active_bound = match *self.direction.get() {
-            StackerDirection::Horizontal => bounds.0,
-            StackerDirection::Vertical => bounds.1,
+        let axis = match *self.direction.get() {
+            StackerDirection::Horizontal => Axis::X,
+            StackerDirection::Vertical => Axis::Y,
         };
+        let along = |size: (f64, f64)| match axis {
+            Axis::X => size.0,
+            Axis::Y => size.1,
+        };
+        let across = |size: (f64, f64)| match axis {
+            Axis::X => size.1,
+            Axis::Y => size.0,
+        };
+        let active_bound = along(bounds);
+
+        // `Auto` cells are as long as the child in them
+        let child_sizes = ctx.slot_children_sizes(bounds);
+        let child_length = |i: usize| child_sizes.get(i).copied().map_or(0.0, along);
 
         let gutter_calc = match *self.gutter.get() {
             Size::Pixels(pix) => pix,
@@ -66,6 +79,8 @@ impl Stacker {
             Size::Combined(pix, per) => {
                 pix + (Numeric::from(active_bound) * (per / Numeric::from(100.0)))
             }
+            // There's no content to fit a gutter to
+            Size::Auto => Numeric::from(0.0),
         };
 
         let usable_interior_space = active_bound - (cells - 1.0) * gutter_calc.to_float();
@@ -94,6 +109,7 @@ impl Stacker {
                         Size::Combined(pix, per) => {
                             *pix + (Numeric::from(active_bound) * (*per / Numeric::from(100.0)))
                         }
+                        Size::Auto => Numeric::from(child_length(i)),
                     }
                     .to_float();
                     used_space += space;
@@ -142,5 +158,20 @@ impl Stacker {
             .collect();
 
         self._cell_specs.set(new_cell_specs);
+
+        // For an `Auto`-sized stacker: its children end to end, with gutters in between, as
+        // measured in the bounds the stacker itself is laid out in
+        let content_sizes = ctx.slot_children_sizes(ctx.bounds_parent);
+        let length = content_sizes.iter().copied().map(along).sum::<f64>()
+            + gutter_calc.to_float() * content_sizes.len().saturating_sub(1) as f64;
+        let thickness = content_sizes
+            .iter()
+            .copied()
+            .map(across)
+            .fold(0.0, f64::max);
+        ctx.set_content_size(match axis {
+            Axis::X => (length, thickness),
+            Axis::Y => (thickness, length),
+        });
     }
 }
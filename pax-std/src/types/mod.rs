@@ -41,6 +41,37 @@ impl Default for Stroke {
     }
 }
 
+/// A drop shadow: the shape's outline moved by `offset_x` and `offset_y`, grown by `spread`
+/// pixels on each side, filled with `color` and blurred by `blur`, the blur's standard deviation
+/// in pixels
+#[pax]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct Shadow {
+    pub offset_x: Size,
+    pub offset_y: Size,
+    pub blur: Numeric,
+    pub spread: Numeric,
+    pub color: Color,
+}
+
+impl Shadow {
+    pub fn new(
+        offset_x: Size,
+        offset_y: Size,
+        blur: Numeric,
+        spread: Numeric,
+        color: Color,
+    ) -> Self {
+        Self {
+            offset_x,
+            offset_y,
+            blur,
+            spread,
+            color,
+        }
+    }
+}
+
 #[cfg_attr(debug_assertions, derive(Debug))]
 #[pax]
 pub struct StackerCell {
@@ -57,6 +88,153 @@ pub enum StackerDirection {
     Horizontal,
 }
 
+#[pax]
+pub enum FlexDirection {
+    #[default]
+    Row,
+    Column,
+}
+
+/// How a `Flex` distributes the free space left on each line between and around its items
+#[pax]
+pub enum JustifyContent {
+    #[default]
+    Start,
+    End,
+    Center,
+    SpaceBetween,
+    SpaceAround,
+    SpaceEvenly,
+}
+
+/// How a `Flex` places its items across each line
+#[pax]
+pub enum AlignItems {
+    /// Items fill the line
+    #[default]
+    Stretch,
+    Start,
+    End,
+    Center,
+}
+
+/// How one of a `Flex`'s items is sized along its main axis: starting at `basis`, it takes a
+/// share, proportional to `grow`, of the free space on its line, or gives up a share,
+/// proportional to `shrink` times its basis, of the space its line overflows by
+#[pax]
+#[custom(Default)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct FlexItem {
+    pub grow: Numeric,
+    pub shrink: Numeric,
+    /// Defaults to the item's own `width` in a row, or `height` in a column
+    pub basis: Option<Size>,
+}
+
+impl Default for FlexItem {
+    fn default() -> Self {
+        Self {
+            grow: 0.into(),
+            shrink: 1.into(),
+            basis: None,
+        }
+    }
+}
+
+impl FlexItem {
+    pub fn new(grow: Numeric, shrink: Numeric, basis: Size) -> Self {
+        Self {
+            grow,
+            shrink,
+            basis: Some(basis),
+        }
+    }
+
+    /// Grows and shrinks from the item's own size
+    pub fn auto(grow: Numeric, shrink: Numeric) -> Self {
+        Self {
+            grow,
+            shrink,
+            basis: None,
+        }
+    }
+}
+
+/// The size of one of a `Grid`'s columns or rows
+#[pax]
+#[custom(Default)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub enum GridTrack {
+    /// Pixels, or a percentage of the grid's width (for columns) or height (for rows)
+    Fixed(Size),
+    /// A share, by weight, of the space left once fixed tracks and gaps are taken out
+    Fraction(Numeric),
+}
+
+impl Default for GridTrack {
+    fn default() -> Self {
+        Self::Fraction(1.into())
+    }
+}
+
+impl GridTrack {
+    pub fn fixed(size: Size) -> Self {
+        Self::Fixed(size)
+    }
+
+    pub fn fr(weight: Numeric) -> Self {
+        Self::Fraction(weight)
+    }
+}
+
+/// Where one of a `Grid`'s children sits: the zero-based `row` and `column` of its top-left
+/// cell, and how many rows and columns it spans.  A child without a `row` or `column` takes the
+/// next free cells after the previous child, row by row.
+#[pax]
+#[custom(Default)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct GridPlacement {
+    pub row: Option<Numeric>,
+    pub column: Option<Numeric>,
+    pub row_span: Numeric,
+    pub column_span: Numeric,
+}
+
+impl Default for GridPlacement {
+    fn default() -> Self {
+        Self {
+            row: None,
+            column: None,
+            row_span: 1.into(),
+            column_span: 1.into(),
+        }
+    }
+}
+
+impl GridPlacement {
+    pub fn at(row: Numeric, column: Numeric) -> Self {
+        Self::area(row, column, 1.into(), 1.into())
+    }
+
+    /// Placed automatically, spanning `row_span` rows and `column_span` columns
+    pub fn span(row_span: Numeric, column_span: Numeric) -> Self {
+        Self {
+            row_span,
+            column_span,
+            ..Default::default()
+        }
+    }
+
+    pub fn area(row: Numeric, column: Numeric, row_span: Numeric, column_span: Numeric) -> Self {
+        Self {
+            row: Some(row),
+            column: Some(column),
+            row_span,
+            column_span,
+        }
+    }
+}
+
 #[pax]
 pub enum SidebarDirection {
     Left,
@@ -132,12 +310,14 @@ impl Fill {
             Size::Pixels(val) => val.to_float() / width,
             Size::Percent(val) => val.to_float() / 100.0,
             Size::Combined(pix, per) => (pix.to_float() / width) + (per.to_float() / 100.0),
+            Size::Auto => 1.0,
         };
 
         let normalized_y = match y {
             Size::Pixels(val) => val.to_float() / height,
             Size::Percent(val) => val.to_float() / 100.0,
             Size::Combined(pix, per) => (pix.to_float() / width) + (per.to_float() / 100.0),
+            Size::Auto => 1.0,
         };
         UnitPoint::new(normalized_x, normalized_y)
     }
@@ -155,7 +335,7 @@ impl Fill {
                         color: gradient_stop.color.to_piet_color(),
                     });
                 }
-                Size::Combined(_, _) => {
+                Size::Combined(_, _) | Size::Auto => {
                     panic!("Gradient stops must be specified in percentages");
                 }
             }
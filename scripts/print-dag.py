@@ -14,6 +14,7 @@ PACKAGES = [
     "pax-runtime",
     "pax-example",
     "pax-engine",
+    "pax-headless",
     "pax-language-server",
     "pax-macro",
     "pax-message",
@@ -34,6 +34,7 @@ PACKAGES = [
     "pax-runtime",
     "pax-runtime-api",
     "pax-engine",
+    "pax-headless",
     "pax-language-server",
     "pax-macro",
     "pax-manifest",